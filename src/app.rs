@@ -1,4 +1,5 @@
 use crate::models::utils::secret::Secret;
+use crate::views::main_page::accounting::Accounting;
 use crate::views::main_page::dashboard::Dashboard;
 use crate::views::main_page::gantt::GanttChart;
 use crate::views::menu::menu::Menu;
@@ -13,6 +14,7 @@ use eframe::egui::{self, CentralPanel, TopBottomPanel};
 pub struct App {
     pub dashboard_view: Dashboard,
     pub gantt_view: GanttChart,
+    pub accounting_view: Accounting,
     pub authentification_view: Authentification,
     pub menu: Menu,
     pub secret: Secret,
@@ -25,7 +27,8 @@ impl App {
         let app = App {
             secret: Secret::default(),
             dashboard_view: Dashboard::default(),
-            gantt_view: GanttChart::default(),
+            gantt_view: GanttChart::new(),
+            accounting_view: Accounting::default(),
             authentification_view: Authentification::default(),
             menu: Menu::default(),
             tools: Tools::default(),
@@ -41,10 +44,27 @@ impl eframe::App for App {
         self.secret.update(ctx);
         self.secret.draw_snake_game(ctx);
 
+        // The arrows belong to the snake game while it is shown (e.g. not to the Gantt canvas)
+        if self.secret.is_game_shown() {
+            ctx.memory_mut(|memory| {
+                if let Some(id) = memory.focused() {
+                    memory.surrender_focus(id);
+                }
+            });
+        }
+
         TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             self.menu.render(ui, &mut self.application_context);
         });
 
+        // The period of the accounting view is only fetched while the view is shown
+        if !matches!(
+            self.application_context.view_type,
+            crate::views::view::ViewType::Accounting
+        ) {
+            self.application_context.accounting_period = None;
+        }
+
         // Check for updates
         self.application_context.check_data_update();
 
@@ -61,6 +81,10 @@ impl eframe::App for App {
                 crate::views::view::ViewType::Gantt => {
                     self.gantt_view.render(ui, &mut self.application_context);
                 }
+                crate::views::view::ViewType::Accounting => {
+                    self.accounting_view
+                        .render(ui, &mut self.application_context);
+                }
                 crate::views::view::ViewType::Authentification => {
                     self.authentification_view
                         .render(ui, &mut self.application_context);
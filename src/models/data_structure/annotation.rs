@@ -0,0 +1,121 @@
+use crate::models::utils::json_file::{load_json, save_json};
+use eframe::egui::Color32;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+/**
+ * Kind of an annotation, used for its color
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum AnnotationKind {
+    Maintenance,
+    Incident,
+    Note,
+}
+
+impl AnnotationKind {
+    pub fn get_label(&self) -> String {
+        match self {
+            AnnotationKind::Maintenance => t!("app.annotations.kind.maintenance").to_string(),
+            AnnotationKind::Incident => t!("app.annotations.kind.incident").to_string(),
+            AnnotationKind::Note => t!("app.annotations.kind.note").to_string(),
+        }
+    }
+
+    pub fn get_color(&self) -> Color32 {
+        match self {
+            AnnotationKind::Maintenance => Color32::from_rgb(255, 165, 0),
+            AnnotationKind::Incident => Color32::from_rgb(220, 50, 50),
+            AnnotationKind::Note => Color32::from_rgb(70, 130, 180),
+        }
+    }
+}
+
+/**
+ * A time range or an instant annotated by the user, optionally scoped to clusters or hosts
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub id: u64,
+    pub kind: AnnotationKind,
+    pub label: String,
+    pub description: String,
+    pub start: i64,            // Start timestamp, in seconds
+    pub end: Option<i64>,      // End timestamp, None for an instant
+    pub clusters: Vec<String>, // Clusters concerned, every cluster if empty
+    pub hosts: Vec<String>,    // Hosts concerned, every host if empty
+}
+
+impl Annotation {
+    /**
+     * Returns the end of the annotation, its start for an instant
+     */
+    pub fn get_end(&self) -> i64 {
+        self.end.unwrap_or(self.start).max(self.start)
+    }
+
+    /**
+     * Returns true if the annotation concerns every cluster and host
+     */
+    pub fn is_global(&self) -> bool {
+        self.clusters.is_empty() && self.hosts.is_empty()
+    }
+
+    /**
+     * Returns the names of the clusters and hosts of the scope
+     */
+    pub fn scope_names(&self) -> Vec<String> {
+        self.clusters.iter().chain(&self.hosts).cloned().collect()
+    }
+
+    pub fn overlaps(&self, start: i64, end: i64) -> bool {
+        self.start <= end && self.get_end() >= start
+    }
+}
+
+/*
+ * `Annotations` is the list of the annotations, stored in a JSON file of the working
+ * directory so that it can be shared between the users of a same installation
+ * or copied to another one.
+ */
+#[derive(Default, Serialize, Deserialize)]
+pub struct Annotations {
+    pub annotations: Vec<Annotation>,
+}
+
+impl Annotations {
+    pub const FILE_PATH: &'static str = "annotations.json";
+
+    /**
+     * Loads the annotations from a file, an absent or invalid file gives no annotation
+     */
+    pub fn load_from_file(file_path: &str) -> Self {
+        load_json(file_path).unwrap_or_default()
+    }
+
+    pub fn save_to_file(&self, file_path: &str) -> Result<(), String> {
+        save_json(self, file_path)
+    }
+
+    /**
+     * Adds an annotation, giving it a new id
+     */
+    pub fn add(&mut self, mut annotation: Annotation) {
+        annotation.id = self.annotations.iter().map(|a| a.id + 1).max().unwrap_or(1);
+        self.annotations.push(annotation);
+        self.annotations.sort_by_key(|a| a.start);
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.annotations.retain(|a| a.id != id);
+    }
+
+    /**
+     * Returns the annotations overlapping the period
+     */
+    pub fn overlapping(&self, start: i64, end: i64) -> impl Iterator<Item = &Annotation> {
+        self.annotations
+            .iter()
+            .filter(move |annotation| annotation.overlaps(start, end))
+    }
+}
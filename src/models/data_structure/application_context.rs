@@ -1,7 +1,10 @@
+use super::annotation::Annotations;
 use super::cluster::Cluster;
 use super::filters::JobFilters;
 use super::job::Job;
+use super::job_cache::{JobBatch, JobCache};
 use super::resource::Resource;
+use super::resource_index::ResourceIndex;
 use super::strata::Strata;
 use crate::models::data_structure::cpu::Cpu;
 use crate::models::data_structure::host::Host;
@@ -22,9 +25,16 @@ pub struct ApplicationContext {
     pub all_jobs: Vec<Job>,
     pub swap_all_jobs: Vec<Job>, // Used to store all jobs when refreshing (and swapped with all_jobs when refreshing is done)
     pub filtered_jobs: Vec<Job>, // Subset of all_jobs that match the filters
+    pub job_cache: JobCache,     // Jobs of every period fetched so far, all_jobs is built from it
+    pub jobs_generation: u64, // Incremented when the filtered jobs or the clusters change, views cache what they compute from them
+    pub comparison_offset_s: Option<i64>, // Offset of the compared window from the current one, None when not comparing
+    pub comparison_jobs: Vec<Job>,        // Jobs of the compared window that match the filters
+    pub comparison_generation: u64,       // Incremented when the jobs of the compared window change
+    pub accounting_period: Option<(i64, i64)>, // Period shown by the accounting view, None while it is not shown
 
     pub all_clusters: Vec<Cluster>,
     pub swap_all_clusters: Vec<Cluster>, // Used to store all clusters when refreshing (and swapped with all_clusters when refreshing is done)
+    pub resource_index: ResourceIndex, // Resource id -> cluster/host lookup, rebuilt with all_clusters
 
     // Application view state
     pub start_date: Arc<Mutex<DateTime<Local>>>,
@@ -35,10 +45,12 @@ pub struct ApplicationContext {
     pub is_refreshing: Arc<Mutex<bool>>,
     pub refresh_rate: Arc<Mutex<u64>>,
     pub filters: JobFilters,
+    pub requested_time_range: Option<(DateTime<Local>, DateTime<Local>)>, // Range chosen with the time range picker, applied by the Gantt
+    pub annotations: Annotations, // Maintenance windows and notes shown on the timeline
 
     // Communication channels for background data updates
-    pub jobs_receiver: Receiver<Vec<Job>>,
-    pub jobs_sender: Sender<Vec<Job>>,
+    pub jobs_receiver: Receiver<JobBatch>,
+    pub jobs_sender: Sender<JobBatch>,
     pub resources_receiver: Receiver<Vec<Strata>>,
     pub resources_sender: Sender<Vec<Strata>>,
 
@@ -48,8 +60,14 @@ pub struct ApplicationContext {
 
 impl ApplicationContext {
     pub fn check_job_update(&mut self) {
-        if let Ok(new_jobs) = self.jobs_receiver.try_recv() {
-            self.swap_all_jobs = new_jobs;
+        // Merge every received batch into the cache, the jobs of other periods are kept
+        let mut received = false;
+        while let Ok(batch) = self.jobs_receiver.try_recv() {
+            self.job_cache.insert(batch);
+            received = true;
+        }
+        if received {
+            self.swap_all_jobs = self.job_cache.jobs();
             self.is_loading = false;
         }
     }
@@ -90,9 +108,18 @@ impl ApplicationContext {
                                         "Dead" => super::resource::ResourceState::Dead,
                                         "Alive" => super::resource::ResourceState::Alive,
                                         "Absent" => super::resource::ResourceState::Absent,
+                                        "Suspected" => super::resource::ResourceState::Suspected,
                                         _ => super::resource::ResourceState::Unknown,
                                     },
                                     thread_count: resource.thread_count.unwrap_or(0) as i32,
+                                    gpu_model: resource
+                                        .gpu_model
+                                        .clone()
+                                        .filter(|model| !model.is_empty()),
+                                    comment: resource
+                                        .comment
+                                        .clone()
+                                        .filter(|comment| !comment.is_empty()),
                                 }],
                                 core_count: resource.core_count.unwrap_or(0) as i32,
                                 cpufreq: resource
@@ -147,9 +174,18 @@ impl ApplicationContext {
                                         "Dead" => super::resource::ResourceState::Dead,
                                         "Alive" => super::resource::ResourceState::Alive,
                                         "Absent" => super::resource::ResourceState::Absent,
+                                        "Suspected" => super::resource::ResourceState::Suspected,
                                         _ => super::resource::ResourceState::Unknown,
                                     },
                                     thread_count: resource.thread_count.unwrap_or(0) as i32,
+                                    gpu_model: resource
+                                        .gpu_model
+                                        .clone()
+                                        .filter(|model| !model.is_empty()),
+                                    comment: resource
+                                        .comment
+                                        .clone()
+                                        .filter(|comment| !comment.is_empty()),
                                 }],
                                 core_count: resource.core_count.unwrap_or(0) as i32,
                                 cpufreq: resource
@@ -201,9 +237,18 @@ impl ApplicationContext {
                                         "Dead" => super::resource::ResourceState::Dead,
                                         "Alive" => super::resource::ResourceState::Alive,
                                         "Absent" => super::resource::ResourceState::Absent,
+                                        "Suspected" => super::resource::ResourceState::Suspected,
                                         _ => super::resource::ResourceState::Unknown,
                                     },
                                     thread_count: resource.thread_count.unwrap_or(0) as i32,
+                                    gpu_model: resource
+                                        .gpu_model
+                                        .clone()
+                                        .filter(|model| !model.is_empty()),
+                                    comment: resource
+                                        .comment
+                                        .clone()
+                                        .filter(|comment| !comment.is_empty()),
                                 }],
                                 core_count: resource.core_count.unwrap_or(0) as i32,
                                 cpufreq: resource
@@ -248,9 +293,18 @@ impl ApplicationContext {
                                     "Dead" => super::resource::ResourceState::Dead,
                                     "Alive" => super::resource::ResourceState::Alive,
                                     "Absent" => super::resource::ResourceState::Absent,
+                                    "Suspected" => super::resource::ResourceState::Suspected,
                                     _ => super::resource::ResourceState::Unknown,
                                 },
                                 thread_count: resource.thread_count.unwrap_or(0) as i32,
+                                gpu_model: resource
+                                    .gpu_model
+                                    .clone()
+                                    .filter(|model| !model.is_empty()),
+                                comment: resource
+                                    .comment
+                                    .clone()
+                                    .filter(|comment| !comment.is_empty()),
                             });
 
                             // add the resource id to the cpu, the host and the cluster
@@ -273,20 +327,30 @@ impl ApplicationContext {
                     let mut dead_count = 0;
                     let mut alive_count = 0;
                     let mut absent_count = 0;
+                    let mut suspected_count = 0;
                     for cpu in host.cpus.iter() {
                         for resource in cpu.resources.iter() {
                             match resource.state {
                                 ResourceState::Dead => dead_count += 1,
                                 ResourceState::Alive => alive_count += 1,
                                 ResourceState::Absent => absent_count += 1,
+                                ResourceState::Suspected => suspected_count += 1,
                                 _ => (),
                             }
                         }
                     }
-                    if dead_count >= alive_count && dead_count >= absent_count {
+                    if dead_count >= alive_count
+                        && dead_count >= absent_count
+                        && dead_count >= suspected_count
+                    {
                         host.state = ResourceState::Dead;
-                    } else if absent_count >= dead_count && absent_count >= alive_count {
+                    } else if absent_count >= dead_count
+                        && absent_count >= alive_count
+                        && absent_count >= suspected_count
+                    {
                         host.state = ResourceState::Absent;
+                    } else if suspected_count >= alive_count {
+                        host.state = ResourceState::Suspected;
                     } else if alive_count > dead_count && alive_count > absent_count {
                         host.state = ResourceState::Alive;
                     } else {
@@ -300,18 +364,28 @@ impl ApplicationContext {
                 let mut dead_count = 0;
                 let mut alive_count = 0;
                 let mut absent_count = 0;
+                let mut suspected_count = 0;
                 for host in cluster.hosts.iter() {
                     match host.state {
                         ResourceState::Dead => dead_count += 1,
                         ResourceState::Alive => alive_count += 1,
                         ResourceState::Absent => absent_count += 1,
+                        ResourceState::Suspected => suspected_count += 1,
                         _ => (),
                     }
                 }
-                if dead_count >= alive_count && dead_count >= absent_count {
+                if dead_count >= alive_count
+                    && dead_count >= absent_count
+                    && dead_count >= suspected_count
+                {
                     cluster.state = ResourceState::Dead;
-                } else if absent_count >= dead_count && absent_count >= alive_count {
+                } else if absent_count >= dead_count
+                    && absent_count >= alive_count
+                    && absent_count >= suspected_count
+                {
                     cluster.state = ResourceState::Absent;
+                } else if suspected_count >= alive_count {
+                    cluster.state = ResourceState::Suspected;
                 } else if alive_count > dead_count && alive_count > absent_count {
                     cluster.state = ResourceState::Alive;
                 } else {
@@ -334,12 +408,15 @@ impl ApplicationContext {
 
             self.all_jobs = self.swap_all_jobs.clone();
             self.all_clusters = self.swap_all_clusters.clone();
+            self.resource_index = ResourceIndex::build(&self.all_clusters);
+            self.jobs_generation += 1;
         }
     }
 
     pub fn check_data_update(&mut self) {
         self.check_job_update();
         self.check_ressource_update();
+        self.fetch_missing_periods();
 
         // set filter date to the date of the app context
         self.filters
@@ -379,62 +456,91 @@ impl ApplicationContext {
      * - Job state filtering
      * - Time range filtering
      * - Cluster resource filtering
+     * - Jobs selected in the Gantt chart
      */
     pub fn filter_jobs(&mut self) {
-        self.filtered_jobs = self
+        let period = (self.filters.scheduled_start_time, self.filters.wall_time);
+        let filtered_jobs: Vec<Job> = self
             .all_jobs
             .iter()
-            .filter(|job| {
-                job.id == 0
-                    || (self
-                        .filters
-                        .owners
-                        .as_ref()
-                        .map_or(true, |owners| owners.contains(&job.owner)))
-                        && (self
-                            .filters
-                            .states
-                            .as_ref()
-                            .map_or(true, |states| states.contains(&job.state)))
-                        && (((self
-                            .filters
-                            .scheduled_start_time
-                            .map_or(true, |time| time <= job.scheduled_start))
-                            && (self
-                                .filters
-                                .wall_time
-                                .map_or(true, |time| time >= job.scheduled_start)))
-                            || ((self
-                                .filters
-                                .scheduled_start_time
-                                .map_or(true, |time| time <= job.get_end_date()))
-                                && (self
-                                    .filters
-                                    .wall_time
-                                    .map_or(true, |time| time >= job.get_end_date())))
-                            || ((self
-                                .filters
-                                .scheduled_start_time
-                                .map_or(true, |time| time >= job.start_time))
-                                && (self
-                                    .filters
-                                    .wall_time
-                                    .map_or(true, |time| time <= job.get_end_date()))))
-                        && (self.filters.clusters.is_none() || {
-                            let selected_clusters = self.filters.clusters.as_ref().unwrap();
-                            selected_clusters.iter().any(|cluster| {
-                                cluster.hosts.iter().any(|host| {
-                                    host.cpus.iter().any(|cpu| {
-                                        cpu.resources.iter().any(|resource| {
-                                            job.assigned_resources.contains(&resource.id)
-                                        })
-                                    })
-                                })
-                            })
-                        })
-            })
+            .filter(|job| self.matches_filters(job, period))
             .cloned() // Clone filtred jobs here
             .collect();
+
+        // Only a change of the displayed jobs starts a new generation
+        if Self::jobs_changed(&filtered_jobs, &self.filtered_jobs) {
+            self.jobs_generation += 1;
+        }
+        self.filtered_jobs = filtered_jobs;
+
+        // The same filters are applied to the compared window
+        let comparison_jobs: Vec<Job> = match (self.comparison_offset_s, period) {
+            (Some(offset_s), (Some(start_s), Some(end_s))) => self
+                .all_jobs
+                .iter()
+                .filter(|job| {
+                    self.matches_filters(job, (Some(start_s + offset_s), Some(end_s + offset_s)))
+                })
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        };
+        if Self::jobs_changed(&comparison_jobs, &self.comparison_jobs) {
+            self.comparison_generation += 1;
+        }
+        self.comparison_jobs = comparison_jobs;
+    }
+
+    /**
+     * Returns true if the job passes the filters during the period (start, end)
+     */
+    pub fn matches_filters(
+        &self,
+        job: &Job,
+        (start_s, end_s): (Option<i64>, Option<i64>),
+    ) -> bool {
+        job.id == 0
+            || (self
+                .filters
+                .owners
+                .as_ref()
+                .map_or(true, |owners| owners.contains(&job.owner)))
+                && (self
+                    .filters
+                    .states
+                    .as_ref()
+                    .map_or(true, |states| states.contains(&job.state)))
+                && (((start_s.map_or(true, |time| time <= job.scheduled_start))
+                    && (end_s.map_or(true, |time| time >= job.scheduled_start)))
+                    || ((start_s.map_or(true, |time| time <= job.get_end_date()))
+                        && (end_s.map_or(true, |time| time >= job.get_end_date())))
+                    || ((start_s.map_or(true, |time| time >= job.start_time))
+                        && (end_s.map_or(true, |time| time <= job.get_end_date()))))
+                && self
+                    .filters
+                    .job_ids
+                    .as_ref()
+                    .map_or(true, |job_ids| job_ids.contains(&job.id))
+                && (!self.filters.has_resource_selection()
+                    || job.assigned_resources.iter().any(|resource_id| {
+                        self.filters
+                            .matches_resource(*resource_id, &self.resource_index)
+                    }))
+    }
+
+    /**
+     * Returns true if the displayed jobs changed, starting a new generation
+     */
+    fn jobs_changed(new_jobs: &[Job], old_jobs: &[Job]) -> bool {
+        new_jobs.len() != old_jobs.len()
+            || new_jobs.iter().zip(old_jobs.iter()).any(|(new, old)| {
+                new.id != old.id
+                    || new.state != old.state
+                    || new.scheduled_start != old.scheduled_start
+                    || new.stop_time != old.stop_time
+                    || new.walltime != old.walltime
+                    || new.assigned_resources != old.assigned_resources
+            })
     }
 }
 
@@ -448,7 +554,14 @@ impl Default for ApplicationContext {
         let now: DateTime<Local> = Local::now();
         let mut context = Self {
             all_jobs: Vec::new(),
+            job_cache: JobCache::default(),
+            jobs_generation: 0,
+            comparison_offset_s: None,
+            comparison_jobs: Vec::new(),
+            comparison_generation: 0,
+            accounting_period: None,
             all_clusters: Vec::new(),
+            resource_index: ResourceIndex::default(),
 
             swap_all_jobs: Vec::new(),
             swap_all_clusters: Vec::new(),
@@ -461,6 +574,8 @@ impl Default for ApplicationContext {
 
             filtered_jobs: Vec::new(),
             filters: JobFilters::default(),
+            requested_time_range: None,
+            annotations: Annotations::load_from_file(Annotations::FILE_PATH),
             start_date: Arc::new(Mutex::new(now - chrono::Duration::hours(1))),
             end_date: Arc::new(Mutex::new(now + chrono::Duration::hours(1))),
             view_type: ViewType::Gantt,
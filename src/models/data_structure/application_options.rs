@@ -1,3 +1,4 @@
+use chrono_tz::Tz;
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -12,11 +13,37 @@ pub enum ThemeOption {
     Dark
 }
 
+/**
+ * Timezone of the displayed timestamps, an IANA zone is stored by its name (e.g. "Europe/Paris")
+ */
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum TimezoneOption {
+    #[default]
+    Local,
+    Utc,
+    Named(String),
+}
+
+impl TimezoneOption {
+    /**
+     * Returns the timezone to use, None for the local one or an unknown name
+     */
+    pub fn to_tz(&self) -> Option<Tz> {
+        match self {
+            TimezoneOption::Local => None,
+            TimezoneOption::Utc => Some(Tz::UTC),
+            TimezoneOption::Named(name) => name.parse().ok(),
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ApplicationOptions {
     pub selected_language: LanguageOption,
     pub font_size: i32,
-    pub selected_theme: ThemeOption
+    pub selected_theme: ThemeOption,
+    #[serde(default)] // Options saved before the timezone was added
+    pub selected_timezone: TimezoneOption
 }
 
 impl Default for ApplicationOptions {
@@ -24,7 +51,8 @@ impl Default for ApplicationOptions {
         ApplicationOptions {
             selected_language: LanguageOption::English,
             font_size: 14,
-            selected_theme: ThemeOption::Dark
+            selected_theme: ThemeOption::Dark,
+            selected_timezone: TimezoneOption::Local
         }
     }
 }
\ No newline at end of file
@@ -1,4 +1,15 @@
-use super::{cluster::Cluster, job::JobState};
+use super::{cluster::Cluster, host::Host, job::JobState, resource_index::ResourceIndex};
+use std::collections::BTreeSet;
+
+/**
+ * Selection state of a node (cluster or host) in the resource filter
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionState {
+    Selected,   // The node and everything below it is selected
+    Partial,    // Only some of the children of the node are selected
+    Unselected, // Nothing below the node is selected
+}
 
 #[derive(Default, Debug, Clone)]
 
@@ -7,7 +18,15 @@ pub struct JobFilters {
     pub states: Option<Vec<JobState>>,
     pub scheduled_start_time: Option<i64>,
     pub wall_time: Option<i64>,
-    pub clusters: Option<Vec<Cluster>>,
+
+    // Resource selection, all sets empty means no resource filtering
+    // A resource is kept if its cluster, its host or its id is selected
+    pub clusters: BTreeSet<String>, // Fully selected clusters
+    pub hosts: BTreeSet<String>,    // Fully selected hosts (their cluster is not fully selected)
+    pub resources: BTreeSet<u32>,   // Selected resources (their host is not fully selected)
+
+    // Jobs selected in the Gantt chart, None means no restriction
+    pub job_ids: Option<BTreeSet<u32>>,
 }
 
 #[allow(dead_code)]
@@ -23,6 +42,9 @@ impl JobFilters {
             scheduled_start_time: filter.scheduled_start_time,
             wall_time: filter.wall_time,
             clusters: filter.clusters.clone(),
+            hosts: filter.hosts.clone(),
+            resources: filter.resources.clone(),
+            job_ids: filter.job_ids.clone(),
         }
     }
 
@@ -42,11 +64,139 @@ impl JobFilters {
         self.wall_time = Some(wall_time);
     }
 
-    pub fn set_clusters(&mut self, selected_clusters: Option<Vec<Cluster>>) {
-        self.clusters = selected_clusters;
-    }
-
     pub fn reset(&mut self) {
         *self = Self::default();
     }
+
+    /**
+     * Returns true if the user restricted the jobs to some clusters, hosts or resources
+     */
+    pub fn has_resource_selection(&self) -> bool {
+        !self.clusters.is_empty() || !self.hosts.is_empty() || !self.resources.is_empty()
+    }
+
+    /**
+     * Returns true if the resource passes the resource selection
+     */
+    pub fn matches_resource(&self, resource_id: u32, index: &ResourceIndex) -> bool {
+        if !self.has_resource_selection() || self.resources.contains(&resource_id) {
+            return true;
+        }
+        index.get(resource_id).is_some_and(|location| {
+            self.clusters.contains(&location.cluster) || self.hosts.contains(&location.host)
+        })
+    }
+
+    pub fn cluster_selection(&self, cluster: &Cluster) -> SelectionState {
+        if self.clusters.contains(&cluster.name) {
+            SelectionState::Selected
+        } else if cluster
+            .hosts
+            .iter()
+            .any(|host| self.host_selection(&cluster.name, host) != SelectionState::Unselected)
+        {
+            SelectionState::Partial
+        } else {
+            SelectionState::Unselected
+        }
+    }
+
+    pub fn host_selection(&self, cluster_name: &str, host: &Host) -> SelectionState {
+        if self.clusters.contains(cluster_name) || self.hosts.contains(&host.name) {
+            SelectionState::Selected
+        } else if host.resource_ids.iter().any(|id| self.resources.contains(id)) {
+            SelectionState::Partial
+        } else {
+            SelectionState::Unselected
+        }
+    }
+
+    pub fn is_resource_selected(&self, cluster_name: &str, host_name: &str, resource_id: u32) -> bool {
+        self.clusters.contains(cluster_name)
+            || self.hosts.contains(host_name)
+            || self.resources.contains(&resource_id)
+    }
+
+    pub fn set_cluster_selected(&mut self, cluster: &Cluster, selected: bool) {
+        for host in &cluster.hosts {
+            self.hosts.remove(&host.name);
+            for id in &host.resource_ids {
+                self.resources.remove(id);
+            }
+        }
+        if selected {
+            self.clusters.insert(cluster.name.clone());
+        } else {
+            self.clusters.remove(&cluster.name);
+        }
+    }
+
+    pub fn set_host_selected(&mut self, cluster: &Cluster, host: &Host, selected: bool) {
+        self.expand_cluster(cluster);
+        for id in &host.resource_ids {
+            self.resources.remove(id);
+        }
+        if selected {
+            self.hosts.insert(host.name.clone());
+        } else {
+            self.hosts.remove(&host.name);
+        }
+        self.collapse_cluster(cluster);
+    }
+
+    pub fn set_resource_selected(
+        &mut self,
+        cluster: &Cluster,
+        host: &Host,
+        resource_id: u32,
+        selected: bool,
+    ) {
+        self.expand_cluster(cluster);
+        if self.hosts.remove(&host.name) {
+            self.resources.extend(host.resource_ids.iter().copied());
+        }
+        if selected {
+            self.resources.insert(resource_id);
+        } else {
+            self.resources.remove(&resource_id);
+        }
+        self.collapse_cluster(cluster);
+    }
+
+    /**
+     * Replaces a fully selected cluster by the list of its hosts
+     */
+    fn expand_cluster(&mut self, cluster: &Cluster) {
+        if self.clusters.remove(&cluster.name) {
+            for host in &cluster.hosts {
+                self.hosts.insert(host.name.clone());
+            }
+        }
+    }
+
+    /**
+     * Replaces fully selected resources by their host and fully selected hosts by their cluster,
+     * so that each selection is stored at the highest possible level
+     */
+    fn collapse_cluster(&mut self, cluster: &Cluster) {
+        for host in &cluster.hosts {
+            if !host.resource_ids.is_empty()
+                && host.resource_ids.iter().all(|id| self.resources.contains(id))
+            {
+                for id in &host.resource_ids {
+                    self.resources.remove(id);
+                }
+                self.hosts.insert(host.name.clone());
+            }
+        }
+
+        if !cluster.hosts.is_empty()
+            && cluster.hosts.iter().all(|host| self.hosts.contains(&host.name))
+        {
+            for host in &cluster.hosts {
+                self.hosts.remove(&host.name);
+            }
+            self.clusters.insert(cluster.name.clone());
+        }
+    }
 }
@@ -120,6 +120,23 @@ impl JobState {
 
 use crate::views::components::dashboard_components::job_table_sorting::JobSortable;
 
+/**
+ * Part of the extent of a job in the Gantt chart
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JobSegmentKind {
+    Predicted, // Predicted run of a job not started yet, from its scheduled start
+    Elapsed,   // Run of the job, until now or until its stop
+    Remaining, // Rest of the walltime reserved by a running job
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JobSegment {
+    pub kind: JobSegmentKind,
+    pub start: i64, // Start timestamp of the segment
+    pub end: i64,   // End timestamp of the segment
+}
+
 #[derive(Clone, Debug)]
 
 pub struct Job {
@@ -130,6 +147,7 @@ pub struct Job {
     pub walltime: i64,
     pub message: Option<String>,
     pub queue: String,
+    pub project: String,
     pub assigned_resources: Vec<u32>,
     pub scheduled_start: i64,
     pub submission_time: i64,
@@ -202,6 +220,10 @@ impl JobSortable for Job {
             self.start_time + self.walltime
         }
     }
+
+    fn get_runtime(&self, now: i64) -> i64 {
+        Job::get_runtime(self, now)
+    }
 }
 
 impl Job {
@@ -213,6 +235,7 @@ impl Job {
         println!("Walltime: {}", self.walltime);
         println!("Message: {:?}", self.message);
         println!("Queue: {}", self.queue);
+        println!("Project: {}", self.project);
         println!("Assigned Resources: {:?}", self.assigned_resources);
         println!("Scheduled Start: {:?}", self.scheduled_start);
         println!("Submission Time: {}", self.submission_time);
@@ -224,6 +247,72 @@ impl Job {
         println!("Host: {:?}", self.hosts);
     }
 
+    /**
+     * Splits the extent of the job into its predicted, elapsed and remaining parts
+     * A job not started yet is predicted from its scheduled start for its whole walltime,
+     * a running job has its elapsed run followed by the rest of its walltime
+     */
+    pub fn get_segments(&self, now: i64) -> Vec<JobSegment> {
+        if self.start_time <= 0 {
+            return vec![JobSegment {
+                kind: JobSegmentKind::Predicted,
+                start: self.scheduled_start,
+                end: self.scheduled_start + self.walltime,
+            }];
+        }
+
+        let elapsed_end = if self.stop_time > 0 {
+            self.stop_time
+        } else {
+            now.max(self.start_time)
+        };
+        let mut segments = vec![JobSegment {
+            kind: JobSegmentKind::Elapsed,
+            start: self.start_time,
+            end: elapsed_end,
+        }];
+
+        let reserved_end = self.start_time + self.walltime;
+        if self.stop_time <= 0 && elapsed_end < reserved_end {
+            segments.push(JobSegment {
+                kind: JobSegmentKind::Remaining,
+                start: elapsed_end,
+                end: reserved_end,
+            });
+        }
+        segments
+    }
+
+    /**
+     * Returns the time the job has been running, until now if it is not finished
+     */
+    pub fn get_runtime(&self, now: i64) -> i64 {
+        if self.start_time <= 0 {
+            0
+        } else if self.stop_time > 0 {
+            (self.stop_time - self.start_time).max(0)
+        } else {
+            (now - self.start_time).max(0)
+        }
+    }
+
+    /**
+     * Returns the core-hours consumed by the job, each assigned resource being a core
+     */
+    pub fn get_core_hours(&self, now: i64) -> f64 {
+        self.assigned_resources.len() as f64 * self.get_runtime(now) as f64 / 3600.0
+    }
+
+    /**
+     * Returns true if the job was stopped because it reached its walltime
+     */
+    pub fn is_killed_at_walltime(&self) -> bool {
+        self.start_time > 0
+            && self.stop_time > 0
+            && self.walltime > 0
+            && self.stop_time - self.start_time >= self.walltime
+    }
+
     // Based on gantt color return a tuple of two colors (the second one is darker)
     pub fn get_gantt_color(&self) -> (egui::Color32, egui::Color32) {
         let r = self.gantt_color.r() as f32;
@@ -0,0 +1,130 @@
+use super::job::Job;
+use std::collections::BTreeMap;
+
+/**
+ * Jobs fetched in the background for a period (in seconds, margin included)
+ */
+pub struct JobBatch {
+    pub start_s: i64,   // Start of the fetched period
+    pub end_s: i64,     // End of the fetched period
+    pub jobs: Vec<Job>, // Jobs of the period
+}
+
+/*
+ * `JobCache` keeps the jobs of every period fetched so far, so that panning back
+ * to an already loaded period doesn't need a new fetch.
+ * The loaded periods are kept sorted and merged, the periods being fetched are
+ * kept aside so that the same period is not requested twice.
+ */
+#[derive(Default)]
+pub struct JobCache {
+    jobs: BTreeMap<u32, Job>,      // Cached jobs by id, the latest fetch wins
+    loaded: Vec<(i64, i64)>,       // Sorted and disjoint loaded periods
+    pending: Vec<(i64, i64, i64)>, // Periods being fetched, with the time of the request
+}
+
+impl JobCache {
+    const PENDING_TIMEOUT_S: i64 = 60; // A failed fetch is requested again after this delay
+    const MIN_MISSING_S: i64 = 60; // Smaller gaps are not worth a fetch
+
+    /**
+     * Merges the jobs of a batch into the cache and marks its period as loaded
+     */
+    pub fn insert(&mut self, batch: JobBatch) {
+        for job in batch.jobs {
+            self.jobs.insert(job.id, job);
+        }
+        self.add_loaded(batch.start_s, batch.end_s);
+
+        // The pending periods now covered by the loaded ones are done
+        let loaded = &self.loaded;
+        self.pending.retain(|(start, end, _)| {
+            !loaded
+                .iter()
+                .any(|(l_start, l_end)| l_start <= start && end <= l_end)
+        });
+    }
+
+    fn add_loaded(&mut self, start_s: i64, end_s: i64) {
+        let (mut start_s, mut end_s) = (start_s, end_s);
+        self.loaded.retain(|(l_start, l_end)| {
+            if *l_end < start_s || end_s < *l_start {
+                true
+            } else {
+                start_s = start_s.min(*l_start);
+                end_s = end_s.max(*l_end);
+                false
+            }
+        });
+        let index = self
+            .loaded
+            .partition_point(|(l_start, _)| *l_start < start_s);
+        self.loaded.insert(index, (start_s, end_s));
+    }
+
+    /**
+     * Marks a period as being fetched
+     */
+    pub fn set_pending(&mut self, start_s: i64, end_s: i64, now_s: i64) {
+        self.pending.push((start_s, end_s, now_s));
+    }
+
+    /**
+     * Returns true while a period is being fetched
+     */
+    pub fn is_fetching(&self, now_s: i64) -> bool {
+        self.pending
+            .iter()
+            .any(|(_, _, since)| now_s - since < Self::PENDING_TIMEOUT_S)
+    }
+
+    /**
+     * Returns the parts of a period that are neither loaded nor being fetched
+     */
+    pub fn missing_periods(&self, start_s: i64, end_s: i64, now_s: i64) -> Vec<(i64, i64)> {
+        let mut covered: Vec<(i64, i64)> = self
+            .pending
+            .iter()
+            .filter(|(_, _, since)| now_s - since < Self::PENDING_TIMEOUT_S)
+            .map(|(start, end, _)| (*start, *end))
+            .chain(self.loaded.iter().copied())
+            .collect();
+        covered.sort();
+
+        let mut missing = Vec::new();
+        let mut cursor = start_s;
+        for (c_start, c_end) in covered {
+            if c_end <= cursor {
+                continue;
+            }
+            if c_start >= end_s {
+                break;
+            }
+            if c_start > cursor {
+                missing.push((cursor, c_start));
+            }
+            cursor = cursor.max(c_end);
+        }
+        if cursor < end_s {
+            missing.push((cursor, end_s));
+        }
+
+        missing.retain(|(start, end)| end - start >= Self::MIN_MISSING_S);
+        missing
+    }
+
+    /**
+     * Returns the start of the first loaded period and the end of the last one
+     */
+    pub fn loaded_range(&self) -> Option<(i64, i64)> {
+        let (first, last) = (self.loaded.first()?, self.loaded.last()?);
+        Some((first.0, last.1))
+    }
+
+    /**
+     * Returns a copy of all the cached jobs
+     */
+    pub fn jobs(&self) -> Vec<Job> {
+        self.jobs.values().cloned().collect()
+    }
+}
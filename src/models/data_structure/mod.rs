@@ -8,4 +8,7 @@ pub mod resource;
 pub mod cluster;
 pub mod host;
 pub mod cpu;
-pub mod strata;
\ No newline at end of file
+pub mod strata;
+pub mod resource_index;
+pub mod job_cache;
+pub mod annotation;
\ No newline at end of file
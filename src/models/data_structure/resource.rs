@@ -5,6 +5,7 @@ pub enum ResourceState {
     Dead,
     Alive,
     Absent,
+    Suspected,
     Unknown,
 }
 
@@ -14,6 +15,7 @@ impl Clone for ResourceState {
             ResourceState::Dead => ResourceState::Dead,
             ResourceState::Alive => ResourceState::Alive,
             ResourceState::Absent => ResourceState::Absent,
+            ResourceState::Suspected => ResourceState::Suspected,
             ResourceState::Unknown => ResourceState::Unknown,
         }
     }
@@ -24,6 +26,8 @@ pub struct Resource {
     pub id: u32,
     pub state: ResourceState,
     pub thread_count: i32,
+    pub gpu_model: Option<String>, // Model of the GPU of the resource, None without GPU
+    pub comment: Option<String>,   // Comment of the administrators on the resource
 }
 
 impl Display for ResourceState {
@@ -32,6 +36,7 @@ impl Display for ResourceState {
             ResourceState::Dead => write!(f, "Dead"),
             ResourceState::Alive => write!(f, "Alive"),
             ResourceState::Absent => write!(f, "Absent"),
+            ResourceState::Suspected => write!(f, "Suspected"),
             ResourceState::Unknown => write!(f, "Unknown"),
         }
     }
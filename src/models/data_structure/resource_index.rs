@@ -0,0 +1,58 @@
+use super::{cluster::Cluster, resource::ResourceState};
+use crate::models::utils::utils::get_site_from_host;
+use std::collections::HashMap;
+
+/**
+ * Location of a resource in the cluster hierarchy
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourceLocation {
+    pub site: String,         // Name of the site hosting the cluster
+    pub cluster: String,      // Name of the cluster owning the resource
+    pub host: String,         // Name of the host owning the resource
+    pub cpu: String,          // Name of the CPU owning the resource
+    pub state: ResourceState, // State of the resource itself
+    pub gpu: bool,            // True if the resource has a GPU
+}
+
+/*
+ * `ResourceIndex` maps every resource id to its site, cluster, host and CPU.
+ * It is rebuilt each time the cluster hierarchy is refreshed so that
+ * filtering and aggregation can resolve a resource in constant time
+ * instead of walking the whole Cluster -> Host -> Cpu -> Resource tree.
+ */
+#[derive(Default, Clone, Debug)]
+pub struct ResourceIndex {
+    locations: HashMap<u32, ResourceLocation>,
+}
+
+impl ResourceIndex {
+    pub fn build(clusters: &[Cluster]) -> Self {
+        let mut locations = HashMap::new();
+        for cluster in clusters {
+            for host in &cluster.hosts {
+                let site = get_site_from_host(&host.name);
+                for cpu in &host.cpus {
+                    for resource in &cpu.resources {
+                        locations.insert(
+                            resource.id,
+                            ResourceLocation {
+                                site: site.clone(),
+                                cluster: cluster.name.clone(),
+                                host: host.name.clone(),
+                                cpu: cpu.name.clone(),
+                                state: resource.state,
+                                gpu: resource.gpu_model.is_some(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+        ResourceIndex { locations }
+    }
+
+    pub fn get(&self, resource_id: u32) -> Option<&ResourceLocation> {
+        self.locations.get(&resource_id)
+    }
+}
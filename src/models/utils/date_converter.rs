@@ -1,15 +1,117 @@
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use std::sync::RwLock;
+
+/*
+ * Timezone of every displayed timestamp, set from the options like the language.
+ * None stands for the local timezone of the machine.
+ */
+static DISPLAY_TIMEZONE: RwLock<Option<Tz>> = RwLock::new(None);
+
+pub fn set_display_timezone(timezone: Option<Tz>) {
+    if let Ok(mut current) = DISPLAY_TIMEZONE.write() {
+        *current = timezone;
+    }
+}
+
+pub fn get_display_timezone() -> Option<Tz> {
+    DISPLAY_TIMEZONE
+        .read()
+        .map(|timezone| *timezone)
+        .unwrap_or(None)
+}
+
+/**
+ * Formats a timestamp in the display timezone, None if the timestamp is invalid
+ */
+pub fn format_timestamp_as(ts: i64, format: &str) -> Option<String> {
+    let dt = DateTime::from_timestamp(ts, 0)?;
+    Some(match get_display_timezone() {
+        Some(timezone) => dt.with_timezone(&timezone).format(format).to_string(),
+        None => dt.with_timezone(&Local).format(format).to_string(),
+    })
+}
+
+/**
+ * Formats a timestamp as RFC 3339 in the display timezone, its offset matching the shown dates
+ */
+pub fn format_timestamp_rfc3339(ts: i64) -> Option<String> {
+    let dt = DateTime::from_timestamp(ts, 0)?;
+    Some(match get_display_timezone() {
+        Some(timezone) => dt.with_timezone(&timezone).to_rfc3339(),
+        None => dt.with_timezone(&Local).to_rfc3339(),
+    })
+}
 
 pub fn format_timestamp(ts: i64) -> String {
     if ts == 0 {
         "N/A".to_string()
     } else {
-        if let Some(dt) = DateTime::from_timestamp(ts, 0) {
-            dt.with_timezone(&Local)
-            .format("%Y-%m-%d %H:%M:%S %Z")
-            .to_string()
-        } else {
-            "Invalid timestamp".to_string()
-        }
-    }
-}
\ No newline at end of file
+        format_timestamp_as(ts, "%Y-%m-%d %H:%M:%S %Z")
+            .unwrap_or_else(|| "Invalid timestamp".to_string())
+    }
+}
+
+/**
+ * Parses a date typed by the user in the display timezone, returns its timestamp
+ */
+pub fn parse_timestamp(text: &str, format: &str) -> Option<i64> {
+    let date = NaiveDateTime::parse_from_str(text.trim(), format).ok()?;
+    match get_display_timezone() {
+        Some(timezone) => timezone
+            .from_local_datetime(&date)
+            .single()
+            .map(|dt| dt.timestamp()),
+        None => Local
+            .from_local_datetime(&date)
+            .single()
+            .map(|dt| dt.timestamp()),
+    }
+}
+
+/**
+ * Formats a duration in seconds as HH:MM:SS, prefixed by the number of days if any
+ */
+pub fn format_duration(seconds: i64) -> String {
+    let sign = if seconds < 0 { "-" } else { "" };
+    let seconds = seconds.abs();
+    let (days, hours, minutes, seconds) = (
+        seconds / 86400,
+        seconds % 86400 / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+    );
+    if days > 0 {
+        format!(
+            "{}{}d {:02}:{:02}:{:02}",
+            sign, days, hours, minutes, seconds
+        )
+    } else {
+        format!("{}{:02}:{:02}:{:02}", sign, hours, minutes, seconds)
+    }
+}
+
+/**
+ * Formats a duration in seconds with its two largest units, e.g. 2d 3h or 5m 10s
+ */
+pub fn format_duration_humanised(seconds: i64) -> String {
+    let sign = if seconds < 0 { "-" } else { "" };
+    let seconds = seconds.abs();
+    let units = [
+        (seconds / 86400, "d"),
+        (seconds % 86400 / 3600, "h"),
+        (seconds % 3600 / 60, "m"),
+        (seconds % 60, "s"),
+    ];
+    let first = units
+        .iter()
+        .position(|(value, _)| *value > 0)
+        .unwrap_or(units.len() - 1);
+    let parts: Vec<String> = units[first..]
+        .iter()
+        .take(2)
+        .filter(|(value, _)| *value > 0 || first == units.len() - 1)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect();
+    format!("{}{}", sign, parts.join(" "))
+}
@@ -0,0 +1,20 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/**
+ * Reads a value from a JSON file, None if the file is absent or invalid
+ */
+pub fn load_json<T: DeserializeOwned>(file_path: &str) -> Option<T> {
+    std::fs::read_to_string(file_path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+/**
+ * Writes a value to a JSON file, indented so that the file can be edited by hand
+ */
+pub fn save_json<T: Serialize>(value: &T, file_path: &str) -> Result<(), String> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(file_path, json).map_err(|e| e.to_string()))
+}
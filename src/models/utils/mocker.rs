@@ -20,6 +20,9 @@ fn mock_job(id: u32) -> Job {
         ("gcc -O3 project.c", "compile"),
     ];
 
+    // Possible projects list
+    let projects = vec!["deep-learning", "hpc-sim", "teaching"];
+
     // Possible clusters list
     let clusters_list = vec!["cluster1", "cluster2", "cluster3"];
 
@@ -135,6 +138,7 @@ fn mock_job(id: u32) -> Job {
         walltime,
         message,
         queue: queue.to_string(),
+        project: projects[random_index(projects.len())].to_string(),
         assigned_resources,
         scheduled_start,
         submission_time,
@@ -164,7 +168,7 @@ fn mock_strata(id: u32) -> Strata {
     let hosts_list = vec!["host1", "host2", "host3", "host4", "host5", "host6"];
 
     // Possible states list
-    let states_list = vec!["Dead", "Alive", "Absent", "Unknown"];
+    let states_list = vec!["Dead", "Alive", "Absent", "Suspected", "Unknown"];
 
     // Possible comments list
     let comments_list = vec![
@@ -1,6 +1,7 @@
 // Module: utils
 
 pub mod date_converter;
+pub mod json_file;
 pub mod parser;
 pub mod updater;
 pub mod utils;
@@ -7,7 +7,6 @@ use std::fs::File;
 use std::io::Read;
 use std::process::Command;
 
-#[cfg(not(target_arch = "wasm32"))]
 use chrono::{DateTime, Local};
 
 /**
@@ -35,10 +34,7 @@ pub fn test_connection(host: &str) -> Result<(), String> {
 #[cfg(not(target_arch = "wasm32"))]
 pub fn get_current_jobs_for_period(start_date: DateTime<Local>, end_date: DateTime<Local>) -> bool {
     // Add a margin to the interval
-    let interval = end_date - start_date;
-    let margin = interval.num_seconds() * 30 / 100;
-    let start_date = start_date - chrono::Duration::seconds(margin);
-    let end_date = end_date + chrono::Duration::seconds(margin);
+    let (start_date, end_date) = get_period_with_margin(start_date, end_date);
 
     // Test connection first
     if test_connection("grenoble.g5k") != Ok(()) {
@@ -72,6 +68,24 @@ pub fn get_current_jobs_for_period(start_date: DateTime<Local>, end_date: DateTi
     true
 }
 
+/**
+ * Get the period really fetched for the specified period, with a 30% margin on both sides
+ * @param start_date: Start date of the period
+ * @param end_date: End date of the period
+ * @return Start and end dates of the fetched period
+ */
+pub fn get_period_with_margin(
+    start_date: DateTime<Local>,
+    end_date: DateTime<Local>,
+) -> (DateTime<Local>, DateTime<Local>) {
+    let interval = end_date - start_date;
+    let margin = interval.num_seconds() * 30 / 100;
+    (
+        start_date - chrono::Duration::seconds(margin),
+        end_date + chrono::Duration::seconds(margin),
+    )
+}
+
 pub fn get_jobs_from_json(file_path: &str) -> Vec<Job> {
     let file_res = File::open(file_path);
 
@@ -165,6 +179,7 @@ fn from_json_value(json: &Value) -> Job {
         walltime: json["walltime"].as_i64().unwrap_or(0) as i64,
         message: json["message"].as_str().map(|s| s.to_string()),
         queue: json["queue"].as_str().unwrap_or("default").to_string(),
+        project: json["project"].as_str().unwrap_or("default").to_string(),
         assigned_resources: json["resource_id"]
             .as_array()
             .unwrap_or(&Vec::new())
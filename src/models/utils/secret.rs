@@ -89,6 +89,13 @@ impl Secret {
         }
     }
 
+    /**
+     * Returns true while the snake game window is shown, its arrows must not be used by other widgets
+     */
+    pub fn is_game_shown(&self) -> bool {
+        self.is_konami_active && self.show_game
+    }
+
     pub fn draw_snake_game(&mut self, ctx: &egui::Context) {
         if self.is_konami_active && self.show_game {
             egui::Window::new("Secret Snake Game")
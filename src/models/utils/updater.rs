@@ -1,8 +1,10 @@
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, TimeZone};
 
 use std::time::Duration;
 
 use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::data_structure::job::Job;
+use crate::models::data_structure::job_cache::JobBatch;
 
 #[cfg(target_arch = "wasm32")]
 use super::mocker::{mock_jobs,mock_stratas};
@@ -14,7 +16,19 @@ use crate::models::utils::parser::get_current_jobs_for_period;
 #[cfg(not(target_arch = "wasm32"))]
 use std::thread;
 
-use super::parser::{get_jobs_from_json, get_resources_from_json};
+use super::parser::{get_jobs_from_json, get_period_with_margin, get_resources_from_json};
+
+/**
+ * Wraps the jobs fetched for a period into a batch for the job cache
+ */
+fn job_batch(start: DateTime<Local>, end: DateTime<Local>, jobs: Vec<Job>) -> JobBatch {
+    let (start, end) = get_period_with_margin(start, end);
+    JobBatch {
+        start_s: start.timestamp(),
+        end_s: end.timestamp(),
+        jobs,
+    }
+}
 
 impl ApplicationContext {
     pub fn update_refresh_rate(&mut self, new_rate: u64) {
@@ -77,9 +91,11 @@ impl ApplicationContext {
                 if res {
                     let jobs = get_jobs_from_json("./data/data.json");
                     let resources = get_resources_from_json("./data/data.json");
-                    jobs_sender.send(jobs).unwrap_or_else(|e| {
-                        println!("Error while sending jobs: {}", e);
-                    });
+                    jobs_sender
+                        .send(job_batch(start, end, jobs))
+                        .unwrap_or_else(|e| {
+                            println!("Error while sending jobs: {}", e);
+                        });
 
                     resources_sender.send(resources).unwrap_or_else(|e| {
                         println!("Error while sending resources: {}", e);
@@ -96,7 +112,7 @@ impl ApplicationContext {
             // LOG DEBUG
             // log::info!("instant_update: start_date: {:?}, end_date: {:?}", start, end);
             let jobs = mock_jobs();
-            jobs_sender.send(jobs).unwrap();
+            jobs_sender.send(job_batch(start, end, jobs)).unwrap();
 
             let strata = mock_stratas();
             resources_sender.send(strata).unwrap();
@@ -142,9 +158,11 @@ impl ApplicationContext {
                         let jobs = get_jobs_from_json("./data/data.json");
                         let resources = get_resources_from_json("./data/data.json");
 
-                        jobs_sender.send(jobs).unwrap_or_else(|e| {
-                            println!("Error while sending jobs: {}", e);
-                        });
+                        jobs_sender
+                            .send(job_batch(start, end, jobs))
+                            .unwrap_or_else(|e| {
+                                println!("Error while sending jobs: {}", e);
+                            });
 
                         resources_sender.send(resources).unwrap_or_else(|e| {
                             println!("Error while sending resources: {}", e);
@@ -163,11 +181,102 @@ impl ApplicationContext {
         {
             // LOG DEBUG
             // log::info!("update_periodically: start_date: {:?}, end_date: {:?}", start, end);
+            let start = *start_date.lock().unwrap();
+            let end = *end_date.lock().unwrap();
+            let jobs = mock_jobs();
+            jobs_sender.send(job_batch(start, end, jobs)).unwrap();
+
+            let strata = mock_stratas();
+            resources_sender.send(strata).unwrap();
+        }
+    }
+
+    /**
+     * Fetches in the background the part of the current period missing from the job cache
+     * One period is fetched at a time, as the fetches share the same data file
+     */
+    pub fn fetch_missing_periods(&mut self) {
+        let is_refreshing = self.is_refreshing.clone();
+        if *is_refreshing.lock().unwrap() {
+            return;
+        }
+
+        let start_s = self.get_start_date().timestamp();
+        let end_s = self.get_end_date().timestamp();
+        let now_s = chrono::Utc::now().timestamp();
+
+        // The compared window and the accounting period are fetched once the current one is loaded
+        let mut periods = vec![(start_s, end_s)];
+        if let Some(offset_s) = self.comparison_offset_s {
+            periods.push((start_s + offset_s, end_s + offset_s));
+        }
+        periods.extend(self.accounting_period);
+        let Some(((start_s, end_s), (mut missing_start_s, mut missing_end_s))) =
+            periods.into_iter().find_map(|(start_s, end_s)| {
+                let missing = self.job_cache.missing_periods(start_s, end_s, now_s);
+                missing.first().map(|first| ((start_s, end_s), *first))
+            })
+        else {
+            return;
+        };
+
+        // Small gaps are widened away from the loaded data, to avoid a fetch for every pan
+        let min_length_s = (end_s - start_s) / 2;
+        if missing_end_s - missing_start_s < min_length_s {
+            if missing_start_s == start_s {
+                missing_start_s = missing_end_s - min_length_s;
+            } else {
+                missing_end_s = missing_start_s + min_length_s;
+            }
+        }
+        let (Some(start), Some(end)) = (
+            Local.timestamp_opt(missing_start_s, 0).single(),
+            Local.timestamp_opt(missing_end_s, 0).single(),
+        ) else {
+            return;
+        };
+
+        let (fetch_start, fetch_end) = get_period_with_margin(start, end);
+        self.job_cache
+            .set_pending(fetch_start.timestamp(), fetch_end.timestamp(), now_s);
+        *is_refreshing.lock().unwrap() = true;
+
+        let jobs_sender = self.jobs_sender.clone();
+        let resources_sender = self.resources_sender.clone();
+
+        // Get the data in a different thread, a failed fetch is retried once the pending period expires
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            thread::spawn(move || {
+                if get_current_jobs_for_period(start, end) {
+                    let jobs = get_jobs_from_json("./data/data.json");
+                    let resources = get_resources_from_json("./data/data.json");
+                    jobs_sender
+                        .send(job_batch(start, end, jobs))
+                        .unwrap_or_else(|e| {
+                            println!("Error while sending jobs: {}", e);
+                        });
+
+                    resources_sender.send(resources).unwrap_or_else(|e| {
+                        println!("Error while sending resources: {}", e);
+                    });
+                }
+
+                // set refreshing to false
+                *is_refreshing.lock().unwrap() = false;
+            });
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
             let jobs = mock_jobs();
-            jobs_sender.send(jobs).unwrap();
+            jobs_sender.send(job_batch(start, end, jobs)).unwrap();
 
             let strata = mock_stratas();
             resources_sender.send(strata).unwrap();
+
+            // set refreshing to false
+            *is_refreshing.lock().unwrap() = false;
         }
     }
 
@@ -189,7 +298,7 @@ impl ApplicationContext {
                 let res = get_current_jobs_for_period(start, end);
                 if res {
                     let jobs = get_jobs_from_json("./data/data.json");
-                    sender.send(jobs).unwrap();
+                    sender.send(job_batch(start, end, jobs)).unwrap();
                 }
             });
         }
@@ -199,7 +308,7 @@ impl ApplicationContext {
             // LOG DEBUG
             // log::info!("update_period: start_date: {:?}, end_date: {:?}", start, end);
             let jobs = mock_jobs();
-            sender.send(jobs).unwrap();
+            sender.send(job_batch(start, end, jobs)).unwrap();
         }
     }
 }
@@ -35,22 +35,9 @@ pub fn get_all_hosts(clusters: &Vec<Cluster>) -> Vec<String> {
     result
 }
 
-pub fn cluster_contain_host(cluster: &Cluster, host_name: &str) -> bool {
-    for host in &cluster.hosts {
-        if host.name == host_name {
-            return true;
-        }
-    }
-    false
-}
-
-pub fn get_cluster_from_name(clusters: &Vec<Cluster>, name: &str) -> Option<Cluster> {
-    for cluster in clusters {
-        if cluster.name == name {
-            return Some(cluster.clone());
-        }
-    }
-    None
+/// Extracts the site from a fully qualified host name (e.g. "dahu-1.grenoble.grid5000.fr" -> "grenoble")
+pub fn get_site_from_host(host_name: &str) -> String {
+    host_name.split('.').nth(1).unwrap_or("default").to_string()
 }
 
 /// Extracts all cluster names from a collection of clusters
@@ -75,17 +62,6 @@ pub fn get_all_resources(clusters: &Vec<Cluster>) -> Vec<u32> {
     result
 }
 
-pub fn contains_host(cluster: &Vec<Cluster>, host_name: &str) -> bool {
-    for c in cluster {
-        for host in &c.hosts {
-            if host.name == host_name {
-                return true;
-            }
-        }
-    }
-    false
-}
-
 pub fn get_cluster_state_from_name(cluster: &Vec<Cluster>, cluster_name: &String) -> ResourceState {
     for c in cluster {
         if c.name == *cluster_name {
@@ -106,15 +82,6 @@ pub fn get_host_state_from_name(cluster: &Vec<Cluster>, host_name: &String) -> R
     ResourceState::Unknown
 }
 
-pub fn contains_cluster(cluster: &Vec<Cluster>, cluster_name: &str) -> bool {
-    for c in cluster {
-        if c.name == cluster_name {
-            return true;
-        }
-    }
-    false
-}
-
 // Compare two strings that may contain numbers (natural sort)
 pub fn compare_string_with_number(a: &str, b: &str) -> Ordering {
     let mut strings_a: Vec<String> = Vec::new();
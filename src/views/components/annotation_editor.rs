@@ -0,0 +1,261 @@
+use crate::models::data_structure::annotation::{Annotation, AnnotationKind, Annotations};
+use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::utils::date_converter::{
+    format_timestamp, format_timestamp_as, parse_timestamp,
+};
+use eframe::egui;
+use strum::IntoEnumIterator;
+
+/**
+ * AnnotationEditor is the window to create, list and delete the annotations
+ * Every change is saved to the shared annotations file
+ */
+pub struct AnnotationEditor {
+    open: bool,
+    kind: AnnotationKind,
+    label: String,
+    description: String,
+    start: String,                 // Start as typed by the user
+    end: String,                   // End as typed by the user, empty for an instant
+    clusters: String,              // Comma separated clusters of the scope
+    hosts: String,                 // Comma separated hosts of the scope
+    error: Option<String>,         // Error of the form
+    status: Option<(String, f64)>, // Status of the last save and the time it was set
+}
+
+impl Default for AnnotationEditor {
+    fn default() -> Self {
+        AnnotationEditor {
+            open: false,
+            kind: AnnotationKind::Maintenance,
+            label: String::new(),
+            description: String::new(),
+            start: String::new(),
+            end: String::new(),
+            clusters: String::new(),
+            hosts: String::new(),
+            error: None,
+            status: None,
+        }
+    }
+}
+
+impl AnnotationEditor {
+    const DATE_FORMAT: &'static str = "%Y-%m-%d %H:%M";
+    const STATUS_DURATION_S: f64 = 5.0;
+
+    /**
+     * Opens the window, the start is filled with the current time
+     */
+    pub fn open(&mut self) {
+        self.open = true;
+        if self.start.is_empty() {
+            let now = chrono::Utc::now().timestamp();
+            self.start = format_timestamp_as(now, Self::DATE_FORMAT).unwrap_or_default();
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
+        let mut open = self.open;
+        egui::Window::new(t!("app.annotations.title"))
+            .collapsible(true)
+            .movable(true)
+            .open(&mut open)
+            .vscroll(true)
+            .show(ui.ctx(), |ui| {
+                self.form_ui(ui, &mut app.annotations);
+                ui.separator();
+                self.list_ui(ui, &mut app.annotations);
+            });
+        self.open = open;
+    }
+
+    /**
+     * Shows the form of a new annotation
+     */
+    fn form_ui(&mut self, ui: &mut egui::Ui, annotations: &mut Annotations) {
+        ui.heading(t!("app.annotations.new"));
+        egui::Grid::new("annotation_form_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(t!("app.annotations.kind.title"));
+                ui.horizontal(|ui| {
+                    for kind in AnnotationKind::iter() {
+                        ui.selectable_value(&mut self.kind, kind, kind.get_label());
+                    }
+                });
+                ui.end_row();
+
+                ui.label(t!("app.annotations.label"));
+                ui.text_edit_singleline(&mut self.label);
+                ui.end_row();
+
+                ui.label(t!("app.annotations.description"));
+                ui.text_edit_multiline(&mut self.description);
+                ui.end_row();
+
+                ui.label(t!("app.annotations.start"));
+                ui.text_edit_singleline(&mut self.start);
+                ui.end_row();
+
+                ui.label(t!("app.annotations.end"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.end)
+                        .hint_text(t!("app.annotations.end_hint")),
+                );
+                ui.end_row();
+
+                ui.label(t!("app.annotations.clusters"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.clusters)
+                        .hint_text(t!("app.annotations.scope_hint")),
+                );
+                ui.end_row();
+
+                ui.label(t!("app.annotations.hosts"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.hosts)
+                        .hint_text(t!("app.annotations.scope_hint")),
+                );
+                ui.end_row();
+            });
+        ui.label(
+            egui::RichText::new(t!(
+                "app.menu.time_range.format",
+                format = "YYYY-MM-DD HH:MM"
+            ))
+            .weak(),
+        );
+
+        if ui.button(t!("app.annotations.add")).clicked() {
+            match self.parse_annotation() {
+                Ok(annotation) => {
+                    annotations.add(annotation);
+                    self.save(ui, annotations);
+                    self.label.clear();
+                    self.description.clear();
+                    self.error = None;
+                }
+                Err(error) => self.error = Some(error),
+            }
+        }
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+    }
+
+    /**
+     * Lists the annotations with a button to delete each of them
+     */
+    fn list_ui(&mut self, ui: &mut egui::Ui, annotations: &mut Annotations) {
+        ui.horizontal(|ui| {
+            ui.heading(t!("app.annotations.list"));
+            // Read the file again to get the annotations added by other users
+            if ui
+                .button("⟳")
+                .on_hover_text(t!("app.annotations.reload"))
+                .clicked()
+            {
+                *annotations = Annotations::load_from_file(Annotations::FILE_PATH);
+            }
+        });
+
+        let mut removed = None;
+        if annotations.annotations.is_empty() {
+            ui.label(t!("app.annotations.empty"));
+        }
+        for annotation in &annotations.annotations {
+            ui.horizontal(|ui| {
+                if ui
+                    .button("🗑")
+                    .on_hover_text(t!("app.annotations.delete"))
+                    .clicked()
+                {
+                    removed = Some(annotation.id);
+                }
+                ui.colored_label(annotation.kind.get_color(), "⏺");
+                ui.strong(&annotation.label);
+                ui.label(describe_period(annotation));
+            });
+        }
+        if let Some(id) = removed {
+            annotations.remove(id);
+            self.save(ui, annotations);
+        }
+
+        if let Some((message, timestamp)) = &self.status {
+            if ui.input(|i| i.time) - timestamp < Self::STATUS_DURATION_S {
+                ui.label(message);
+            } else {
+                self.status = None; // Clear the message after timeout
+            }
+        }
+    }
+
+    fn save(&mut self, ui: &egui::Ui, annotations: &Annotations) {
+        let message = match annotations.save_to_file(Annotations::FILE_PATH) {
+            Ok(()) => t!("app.annotations.saved", path = Annotations::FILE_PATH).to_string(),
+            Err(error) => t!("app.annotations.save_error", error = error).to_string(),
+        };
+        self.status = Some((message, ui.input(|i| i.time)));
+    }
+
+    /**
+     * Builds the annotation of the form, the end is optional and must not be before the start
+     */
+    fn parse_annotation(&self) -> Result<Annotation, String> {
+        let parse = |text: &str| {
+            parse_timestamp(text, Self::DATE_FORMAT)
+                .ok_or_else(|| t!("app.menu.time_range.invalid_date", date = text).to_string())
+        };
+        let split = |text: &str| -> Vec<String> {
+            text.split(',')
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect()
+        };
+
+        if self.label.trim().is_empty() {
+            return Err(t!("app.annotations.missing_label").to_string());
+        }
+        let start = parse(&self.start)?;
+        let end = if self.end.trim().is_empty() {
+            None
+        } else {
+            Some(parse(&self.end)?)
+        };
+        if end.is_some_and(|end| end < start) {
+            return Err(t!("app.menu.time_range.end_before_start").to_string());
+        }
+
+        Ok(Annotation {
+            id: 0,
+            kind: self.kind,
+            label: self.label.trim().to_string(),
+            description: self.description.trim().to_string(),
+            start,
+            end,
+            clusters: split(&self.clusters),
+            hosts: split(&self.hosts),
+        })
+    }
+}
+
+/**
+ * Describes the period and the scope of an annotation on one line
+ */
+pub fn describe_period(annotation: &Annotation) -> String {
+    let period = match annotation.end {
+        Some(end) => format!(
+            "{} - {}",
+            format_timestamp(annotation.start),
+            format_timestamp(end)
+        ),
+        None => format_timestamp(annotation.start),
+    };
+    if annotation.is_global() {
+        period
+    } else {
+        format!("{} ({})", period, annotation.scope_names().join(", "))
+    }
+}
@@ -0,0 +1,114 @@
+use eframe::egui;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/**
+ * Window compared with the current one
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+pub enum ComparisonOffset {
+    Off,
+    PreviousDay,
+    PreviousWeek,
+    Custom,
+}
+
+impl ComparisonOffset {
+    pub fn get_label(&self) -> String {
+        match self {
+            ComparisonOffset::Off => t!("app.menu.comparison.off").to_string(),
+            ComparisonOffset::PreviousDay => t!("app.menu.comparison.previous_day").to_string(),
+            ComparisonOffset::PreviousWeek => t!("app.menu.comparison.previous_week").to_string(),
+            ComparisonOffset::Custom => t!("app.menu.comparison.custom").to_string(),
+        }
+    }
+
+    /**
+     * Returns the offset of the compared window in seconds, None for the custom offset
+     */
+    pub fn get_offset_s(&self) -> Option<Option<i64>> {
+        match self {
+            ComparisonOffset::Off => Some(None),
+            ComparisonOffset::PreviousDay => Some(Some(-86_400)),
+            ComparisonOffset::PreviousWeek => Some(Some(-7 * 86_400)),
+            ComparisonOffset::Custom => None,
+        }
+    }
+}
+
+/**
+ * ComparisonPicker chooses the offset of the window compared with the current one
+ */
+pub struct ComparisonPicker {
+    selected: ComparisonOffset,
+    custom_hours: String,  // Custom offset in hours, as typed by the user
+    error: Option<String>, // Error of the custom offset
+}
+
+impl Default for ComparisonPicker {
+    fn default() -> Self {
+        ComparisonPicker {
+            selected: ComparisonOffset::Off,
+            custom_hours: "-24".to_string(),
+            error: None,
+        }
+    }
+}
+
+impl ComparisonPicker {
+    /**
+     * Shows the picker and updates the offset chosen by the user
+     */
+    pub fn ui(&mut self, ui: &mut egui::Ui, offset_s: &mut Option<i64>) {
+        // The offset may have been turned off elsewhere
+        if offset_s.is_none() {
+            self.selected = ComparisonOffset::Off;
+        }
+        let title = match self.selected {
+            ComparisonOffset::Off => t!("app.menu.comparison.button").to_string(),
+            selected => selected.get_label(),
+        };
+
+        ui.menu_button(format!("⇅ {}", title), |ui| {
+            for offset in ComparisonOffset::iter() {
+                if ui
+                    .selectable_label(self.selected == offset, offset.get_label())
+                    .clicked()
+                {
+                    self.selected = offset;
+                    if let Some(new_offset_s) = offset.get_offset_s() {
+                        *offset_s = new_offset_s;
+                        ui.close_menu();
+                    }
+                }
+            }
+
+            if self.selected == ComparisonOffset::Custom {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(t!("app.menu.comparison.hours"));
+                    ui.add(egui::TextEdit::singleline(&mut self.custom_hours).desired_width(60.0));
+                });
+                ui.label(egui::RichText::new(t!("app.menu.comparison.hours_hint")).weak());
+                if ui.button(t!("app.menu.time_range.apply")).clicked() {
+                    match self.custom_hours.trim().parse::<f64>() {
+                        Ok(hours) if hours != 0.0 => {
+                            *offset_s = Some((hours * 3600.0) as i64);
+                            self.error = None;
+                            ui.close_menu();
+                        }
+                        _ => {
+                            self.error = Some(
+                                t!("app.menu.comparison.invalid", value = self.custom_hours)
+                                    .to_string(),
+                            )
+                        }
+                    }
+                }
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,42 @@
+use crate::models::data_structure::annotation::Annotations;
+use crate::views::components::annotation_editor::describe_period;
+use eframe::egui::{self, RichText};
+
+/**
+ * Shows the annotations that are not over yet, the ongoing ones first
+ */
+pub fn show_annotation_list(ui: &mut egui::Ui, annotations: &Annotations) {
+    let now = chrono::Utc::now().timestamp();
+    let mut current: Vec<_> = annotations
+        .annotations
+        .iter()
+        .filter(|annotation| annotation.get_end() >= now)
+        .collect();
+    current.sort_by_key(|annotation| (annotation.start > now, annotation.start));
+
+    egui::CollapsingHeader::new(t!("app.annotations.dashboard_title", count = current.len()))
+        .default_open(true)
+        .show(ui, |ui| {
+            if current.is_empty() {
+                ui.label(t!("app.annotations.empty"));
+            }
+            egui::Grid::new("annotation_list_grid")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    for annotation in current {
+                        let status = if annotation.start <= now {
+                            t!("app.annotations.ongoing")
+                        } else {
+                            t!("app.annotations.upcoming")
+                        };
+                        ui.colored_label(annotation.kind.get_color(), annotation.kind.get_label());
+                        ui.label(RichText::new(&annotation.label).strong())
+                            .on_hover_text(&annotation.description);
+                        ui.label(describe_period(annotation));
+                        ui.label(status);
+                        ui.end_row();
+                    }
+                });
+        });
+}
@@ -0,0 +1,236 @@
+use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::data_structure::host::Host;
+use crate::models::data_structure::job::{Job, JobState};
+use crate::models::data_structure::resource::ResourceState;
+use eframe::egui::{self, Color32, RichText};
+use std::collections::{BTreeSet, HashMap};
+
+/**
+ * States counted by the health panel, in the order of its columns
+ */
+const HEALTH_STATES: [ResourceState; 4] = [
+    ResourceState::Alive,
+    ResourceState::Dead,
+    ResourceState::Absent,
+    ResourceState::Suspected,
+];
+
+fn state_label(state: ResourceState) -> String {
+    match state {
+        ResourceState::Alive => t!("app.dashboard.health.alive").to_string(),
+        ResourceState::Dead => t!("app.dashboard.health.dead").to_string(),
+        ResourceState::Absent => t!("app.dashboard.health.absent").to_string(),
+        ResourceState::Suspected => t!("app.dashboard.health.suspected").to_string(),
+        ResourceState::Unknown => t!("app.dashboard.health.unknown").to_string(),
+    }
+}
+
+fn state_color(state: ResourceState) -> Color32 {
+    match state {
+        ResourceState::Alive => Color32::from_rgb(0, 158, 115),
+        ResourceState::Dead => Color32::from_rgb(213, 94, 0),
+        ResourceState::Absent => Color32::from_rgb(128, 128, 128),
+        ResourceState::Suspected => Color32::from_rgb(230, 159, 0),
+        ResourceState::Unknown => Color32::from_rgb(86, 180, 233),
+    }
+}
+
+fn is_unhealthy(state: ResourceState) -> bool {
+    matches!(
+        state,
+        ResourceState::Dead | ResourceState::Absent | ResourceState::Suspected
+    )
+}
+
+/**
+ * Number of hosts or resources in each state of the panel
+ */
+#[derive(Default)]
+struct StateCounts {
+    counts: [usize; HEALTH_STATES.len()],
+    total: usize, // Also counts the unknown states
+}
+
+impl StateCounts {
+    fn add(&mut self, state: ResourceState) {
+        if let Some(index) = HEALTH_STATES.iter().position(|s| *s == state) {
+            self.counts[index] += 1;
+        }
+        self.total += 1;
+    }
+
+    /**
+     * Adds a cell per state with its count and its percentage of the total
+     */
+    fn cells_ui(&self, ui: &mut egui::Ui) {
+        for (state, count) in HEALTH_STATES.iter().zip(self.counts) {
+            let percent = if self.total > 0 {
+                count as f64 / self.total as f64 * 100.0
+            } else {
+                0.0
+            };
+            let text = RichText::new(format!("{} ({:.0} %)", count, percent));
+            if count > 0 && is_unhealthy(*state) {
+                ui.label(text.color(state_color(*state)).strong());
+            } else {
+                ui.label(text);
+            }
+        }
+    }
+}
+
+/**
+ * Returns the distinct comments of the resources of a host
+ */
+fn host_comments(host: &Host) -> Vec<&str> {
+    let comments: BTreeSet<&str> = host
+        .cpus
+        .iter()
+        .flat_map(|cpu| &cpu.resources)
+        .filter_map(|resource| resource.comment.as_deref())
+        .collect();
+    comments.into_iter().collect()
+}
+
+/**
+ * Shows, per cluster, the hosts and resources of each state and the unhealthy hosts
+ * with the comment of the administrators and the jobs running on them
+ */
+pub fn show_cluster_health(ui: &mut egui::Ui, app: &ApplicationContext) {
+    const MAX_JOBS: usize = 5;
+
+    // Jobs running on each resource
+    let mut running: HashMap<u32, Vec<&Job>> = HashMap::new();
+    for job in app
+        .all_jobs
+        .iter()
+        .filter(|job| job.id != 0 && job.state == JobState::Running)
+    {
+        for resource_id in &job.assigned_resources {
+            running.entry(*resource_id).or_default().push(job);
+        }
+    }
+
+    let unhealthy_hosts: Vec<(&str, &Host, usize)> = app
+        .all_clusters
+        .iter()
+        .flat_map(|cluster| {
+            cluster.hosts.iter().map(move |host| {
+                let nb_unhealthy = host
+                    .cpus
+                    .iter()
+                    .flat_map(|cpu| &cpu.resources)
+                    .filter(|resource| is_unhealthy(resource.state))
+                    .count();
+                (cluster.name.as_str(), host, nb_unhealthy)
+            })
+        })
+        .filter(|(_, host, nb_unhealthy)| is_unhealthy(host.state) || *nb_unhealthy > 0)
+        .collect();
+
+    egui::CollapsingHeader::new(t!(
+        "app.dashboard.health.title",
+        count = unhealthy_hosts.len()
+    ))
+    .default_open(true)
+    .show(ui, |ui| {
+        if app.all_clusters.is_empty() {
+            ui.label(t!("app.dashboard.health.no_cluster"));
+            return;
+        }
+
+        egui::Grid::new("cluster_health_grid")
+            .num_columns(2 + HEALTH_STATES.len())
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(RichText::new(t!("app.dashboard.health.cluster")).strong());
+                ui.label("");
+                for state in HEALTH_STATES {
+                    ui.label(
+                        RichText::new(state_label(state))
+                            .color(state_color(state))
+                            .strong(),
+                    );
+                }
+                ui.end_row();
+
+                for cluster in &app.all_clusters {
+                    let mut hosts = StateCounts::default();
+                    let mut resources = StateCounts::default();
+                    for host in &cluster.hosts {
+                        hosts.add(host.state);
+                        for resource in host.cpus.iter().flat_map(|cpu| &cpu.resources) {
+                            resources.add(resource.state);
+                        }
+                    }
+
+                    ui.label(RichText::new(&cluster.name).strong());
+                    ui.label(t!("app.dashboard.health.hosts", count = hosts.total));
+                    hosts.cells_ui(ui);
+                    ui.end_row();
+                    ui.label("");
+                    ui.label(t!(
+                        "app.dashboard.health.resources",
+                        count = resources.total
+                    ));
+                    resources.cells_ui(ui);
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(5.0);
+        if unhealthy_hosts.is_empty() {
+            ui.label(t!("app.dashboard.health.all_healthy"));
+            return;
+        }
+        egui::Grid::new("unhealthy_hosts_grid")
+            .num_columns(6)
+            .striped(true)
+            .show(ui, |ui| {
+                for header in [
+                    t!("app.dashboard.health.host"),
+                    t!("app.dashboard.health.cluster"),
+                    t!("app.dashboard.health.state"),
+                    t!("app.dashboard.health.unhealthy_resources"),
+                    t!("app.dashboard.health.comment"),
+                    t!("app.dashboard.health.jobs"),
+                ] {
+                    ui.label(RichText::new(header).strong());
+                }
+                ui.end_row();
+
+                for (cluster, host, nb_unhealthy) in &unhealthy_hosts {
+                    let jobs: BTreeSet<(u32, &str)> = host
+                        .resource_ids
+                        .iter()
+                        .filter_map(|id| running.get(id))
+                        .flatten()
+                        .map(|job| (job.id, job.owner.as_str()))
+                        .collect();
+                    let jobs: Vec<String> = jobs
+                        .into_iter()
+                        .map(|(id, owner)| format!("#{} {}", id, owner))
+                        .collect();
+
+                    ui.label(&host.name);
+                    ui.label(*cluster);
+                    ui.colored_label(state_color(host.state), state_label(host.state));
+                    ui.label(format!("{}/{}", nb_unhealthy, host.resource_ids.len()));
+                    ui.label(host_comments(host).join(" / "));
+                    if jobs.is_empty() {
+                        ui.label("-");
+                    } else if jobs.len() > MAX_JOBS {
+                        ui.label(format!(
+                            "{} … (+{})",
+                            jobs[..MAX_JOBS].join(", "),
+                            jobs.len() - MAX_JOBS
+                        ))
+                        .on_hover_text(jobs.join("\n"));
+                    } else {
+                        ui.label(jobs.join(", "));
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+}
@@ -0,0 +1,222 @@
+use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::data_structure::job::{Job, JobState};
+use crate::models::utils::date_converter::{format_duration, format_timestamp};
+use eframe::egui::{self, RichText};
+use std::collections::{BTreeMap, BTreeSet};
+
+/**
+ * Statistics of the jobs of a time window
+ */
+#[derive(Default)]
+struct WindowStats {
+    jobs_by_state: BTreeMap<JobState, usize>,
+    core_hours_by_owner: BTreeMap<String, f64>, // Core-hours used inside the window
+    core_hours_by_queue: BTreeMap<String, f64>, // Core-hours used inside the window
+    total_core_hours: f64,
+    mean_wait_s: Option<f64>, // Mean wait of the jobs started in the window
+}
+
+impl WindowStats {
+    fn compute(jobs: &[Job], start_s: i64, end_s: i64, now: i64) -> Self {
+        let mut stats = WindowStats::default();
+        let mut waits = Vec::new();
+
+        for job in jobs.iter().filter(|job| job.id != 0) {
+            *stats.jobs_by_state.entry(job.state.clone()).or_default() += 1;
+
+            // Only the part of the run inside the window is counted
+            if job.start_time > 0 {
+                let run_end = if job.stop_time > 0 {
+                    job.stop_time
+                } else {
+                    now
+                };
+                let overlap_s = (run_end.min(end_s) - job.start_time.max(start_s)).max(0);
+                let core_hours = job.assigned_resources.len() as f64 * overlap_s as f64 / 3600.0;
+                *stats
+                    .core_hours_by_owner
+                    .entry(job.owner.clone())
+                    .or_default() += core_hours;
+                *stats
+                    .core_hours_by_queue
+                    .entry(job.queue.clone())
+                    .or_default() += core_hours;
+                stats.total_core_hours += core_hours;
+
+                if job.submission_time > 0 && (start_s..=end_s).contains(&job.start_time) {
+                    waits.push((job.start_time - job.submission_time).max(0));
+                }
+            }
+        }
+
+        if !waits.is_empty() {
+            stats.mean_wait_s = Some(waits.iter().sum::<i64>() as f64 / waits.len() as f64);
+        }
+        stats
+    }
+}
+
+/**
+ * Formats the difference between the compared window and the current one
+ */
+fn format_delta(current: f64, compared: f64, decimals: usize) -> String {
+    let delta = current - compared;
+    if delta.abs() < 10f64.powi(-(decimals as i32)) / 2.0 {
+        "=".to_string()
+    } else {
+        format!("{:+.*}", decimals, delta)
+    }
+}
+
+/**
+ * Adds a row of the comparison grid, with the value of each window and their difference
+ */
+fn stats_row(ui: &mut egui::Ui, label: &str, current: f64, compared: f64, decimals: usize) {
+    ui.label(label);
+    ui.label(format!("{:.*}", decimals, current));
+    ui.label(format!("{:.*}", decimals, compared));
+    ui.label(RichText::new(format_delta(current, compared, decimals)).strong());
+    ui.end_row();
+}
+
+/**
+ * Adds the rows of the largest values of a breakdown, in either window
+ */
+fn breakdown_rows(
+    ui: &mut egui::Ui,
+    current: &BTreeMap<String, f64>,
+    compared: &BTreeMap<String, f64>,
+) {
+    const MAX_ROWS: usize = 10;
+    let keys: BTreeSet<&String> = current.keys().chain(compared.keys()).collect();
+    let mut rows: Vec<(&String, f64, f64)> = keys
+        .into_iter()
+        .map(|key| {
+            (
+                key,
+                current.get(key).copied().unwrap_or(0.0),
+                compared.get(key).copied().unwrap_or(0.0),
+            )
+        })
+        .collect();
+    rows.sort_by(|a, b| b.1.max(b.2).total_cmp(&a.1.max(a.2)));
+
+    for (key, current, compared) in rows.iter().take(MAX_ROWS) {
+        stats_row(ui, key, *current, *compared, 1);
+    }
+    if rows.len() > MAX_ROWS {
+        ui.label(format!("(+{})", rows.len() - MAX_ROWS));
+        ui.end_row();
+    }
+}
+
+/**
+ * Shows the statistics of the current window next to the ones of the compared window
+ */
+pub fn show_comparison_panel(ui: &mut egui::Ui, app: &ApplicationContext, offset_s: i64) {
+    let now = chrono::Utc::now().timestamp();
+    let start_s = app.get_start_date().timestamp();
+    let end_s = app.get_end_date().timestamp();
+    let current = WindowStats::compute(&app.filtered_jobs, start_s, end_s, now);
+    let compared = WindowStats::compute(
+        &app.comparison_jobs,
+        start_s + offset_s,
+        end_s + offset_s,
+        now,
+    );
+
+    egui::CollapsingHeader::new(t!("app.dashboard.comparison.title"))
+        .default_open(true)
+        .show(ui, |ui| {
+            egui::Grid::new("comparison_panel_grid")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("");
+                    ui.label(
+                        RichText::new(t!(
+                            "app.dashboard.comparison.current",
+                            start = format_timestamp(start_s),
+                            end = format_timestamp(end_s)
+                        ))
+                        .strong(),
+                    );
+                    ui.label(
+                        RichText::new(t!(
+                            "app.dashboard.comparison.compared",
+                            start = format_timestamp(start_s + offset_s),
+                            end = format_timestamp(end_s + offset_s)
+                        ))
+                        .strong(),
+                    );
+                    ui.label(RichText::new(t!("app.dashboard.comparison.delta")).strong());
+                    ui.end_row();
+
+                    // Jobs by state
+                    let total = |stats: &WindowStats| stats.jobs_by_state.values().sum::<usize>();
+                    stats_row(
+                        ui,
+                        &t!("app.dashboard.total_jobs"),
+                        total(&current) as f64,
+                        total(&compared) as f64,
+                        0,
+                    );
+                    let states: BTreeSet<&JobState> = current
+                        .jobs_by_state
+                        .keys()
+                        .chain(compared.jobs_by_state.keys())
+                        .collect();
+                    for state in states {
+                        let count = |stats: &WindowStats| {
+                            stats.jobs_by_state.get(state).copied().unwrap_or(0) as f64
+                        };
+                        stats_row(ui, &state.get_label(), count(&current), count(&compared), 0);
+                    }
+
+                    // Mean wait time
+                    ui.label(t!("app.dashboard.comparison.mean_wait"));
+                    let wait = |stats: &WindowStats| {
+                        stats
+                            .mean_wait_s
+                            .map_or("-".to_string(), |wait| format_duration(wait as i64))
+                    };
+                    ui.label(wait(&current));
+                    ui.label(wait(&compared));
+                    match (current.mean_wait_s, compared.mean_wait_s) {
+                        (Some(current_wait), Some(compared_wait)) => {
+                            let delta = (current_wait - compared_wait) as i64;
+                            let sign = if delta >= 0 { "+" } else { "" };
+                            ui.label(
+                                RichText::new(format!("{}{}", sign, format_duration(delta)))
+                                    .strong(),
+                            )
+                        }
+                        _ => ui.label("-"),
+                    };
+                    ui.end_row();
+
+                    // Core-hours
+                    stats_row(
+                        ui,
+                        &t!("app.dashboard.comparison.core_hours"),
+                        current.total_core_hours,
+                        compared.total_core_hours,
+                        1,
+                    );
+                    ui.label(RichText::new(t!("app.dashboard.comparison.by_owner")).strong());
+                    ui.end_row();
+                    breakdown_rows(
+                        ui,
+                        &current.core_hours_by_owner,
+                        &compared.core_hours_by_owner,
+                    );
+                    ui.label(RichText::new(t!("app.dashboard.comparison.by_queue")).strong());
+                    ui.end_row();
+                    breakdown_rows(
+                        ui,
+                        &current.core_hours_by_queue,
+                        &compared.core_hours_by_queue,
+                    );
+                });
+        });
+}
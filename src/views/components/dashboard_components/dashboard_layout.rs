@@ -0,0 +1,175 @@
+use super::time_series::SeriesKind;
+use crate::views::components::named_profiles::{NamedProfile, NamedProfiles};
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+/**
+ * Kind of a widget of the dashboard, the catalogue of the widgets that can be added
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum WidgetKind {
+    TotalJobs,
+    StateCounts,
+    StartTime,
+    EndTime,
+    StateChart,
+    StateDonut,
+    JobsOverTime,
+    Throughput,
+    WaitHistogram,
+    RuntimeScatter,
+    RunningJobsSeries,
+    WaitingJobsSeries,
+    BusyCoresSeries,
+    Utilisation,
+    TopUsers,
+    Alerts,
+}
+
+impl WidgetKind {
+    pub fn get_label(&self) -> String {
+        match self {
+            WidgetKind::TotalJobs => t!("app.dashboard.total_jobs").to_string(),
+            WidgetKind::StateCounts => t!("app.dashboard.widgets.state_counts").to_string(),
+            WidgetKind::StartTime => t!("app.dashboard.start_time").to_string(),
+            WidgetKind::EndTime => t!("app.dashboard.end_time").to_string(),
+            WidgetKind::StateChart => t!("app.dashboard.widgets.state_chart").to_string(),
+            WidgetKind::StateDonut => t!("app.dashboard.widgets.state_donut").to_string(),
+            WidgetKind::JobsOverTime => t!("app.dashboard.widgets.jobs_over_time").to_string(),
+            WidgetKind::Throughput => t!("app.dashboard.widgets.throughput").to_string(),
+            WidgetKind::WaitHistogram => t!("app.dashboard.widgets.wait_histogram").to_string(),
+            WidgetKind::RuntimeScatter => t!("app.dashboard.widgets.runtime_scatter").to_string(),
+            WidgetKind::RunningJobsSeries => SeriesKind::RunningJobs.get_label(),
+            WidgetKind::WaitingJobsSeries => SeriesKind::WaitingJobs.get_label(),
+            WidgetKind::BusyCoresSeries => SeriesKind::BusyCores.get_label(),
+            WidgetKind::Utilisation => t!("app.dashboard.widgets.utilisation").to_string(),
+            WidgetKind::TopUsers => t!("app.dashboard.widgets.top_users").to_string(),
+            WidgetKind::Alerts => t!("app.dashboard.widgets.alerts").to_string(),
+        }
+    }
+
+    /**
+     * Returns the size of a new widget, in columns and rows
+     */
+    fn default_size(&self) -> (usize, usize) {
+        match self {
+            WidgetKind::StateCounts => (3, 1),
+            WidgetKind::StateChart
+            | WidgetKind::JobsOverTime
+            | WidgetKind::Throughput
+            | WidgetKind::WaitHistogram
+            | WidgetKind::RuntimeScatter
+            | WidgetKind::RunningJobsSeries
+            | WidgetKind::WaitingJobsSeries
+            | WidgetKind::BusyCoresSeries => (2, 2),
+            WidgetKind::StateDonut | WidgetKind::TopUsers | WidgetKind::Alerts => (1, 2),
+            _ => (1, 1),
+        }
+    }
+}
+
+/**
+ * A widget placed on the dashboard, its size is given in cells of the grid
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DashboardWidget {
+    pub kind: WidgetKind,
+    pub width: usize,  // Number of columns
+    pub height: usize, // Number of rows
+}
+
+impl DashboardWidget {
+    pub const MAX_HEIGHT: usize = 4;
+
+    pub fn new(kind: WidgetKind) -> Self {
+        let (width, height) = kind.default_size();
+        DashboardWidget {
+            kind,
+            width,
+            height,
+        }
+    }
+}
+
+/**
+ * An ordered list of widgets saved under a name
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NamedLayout {
+    pub name: String,
+    pub columns: usize, // Number of columns of the grid
+    pub widgets: Vec<DashboardWidget>,
+}
+
+impl NamedLayout {
+    pub const MAX_COLUMNS: usize = 6;
+
+    fn new(name: String, kinds: &[WidgetKind]) -> Self {
+        NamedLayout {
+            name,
+            columns: 3,
+            widgets: kinds
+                .iter()
+                .map(|kind| DashboardWidget::new(*kind))
+                .collect(),
+        }
+    }
+}
+
+impl NamedProfile for NamedLayout {
+    fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+}
+
+/**
+ * The layouts of the dashboard and the one shown, the default layouts being created
+ * when the file does not exist
+ */
+pub type DashboardLayouts = NamedProfiles<NamedLayout>;
+
+impl Default for DashboardLayouts {
+    fn default() -> Self {
+        DashboardLayouts {
+            selected: 0,
+            profiles: vec![
+                NamedLayout::new(
+                    t!("app.dashboard.layout.metrics").to_string(),
+                    &[
+                        WidgetKind::TotalJobs,
+                        WidgetKind::StartTime,
+                        WidgetKind::EndTime,
+                        WidgetKind::StateCounts,
+                    ],
+                ),
+                NamedLayout::new(
+                    t!("app.dashboard.layout.charts").to_string(),
+                    &[
+                        WidgetKind::JobsOverTime,
+                        WidgetKind::StateDonut,
+                        WidgetKind::WaitHistogram,
+                        WidgetKind::TopUsers,
+                        WidgetKind::RuntimeScatter,
+                        WidgetKind::StateChart,
+                    ],
+                ),
+                NamedLayout::new(
+                    t!("app.dashboard.layout.operations").to_string(),
+                    &[
+                        WidgetKind::Utilisation,
+                        WidgetKind::TotalJobs,
+                        WidgetKind::StateCounts,
+                        WidgetKind::Alerts,
+                        WidgetKind::TopUsers,
+                        WidgetKind::BusyCoresSeries,
+                        WidgetKind::WaitingJobsSeries,
+                    ],
+                ),
+            ],
+        }
+    }
+}
+
+impl DashboardLayouts {
+    pub const FILE_PATH: &'static str = "dashboard_layouts.json";
+}
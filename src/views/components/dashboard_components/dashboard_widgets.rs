@@ -0,0 +1,258 @@
+use super::dashboard_layout::WidgetKind;
+use super::metric_box::MetricBox;
+use super::metric_chart::{
+    create_jobs_over_time_chart, create_jobstate_chart, create_jobstate_donut_chart,
+    create_runtime_scatter_chart, create_throughput_chart, create_wait_time_histogram,
+};
+use super::time_series::{show_time_series, SeriesKind};
+use crate::models::data_structure::annotation::AnnotationKind;
+use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::data_structure::job::JobState;
+use crate::models::data_structure::resource::ResourceState;
+use crate::models::utils::date_converter::format_timestamp_as;
+use crate::views::components::gantt_heat_strip::heat_color;
+use eframe::egui::{self, Color32, RichText, Vec2};
+use std::collections::{HashMap, HashSet};
+use strum::IntoEnumIterator;
+
+const GREY: Color32 = Color32::from_rgb(128, 128, 128);
+const BLUE: Color32 = Color32::from_rgb(70, 130, 180);
+
+/**
+ * Shows a widget of the dashboard in the given size
+ */
+pub fn show_widget(ui: &mut egui::Ui, kind: WidgetKind, app: &ApplicationContext, size: Vec2) {
+    match kind {
+        WidgetKind::TotalJobs => {
+            MetricBox::new(kind.get_label(), app.filtered_jobs.len().to_string(), GREY)
+                .ui_sized(ui, size);
+        }
+        WidgetKind::StartTime | WidgetKind::EndTime => {
+            let date = if kind == WidgetKind::StartTime {
+                app.get_start_date()
+            } else {
+                app.get_end_date()
+            };
+            let value = format_timestamp_as(date.timestamp(), "%Y-%m-%d %H:%M").unwrap_or_default();
+            MetricBox::new(kind.get_label(), value, BLUE).ui_sized(ui, size);
+        }
+        WidgetKind::StateChart => {
+            create_jobstate_chart(app.filtered_jobs.clone()).ui_with_chart(ui, size);
+        }
+        WidgetKind::StateDonut => {
+            create_jobstate_donut_chart(&app.filtered_jobs).ui_with_chart(ui, size);
+        }
+        WidgetKind::JobsOverTime => {
+            create_jobs_over_time_chart(&app.filtered_jobs, window(app)).ui_with_chart(ui, size);
+        }
+        WidgetKind::Throughput => {
+            create_throughput_chart(&app.filtered_jobs, window(app)).ui_with_chart(ui, size);
+        }
+        WidgetKind::WaitHistogram => {
+            create_wait_time_histogram(&app.filtered_jobs).ui_with_chart(ui, size);
+        }
+        WidgetKind::RuntimeScatter => {
+            let now = chrono::Utc::now().timestamp();
+            create_runtime_scatter_chart(&app.filtered_jobs, now).ui_with_chart(ui, size);
+        }
+        WidgetKind::RunningJobsSeries => show_time_series(ui, SeriesKind::RunningJobs, app, size),
+        WidgetKind::WaitingJobsSeries => show_time_series(ui, SeriesKind::WaitingJobs, app, size),
+        WidgetKind::BusyCoresSeries => show_time_series(ui, SeriesKind::BusyCores, app, size),
+        WidgetKind::Utilisation => {
+            let (busy, total) = resources_in_use(app);
+            let fraction = if total > 0 {
+                busy as f32 / total as f32
+            } else {
+                0.0
+            };
+            let value = format!("{:.0} % ({}/{})", fraction * 100.0, busy, total);
+            MetricBox::new(kind.get_label(), value, heat_color(fraction)).ui_sized(ui, size);
+        }
+        WidgetKind::StateCounts => {
+            widget_frame(ui, &kind.get_label(), size, |ui| state_counts_ui(ui, app))
+        }
+        WidgetKind::TopUsers => {
+            widget_frame(ui, &kind.get_label(), size, |ui| top_users_ui(ui, app))
+        }
+        WidgetKind::Alerts => widget_frame(ui, &kind.get_label(), size, |ui| alerts_ui(ui, app)),
+    }
+}
+
+/**
+ * Returns the time window of the application, in seconds
+ */
+fn window(app: &ApplicationContext) -> (i64, i64) {
+    (
+        app.get_start_date().timestamp(),
+        app.get_end_date().timestamp(),
+    )
+}
+
+/**
+ * Shows the frame of a widget with a title, like the metric boxes
+ */
+pub(crate) fn widget_frame(
+    ui: &mut egui::Ui,
+    title: &str,
+    size: Vec2,
+    add_contents: impl FnOnce(&mut egui::Ui),
+) {
+    egui::Frame::none()
+        .fill(if ui.ctx().style().visuals.dark_mode {
+            egui::Color32::from_gray(28)
+        } else {
+            egui::Color32::from_gray(255)
+        })
+        .rounding(6.0)
+        .stroke(egui::Stroke::new(0.7, GREY))
+        .inner_margin(6.0)
+        .show(ui, |ui| {
+            ui.set_min_size(size - Vec2::splat(12.0));
+            ui.set_max_size(size - Vec2::splat(12.0));
+            ui.vertical_centered(|ui| {
+                ui.label(
+                    RichText::new(title)
+                        .color(egui::Color32::from_gray(160))
+                        .size(13.0),
+                );
+            });
+            egui::ScrollArea::vertical()
+                .id_salt(title)
+                .auto_shrink([false, false])
+                .show(ui, add_contents);
+        });
+}
+
+/**
+ * Returns the number of resources used by a running job and the number of resources
+ */
+fn resources_in_use(app: &ApplicationContext) -> (usize, usize) {
+    let total = app
+        .all_clusters
+        .iter()
+        .flat_map(|cluster| &cluster.hosts)
+        .map(|host| host.resource_ids.len())
+        .sum();
+    let busy: HashSet<u32> = app
+        .all_jobs
+        .iter()
+        .filter(|job| job.id != 0 && job.state == JobState::Running)
+        .flat_map(|job| job.assigned_resources.iter().copied())
+        .collect();
+    (busy.len(), total)
+}
+
+/**
+ * Shows the number of jobs of every state having jobs
+ */
+fn state_counts_ui(ui: &mut egui::Ui, app: &ApplicationContext) {
+    ui.horizontal_wrapped(|ui| {
+        for state in JobState::iter() {
+            let count = app
+                .filtered_jobs
+                .iter()
+                .filter(|job| job.state == state)
+                .count();
+            if count > 0 {
+                ui.label(RichText::new("⏺").color(state.get_color().1));
+                ui.label(state.get_label());
+                ui.label(RichText::new(count.to_string()).strong().size(18.0));
+                ui.add_space(12.0);
+            }
+        }
+    });
+}
+
+/**
+ * Shows the owners using the most resources with their running jobs
+ */
+fn top_users_ui(ui: &mut egui::Ui, app: &ApplicationContext) {
+    // Owner -> (number of jobs, resources used by the running jobs)
+    let mut users: HashMap<&str, (usize, usize)> = HashMap::new();
+    for job in app.filtered_jobs.iter().filter(|job| job.id != 0) {
+        let user = users.entry(&job.owner).or_default();
+        user.0 += 1;
+        if job.state == JobState::Running {
+            user.1 += job.assigned_resources.len();
+        }
+    }
+    let mut users: Vec<_> = users.into_iter().collect();
+    users.sort_by_key(|(owner, (jobs, resources))| {
+        (
+            std::cmp::Reverse(*resources),
+            std::cmp::Reverse(*jobs),
+            *owner,
+        )
+    });
+
+    egui::Grid::new("top_users_grid")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label(RichText::new(t!("app.dashboard.widgets.owner")).strong());
+            ui.label(RichText::new(t!("app.dashboard.widgets.jobs")).strong());
+            ui.label(RichText::new(t!("app.dashboard.widgets.running_resources")).strong());
+            ui.end_row();
+            for (owner, (jobs, resources)) in users.iter().take(10) {
+                ui.label(*owner);
+                ui.label(jobs.to_string());
+                ui.label(resources.to_string());
+                ui.end_row();
+            }
+        });
+}
+
+/**
+ * Shows the jobs in error, the unavailable hosts and the ongoing incidents
+ */
+fn alerts_ui(ui: &mut egui::Ui, app: &ApplicationContext) {
+    let warning = ui.visuals().warn_fg_color;
+    let mut nb_alerts = 0;
+
+    let nb_errors = app
+        .filtered_jobs
+        .iter()
+        .filter(|job| job.state == JobState::Error)
+        .count();
+    if nb_errors > 0 {
+        ui.colored_label(
+            warning,
+            t!("app.dashboard.widgets.jobs_in_error", count = nb_errors),
+        );
+        nb_alerts += 1;
+    }
+
+    let unavailable: Vec<&str> = app
+        .all_clusters
+        .iter()
+        .flat_map(|cluster| &cluster.hosts)
+        .filter(|host| matches!(host.state, ResourceState::Dead | ResourceState::Absent))
+        .map(|host| host.name.as_str())
+        .collect();
+    if !unavailable.is_empty() {
+        ui.colored_label(
+            warning,
+            t!(
+                "app.dashboard.widgets.unavailable_hosts",
+                count = unavailable.len()
+            ),
+        )
+        .on_hover_text(unavailable.join(", "));
+        nb_alerts += 1;
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    for incident in app
+        .annotations
+        .overlapping(now, now)
+        .filter(|annotation| annotation.kind == AnnotationKind::Incident)
+    {
+        ui.colored_label(incident.kind.get_color(), format!("⚠ {}", incident.label))
+            .on_hover_text(&incident.description);
+        nb_alerts += 1;
+    }
+
+    if nb_alerts == 0 {
+        ui.label(t!("app.dashboard.widgets.no_alert"));
+    }
+}
@@ -1,12 +1,24 @@
-use super::job_table_col_selection::ColumnSelection;
-use super::job_table_sorting::SortKey;
+use super::job_table_col_selection::{ColumnInfo, ColumnProfiles, ColumnSelection};
+use super::job_table_export::JobTableExport;
+use super::job_table_grouping::{group_jobs, GroupKey, JobGroup};
+use super::job_table_sorting::{sort_jobs_by_keys, SortKey};
 use crate::models::data_structure::application_context::ApplicationContext;
 use crate::models::utils::date_converter::format_timestamp;
 use crate::models::utils::utils::get_tree_structure_for_job;
 use crate::{models::data_structure::job::Job, views::components::job_details::JobDetailsWindow};
 use eframe::egui;
 use egui::{RichText, Sense, Ui};
-use egui_extras::{Column, TableBuilder};
+use egui_extras::{Column, TableBuilder, TableRow};
+use std::collections::HashSet;
+use strum::IntoEnumIterator;
+
+/**
+ * A row of the table, either the header of a group or a job
+ */
+enum TableLine {
+    Group(usize), // Index of the group
+    Job(usize, usize), // Index of the job and its number in the table or in its group
+}
 
 /**
  * Struct for the job table
@@ -18,9 +30,17 @@ pub struct JobTable {
     start_idx: usize, // Start index for the jobs
     end_idx: usize, // End index for the jobs
     displayed_jobs_per_page: Vec<Job>, // Jobs displayed per page
-    sort_key: SortKey, // Sort key for the jobs
-    sort_ascending: bool, // True if the sorting is ascending
-    column_selection: ColumnSelection, // Column selection for the table
+    sort_keys: Vec<(SortKey, bool)>, // Sort keys for the jobs, the first one first, with true if the sorting is ascending
+    column_profiles: ColumnProfiles, // Column profiles of the table, saved in a file
+    new_profile_name: String, // Name of the next column profile created
+    profiles_error: Option<String>, // Error of the last save of the profiles
+    columns_changed: bool, // The profiles are saved once the pointer is released
+    last_widths: Vec<f32>, // Widths of the visible columns in the last frame
+    search: String, // Text searched in the visible columns
+    infinite_scroll: bool, // True to scroll through all the jobs instead of showing pages
+    export: JobTableExport, // Export of the displayed jobs to a file
+    group_by: Option<GroupKey>, // Key the rows are grouped by, None for a flat list
+    collapsed_groups: HashSet<String>, // Names of the groups whose jobs are hidden
 }
 
 /**
@@ -35,9 +55,17 @@ impl Default for JobTable {
             start_idx: 0, // Default start index is 0
             end_idx: 0, // Default end index is 0
             displayed_jobs_per_page: Vec::new(), // No jobs displayed by default
-            sort_key: SortKey::Id, // Default sort key is the job id
-            sort_ascending: true, // Default sorting is ascending
-            column_selection: ColumnSelection::default(), // Default column selection
+            sort_keys: vec![(SortKey::Id, true)], // Default sorting is by ascending job id
+            column_profiles: ColumnProfiles::load_from_file(ColumnProfiles::FILE_PATH), // Saved column profiles
+            new_profile_name: String::new(), // No name by default
+            profiles_error: None, // No error by default
+            columns_changed: false, // Nothing to save by default
+            last_widths: Vec::new(), // No width by default
+            search: String::new(), // No search by default
+            infinite_scroll: false, // Pagination by default
+            export: JobTableExport::default(), // Default export format
+            group_by: None, // No grouping by default
+            collapsed_groups: HashSet::new(), // Groups expanded by default
         }
     }
 }
@@ -46,24 +74,38 @@ impl Default for JobTable {
  * Implementation of the job table
  */
 impl JobTable {
+    const PAGE_SIZES: [usize; 5] = [10, 20, 50, 100, 200]; // Choices of the number of jobs per page
+    const ROW_HEIGHT: f32 = 20.0;
+    const SCROLL_HEIGHT: f32 = 600.0; // Height of the table in infinite scroll mode
+
     pub fn ui(&mut self, ui: &mut Ui, app: &mut ApplicationContext) {
+        // Time until which the running jobs run, the same for the sorting and the cells
+        let now = chrono::Utc::now().timestamp();
         self.displayed_jobs_per_page = app.filtered_jobs.clone();
-        self.sort_key
-            .sort_jobs(&mut self.displayed_jobs_per_page, self.sort_ascending);
+        let search = self.search.trim().to_lowercase();
+        if !search.is_empty() {
+            let column_selection = self.column_profiles.current();
+            self.displayed_jobs_per_page
+                .retain(|job| Self::matches_search(column_selection, job, &search, now));
+        }
+        sort_jobs_by_keys(&mut self.displayed_jobs_per_page, &self.sort_keys, now);
+
+        // The jobs are grouped after being sorted, so the sorting applies within each group
+        let groups = self
+            .group_by
+            .map(|key| group_jobs(&self.displayed_jobs_per_page, key, now))
+            .unwrap_or_default();
+        let lines = self.table_lines(&groups);
 
         ui.add_space(10.0);
         ui.heading(RichText::new(t!("app.job_table.title")).strong().size(20.0));
         ui.add_space(8.0);
 
+        let nb_jobs = self.displayed_jobs_per_page.len();
+        let total_pages = lines.len().div_ceil(self.jobs_per_page).max(1);
+        self.page = self.page.min(total_pages - 1);
         self.start_idx = self.page * self.jobs_per_page;
-        self.end_idx = (self.start_idx + self.jobs_per_page).min(app.filtered_jobs.len());
-        let total_pages =
-            (app.filtered_jobs.len() as f32 / self.jobs_per_page as f32).ceil() as usize;
-
-        if self.start_idx >= app.filtered_jobs.len() {
-            self.reset_pagination();
-            return;
-        }
+        self.end_idx = (self.start_idx + self.jobs_per_page).min(lines.len());
 
         ui.horizontal(|ui| {
             // Left side with Options
@@ -71,16 +113,68 @@ impl JobTable {
                 ui.menu_button(t!("app.job_table.settings"), |ui| {
                     ui.set_max_height(500.0);
 
-                    // Column selection
-                    self.column_selection.ui(ui);
+                    // Column profiles and selection
+                    self.profiles_ui(ui);
+                    ui.separator();
+                    self.columns_changed |= self.column_profiles.current_mut().ui(ui);
                 });
                 ui.menu_button("❓", |ui| {
                     ui.label(t!("app.job_table.help"));
                 });
+
+                // Export of the jobs of the table, in the order they are sorted
+                self.export.ui(
+                    ui,
+                    &self.displayed_jobs_per_page,
+                    self.column_profiles.current(),
+                );
+
+                // Grouping of the rows
+                ui.label(t!("app.job_table.group.label"));
+                let group_by = self.group_by;
+                egui::ComboBox::from_id_salt("job_table_group_by")
+                    .selected_text(self.group_by.map_or(
+                        t!("app.job_table.group.no_group").to_string(),
+                        |key| key.get_label(),
+                    ))
+                    .show_ui(ui, |ui| {
+                        let no_group = t!("app.job_table.group.no_group");
+                        ui.selectable_value(&mut self.group_by, None, no_group);
+                        for key in GroupKey::iter() {
+                            ui.selectable_value(&mut self.group_by, Some(key), key.get_label());
+                        }
+                    });
+                if self.group_by != group_by {
+                    self.collapsed_groups.clear();
+                    self.page = 0;
+                }
+
+                // Search in the visible columns
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.search)
+                        .hint_text(t!("app.job_table.search"))
+                        .desired_width(200.0),
+                );
+                if response.changed() {
+                    self.page = 0;
+                }
+                if !search.is_empty() {
+                    ui.label(t!(
+                        "app.job_table.matches",
+                        count = nb_jobs,
+                        total = app.filtered_jobs.len()
+                    ));
+                }
             });
 
             // Right side with Pagination, using remaining space
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.checkbox(&mut self.infinite_scroll, t!("app.job_table.infinite_scroll"));
+                if self.infinite_scroll {
+                    return;
+                }
+                ui.separator();
+
                 let mut pagination_ui = |ui: &mut Ui| {
                     if ui
                         .button(RichText::new(t!("app.job_table.next")).size(14.0))
@@ -89,10 +183,18 @@ impl JobTable {
                     {
                         self.page += 1;
                     }
-                    ui.label(
-                        RichText::new(format!("Page {} / {}", self.page + 1, total_pages))
-                            .size(14.0),
-                    );
+
+                    // Jump to a page, the pages are numbered from 1
+                    let mut page_number = self.page + 1;
+                    ui.label(RichText::new(format!("/ {}", total_pages)).size(14.0));
+                    if ui
+                        .add(egui::DragValue::new(&mut page_number).range(1..=total_pages))
+                        .changed()
+                    {
+                        self.page = page_number - 1;
+                    }
+                    ui.label(RichText::new("Page").size(14.0));
+
                     if ui
                         .button(RichText::new(t!("app.job_table.previous")).size(14.0))
                         .clicked()
@@ -100,6 +202,22 @@ impl JobTable {
                     {
                         self.page -= 1;
                     }
+                    ui.separator();
+
+                    // Number of jobs per page
+                    egui::ComboBox::from_id_salt("job_table_page_size")
+                        .selected_text(self.jobs_per_page.to_string())
+                        .show_ui(ui, |ui| {
+                            for size in Self::PAGE_SIZES {
+                                if ui
+                                    .selectable_value(&mut self.jobs_per_page, size, size.to_string())
+                                    .clicked()
+                                {
+                                    self.page = 0;
+                                }
+                            }
+                        });
+                    ui.label(t!("app.job_table.page_size"));
                 };
                 pagination_ui(ui);
             });
@@ -110,147 +228,224 @@ impl JobTable {
         egui::ScrollArea::horizontal().show(ui, |ui| {
             // Table with pagination, sorting and selection
             let available_width = ui.available_width();
+            let column_selection = self.column_profiles.current();
+            let column_keys: Vec<SortKey> = column_selection
+                .selected()
+                .map(|value| value.sort_key)
+                .collect();
             let mut table = TableBuilder::new(ui)
+                .id_salt(("job_table", self.column_profiles.selected, &column_keys))
                 .striped(true)
                 .resizable(true)
                 .sense(Sense::click())
                 .column(Column::auto().at_least(2.0).resizable(true));
+            if self.infinite_scroll {
+                table = table.max_scroll_height(Self::SCROLL_HEIGHT);
+            }
 
-            // Add columns based on the selection
-            for value in self.column_selection.values.values() {
-                if value.selected {
-                    table = table.column(
-                        Column::remainder()
-                            .at_least(10.0)
-                            .at_most(available_width)
-                            .resizable(true),
-                    );
-                }
+            // Add columns based on the selection, with the width chosen by the user if any
+            for value in column_selection.selected() {
+                let column = match value.width {
+                    Some(width) => Column::initial(width),
+                    None => Column::remainder(),
+                };
+                table = table.column(
+                    column
+                        .at_least(10.0)
+                        .at_most(available_width)
+                        .resizable(true),
+                );
             }
 
             // Table header
+            let mut clicked_header = None;
+            let mut toggled_group = None;
+            let mut widths = Vec::new();
             table.header(20.0, |mut header| {
                     header.col(|ui| {
                         ui.label(RichText::new(t!("app.job_table.table.row")).strong());
                     });
 
                     // Add columns in header based on the selection
-                    for value in self.column_selection.values.values() {
-                        if value.selected {
-                            header.col(|ui| {
-                                let is_current_column = value.sort_key == self.sort_key;
-
-                                let header_btn;
+                    for value in column_selection.selected() {
+                        let (rect, _) = header.col(|ui| {
+                            let position = self
+                                .sort_keys
+                                .iter()
+                                .position(|(key, _)| *key == value.sort_key);
 
-                                // If the current column is selected, add an arrow to indicate the sorting direction
-                                if is_current_column {
-                                    header_btn = egui::Button::new(format!(
-                                        "{} {}",
+                            // If the column is a sort key, add an arrow to indicate the sorting direction
+                            // and its rank when there are several keys
+                            let header_btn = match position {
+                                Some(index) => {
+                                    let arrow = if self.sort_keys[index].1 { '⬆' } else { '⬇' };
+                                    let rank = if self.sort_keys.len() > 1 {
+                                        (index + 1).to_string()
+                                    } else {
+                                        String::new()
+                                    };
+                                    egui::Button::new(format!(
+                                        "{} {}{}",
                                         t!(value.name.clone()),
-                                        if self.sort_ascending { '⬆' } else { '⬇' }
+                                        arrow,
+                                        rank
                                     ))
-                                    .frame(true);
-                                } else { // Otherwise, just add the column name
-                                    header_btn = egui::Button::new(t!(value.name.clone())).frame(false);
+                                    .frame(index == 0)
                                 }
+                                // Otherwise, just add the column name
+                                None => egui::Button::new(t!(value.name.clone())).frame(false),
+                            };
 
-                                // If the column is clicked, change the sorting key and arrow direction
-                                if ui.add(header_btn).clicked() {
-                                    self.sort_key = value.sort_key;
-                                    self.sort_ascending = !self.sort_ascending;
-                                    self.page = 0;
-                                }
-                            });
-                        }
+                            // If the column is clicked, change the sorting keys, shift-click adds a secondary key
+                            if ui.add(header_btn).clicked() {
+                                let shift = ui.input(|i| i.modifiers.shift);
+                                clicked_header = Some((value.sort_key, shift));
+                            }
+                        });
+                        widths.push(rect.width());
                     }
                 })
 
                 // Table bodys
                 .body(|mut body| {
-                    for job in self.displayed_jobs_per_page[self.start_idx..self.end_idx].iter() {
-                        body.row(20.0, |mut row| {
-                            // Row index
-                            let row_index = self.start_idx + row.index() + 1;
+                    let details_window = &mut self.details_window;
+                    let collapsed_groups = &self.collapsed_groups;
+                    let jobs = &self.displayed_jobs_per_page;
+                    let mut row_ui = |mut row: TableRow, line: &TableLine| match *line {
+                        TableLine::Group(index) => {
+                            let group = &groups[index];
+                            let collapsed = collapsed_groups.contains(&group.name);
+                            let arrow = if collapsed { "⏵" } else { "⏷" };
                             row.col(|ui| {
-                                ui.label(row_index.to_string());
+                                ui.label(RichText::new(arrow).strong());
                             });
 
-                            for value in self.column_selection.values.values() {
-                                if value.selected {
-                                    row.col(|ui| match value.sort_key {
-                                        SortKey::Id => {
-                                            ui.label(job.id.to_string());
-                                        }
-                                        SortKey::Owner => {
-                                            ui.label(job.owner.to_string());
-                                        }
-                                        SortKey::State => {
-                                            let state_text = job.state.get_label();
-                                            let (state_color, bg_color) = job.state.get_color();
-                                            ui.label(
-                                                egui::RichText::new(state_text)
-                                                    .color(state_color)
-                                                    .background_color(bg_color)
-                                                    .strong(),
-                                            );
-                                        }
-                                        SortKey::StartTime => {
-                                            ui.label(format_timestamp(job.start_time));
-                                        }
-                                        SortKey::WallTime => {
-                                            ui.label(job.walltime.to_string());
-                                        }
-                                        SortKey::Queue => {
-                                            ui.label(&job.queue);
-                                        }
-                                        SortKey::Command => {
-                                            ui.label(&job.command);
-                                        }
-                                        SortKey::Message => {
-                                            ui.label(job.message.as_deref().unwrap_or(""));
-                                        }
-                                        SortKey::SubmissionTime => {
-                                            ui.label(format_timestamp(job.submission_time));
-                                        }
-                                        SortKey::ScheduledStartTime => {
-                                            ui.label(format_timestamp(job.scheduled_start));
-                                        }
-                                        SortKey::StopTime => {
-                                            ui.label(format_timestamp(job.stop_time));
-                                        }
-                                        SortKey::ExitCode => {
-                                            ui.label(
-                                                job.exit_code.map_or("N/A".to_string(), |code| {
-                                                    code.to_string()
-                                                }),
-                                            );
-                                        }
-                                        SortKey::Clusters => {
-                                            ui.label(job.clusters.join(", "));
-                                        }
-                                    });
-                                }
+                            // The subtotals fill the columns, the last one shows the remaining ones
+                            let parts = [
+                                group.name.clone(),
+                                t!("app.job_table.group.jobs", count = group.jobs.len())
+                                    .to_string(),
+                                t!(
+                                    "app.job_table.group.core_hours",
+                                    hours = format!("{:.1}", group.core_hours)
+                                )
+                                .to_string(),
+                                group.state_breakdown(),
+                            ];
+                            let nb_columns = column_selection.selected().count();
+                            for index in 0..nb_columns {
+                                let text = if index + 1 == nb_columns {
+                                    parts[index.min(parts.len())..].join(" · ")
+                                } else {
+                                    parts.get(index).cloned().unwrap_or_default()
+                                };
+                                row.col(|ui| {
+                                    ui.label(RichText::new(text).strong());
+                                });
+                            }
+
+                            // Clicking the header shows or hides the jobs of the group
+                            let response = row.response().interact(Sense::click());
+                            if response.on_hover_text(parts.join("\n")).clicked() {
+                                toggled_group = Some(group.name.clone());
+                            }
+                        }
+                        TableLine::Job(index, number) => {
+                            let job = &jobs[index];
+                            row.col(|ui| {
+                                ui.label(number.to_string());
+                            });
+
+                            for value in column_selection.selected() {
+                                row.col(|ui| match value.sort_key {
+                                    SortKey::State => {
+                                        let state_text = job.state.get_label();
+                                        let (state_color, bg_color) = job.state.get_color();
+                                        ui.label(
+                                            egui::RichText::new(state_text)
+                                                .color(state_color)
+                                                .background_color(bg_color)
+                                                .strong(),
+                                        );
+                                    }
+                                    _ => {
+                                        ui.label(cell_text(job, value, now));
+                                    }
+                                });
                             }
 
                             // Clickable row
                             let response = row.response().interact(Sense::click());
                             if response.clicked() {
-                                for window in self.details_window.iter_mut() {
+                                for window in details_window.iter_mut() {
                                     if window.job.id == job.id {
                                         window.open = true;
                                         return;
                                     }
                                 }
-                                self.details_window.push(JobDetailsWindow::new(
+                                details_window.push(JobDetailsWindow::new(
                                     job.clone(),
                                     get_tree_structure_for_job(job, &app.all_clusters),
                                 ));
                             }
+                        }
+                    };
+
+                    if self.infinite_scroll {
+                        // Only the visible rows are shown
+                        body.rows(Self::ROW_HEIGHT, lines.len(), |row| {
+                            let index = row.index();
+                            row_ui(row, &lines[index]);
                         });
+                    } else {
+                        for line in &lines[self.start_idx..self.end_idx] {
+                            body.row(Self::ROW_HEIGHT, |row| {
+                                row_ui(row, line);
+                            });
+                        }
                     }
                 });
+
+            if let Some((sort_key, shift)) = clicked_header {
+                self.click_sort_key(sort_key, shift);
+                self.page = 0;
+            }
+            if let Some(name) = toggled_group {
+                if !self.collapsed_groups.remove(&name) {
+                    self.collapsed_groups.insert(name);
+                }
+            }
+
+            // Widths changed while dragging in the table are the widths chosen by the user
+            let dragging = ui.input(|i| i.pointer.primary_down()) && ui.ui_contains_pointer();
+            if dragging && self.last_widths.len() == widths.len() && self.last_widths != widths {
+                let columns = &mut self.column_profiles.current_mut().columns;
+                let selected = columns.iter_mut().filter(|value| value.selected);
+                for (value, width) in selected.zip(&widths) {
+                    value.width = Some(*width);
+                }
+                self.columns_changed = true;
+            }
+            self.last_widths = widths;
         });
 
+        // The profiles are saved once the columns are no longer dragged
+        if self.columns_changed && !ui.input(|i| i.pointer.any_down()) {
+            self.columns_changed = false;
+            self.profiles_error = self
+                .column_profiles
+                .save_to_file(ColumnProfiles::FILE_PATH)
+                .err()
+                .map(|error| t!("app.job_table.columns.save_error", error = error).to_string());
+        }
+        if let Some(error) = &self.profiles_error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        if self.displayed_jobs_per_page.is_empty() {
+            ui.label(t!("app.job_table.empty"));
+        }
+
         ui.add_space(10.0);
 
         for window in self.details_window.iter_mut() {
@@ -259,11 +454,120 @@ impl JobTable {
     }
 
     /**
-     * Resets the pagination
+     * Returns the rows of the table, the jobs of the collapsed groups being hidden
+     */
+    fn table_lines(&self, groups: &[JobGroup]) -> Vec<TableLine> {
+        if self.group_by.is_none() {
+            return (0..self.displayed_jobs_per_page.len())
+                .map(|index| TableLine::Job(index, index + 1))
+                .collect();
+        }
+        let mut lines = Vec::new();
+        for (group_index, group) in groups.iter().enumerate() {
+            lines.push(TableLine::Group(group_index));
+            if !self.collapsed_groups.contains(&group.name) {
+                for (number, index) in group.jobs.iter().enumerate() {
+                    lines.push(TableLine::Job(*index, number + 1));
+                }
+            }
+        }
+        lines
+    }
+
+    /**
+     * Changes the sorting keys after a click on a column header
+     * A click sorts on the column only, a shift-click adds the column as the next key
+     * Clicking a column already sorted on toggles its direction
+     */
+    fn click_sort_key(&mut self, sort_key: SortKey, shift: bool) {
+        let position = self.sort_keys.iter().position(|(key, _)| *key == sort_key);
+        match (position, shift) {
+            (Some(index), true) => self.sort_keys[index].1 = !self.sort_keys[index].1,
+            (Some(0), false) if self.sort_keys.len() == 1 => {
+                self.sort_keys[0].1 = !self.sort_keys[0].1
+            }
+            (None, true) => self.sort_keys.push((sort_key, true)),
+            (_, false) => self.sort_keys = vec![(sort_key, true)],
+        }
+    }
+
+    /**
+     * Returns true if one of the visible columns of the job contains the searched text
+     */
+    fn matches_search(
+        column_selection: &ColumnSelection,
+        job: &Job,
+        search: &str,
+        now: i64,
+    ) -> bool {
+        column_selection
+            .selected()
+            .any(|value| cell_text(job, value, now).to_lowercase().contains(search))
+    }
+
+    /**
+     * Shows the choice of the column profile and the actions on the profiles
      */
-    pub fn reset_pagination(&mut self) {
-        self.page = 0;
-        self.start_idx = 0;
-        self.end_idx = 0;
+    fn profiles_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(t!("app.job_table.columns.profile"));
+            let selected = self.column_profiles.selected;
+            egui::ComboBox::from_id_salt("job_table_profile")
+                .selected_text(self.column_profiles.current().name.clone())
+                .show_ui(ui, |ui| {
+                    for (index, profile) in self.column_profiles.profiles.iter().enumerate() {
+                        ui.selectable_value(&mut self.column_profiles.selected, index, &profile.name);
+                    }
+                });
+            self.columns_changed |= self.column_profiles.selected != selected;
+        });
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_profile_name)
+                    .hint_text(t!("app.job_table.columns.name_hint"))
+                    .desired_width(120.0),
+            );
+            let name = self.new_profile_name.trim().to_string();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new(t!("app.job_table.columns.save_as")))
+                .clicked()
+            {
+                self.column_profiles.add_copy(name);
+                self.new_profile_name.clear();
+                self.columns_changed = true;
+            }
+            if ui
+                .add_enabled(
+                    self.column_profiles.profiles.len() > 1,
+                    egui::Button::new(t!("app.job_table.columns.delete")),
+                )
+                .clicked()
+            {
+                self.column_profiles.remove_current();
+                self.columns_changed = true;
+            }
+        });
+    }
+}
+
+/**
+ * Returns the text shown in a column of the table for the job, a running job running until `now`
+ */
+pub(crate) fn cell_text(job: &Job, column: &ColumnInfo, now: i64) -> String {
+    match column.sort_key {
+        SortKey::Id => job.id.to_string(),
+        SortKey::Owner => job.owner.to_string(),
+        SortKey::State => job.state.get_label(),
+        SortKey::StartTime => format_timestamp(job.start_time),
+        SortKey::WallTime => column.duration_format.format(job.walltime),
+        SortKey::Runtime => column.duration_format.format(job.get_runtime(now)),
+        SortKey::Queue => job.queue.clone(),
+        SortKey::Command => job.command.clone(),
+        SortKey::Message => job.message.clone().unwrap_or_default(),
+        SortKey::SubmissionTime => format_timestamp(job.submission_time),
+        SortKey::ScheduledStartTime => format_timestamp(job.scheduled_start),
+        SortKey::StopTime => format_timestamp(job.stop_time),
+        SortKey::ExitCode => job.exit_code.map_or("N/A".to_string(), |code| code.to_string()),
+        SortKey::Clusters => job.clusters.join(", "),
     }
 }
@@ -1,21 +1,89 @@
 use super::job_table_sorting::SortKey;
-use egui::Layout;
-use std::collections::BTreeMap;
+use crate::models::utils::date_converter::{format_duration, format_duration_humanised};
+use crate::views::components::named_profiles::{NamedProfile, NamedProfiles};
+use egui::{Id, Layout};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/**
+ * Format of the columns showing a duration
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum DurationFormat {
+    Seconds,
+    #[default]
+    Clock, // HH:MM:SS, prefixed by the number of days
+    Humanised, // The two largest units, e.g. 2d 3h
+}
+
+impl DurationFormat {
+    pub fn get_label(&self) -> String {
+        match self {
+            DurationFormat::Seconds => t!("app.job_table.columns.seconds").to_string(),
+            DurationFormat::Clock => t!("app.job_table.columns.clock").to_string(),
+            DurationFormat::Humanised => t!("app.job_table.columns.humanised").to_string(),
+        }
+    }
+
+    pub fn format(&self, seconds: i64) -> String {
+        match self {
+            DurationFormat::Seconds => seconds.to_string(),
+            DurationFormat::Clock => format_duration(seconds),
+            DurationFormat::Humanised => format_duration_humanised(seconds),
+        }
+    }
+}
 
 /**
  * Struct for the column information
  */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ColumnInfo {
-    pub name: String, // Name of the column
-    pub selected: bool, // True if the column is selected
+    pub name: String,      // Name of the column
+    pub selected: bool,    // True if the column is selected
     pub sort_key: SortKey, // Sort key for the column
+    #[serde(default)]
+    pub width: Option<f32>, // Width chosen by the user, None to share the available width
+    #[serde(default)]
+    pub duration_format: DurationFormat, // Format of the values, for the durations only
+}
+
+impl ColumnInfo {
+    fn new(sort_key: SortKey, selected: bool) -> Self {
+        let name = match sort_key {
+            SortKey::Id => "app.job_table.table.job_id",
+            SortKey::Owner => "app.job_table.table.owner",
+            SortKey::Queue => "app.job_table.table.queue",
+            SortKey::Command => "app.job_table.table.command",
+            SortKey::State => "app.job_table.table.state",
+            SortKey::Message => "app.job_table.table.message",
+            SortKey::SubmissionTime => "app.job_table.table.submission_time",
+            SortKey::ScheduledStartTime => "app.job_table.table.scheduled_start_time",
+            SortKey::StartTime => "app.job_table.table.start_time",
+            SortKey::StopTime => "app.job_table.table.stop_time",
+            SortKey::WallTime => "app.job_table.table.wall_time",
+            SortKey::Runtime => "app.job_table.table.runtime",
+            SortKey::ExitCode => "app.job_table.table.exit_code",
+            SortKey::Clusters => "app.job_table.table.clusters",
+        };
+        ColumnInfo {
+            name: name.to_string(),
+            selected,
+            sort_key,
+            width: None,
+            duration_format: DurationFormat::default(),
+        }
+    }
 }
 
 /**
- * Struct for the column selection
+ * Struct for the column selection, a named profile of the columns in their order
  */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ColumnSelection {
-    pub values: BTreeMap<u8, ColumnInfo>, // Map of column index to column info
+    pub name: String,             // Name of the profile
+    pub columns: Vec<ColumnInfo>, // Columns in the order they are shown
 }
 
 /**
@@ -24,7 +92,8 @@ pub struct ColumnSelection {
 impl Default for ColumnSelection {
     fn default() -> Self {
         let mut instance = Self {
-            values: BTreeMap::new(),
+            name: t!("app.job_table.columns.default_profile").to_string(),
+            columns: Vec::new(),
         };
         instance.select_default();
         instance
@@ -35,157 +104,151 @@ impl Default for ColumnSelection {
  * Implementation of the column selection
  */
 impl ColumnSelection {
-    pub fn ui(&mut self, ui: &mut egui::Ui) {
+    /**
+     * Shows the columns, which can be dragged to reorder them
+     * Returns true if the selection was changed
+     */
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        let mut moved = None;
         ui.vertical(|ui| {
-            egui::Grid::new("")
-                .num_columns(2)
-                .striped(false)
-                .show(ui, |ui| {
-                    ui.with_layout(Layout::top_down(egui::Align::Max), |ui| {
-                        ui.set_max_width(100.0);
-                        ui.label(t!("app.job_table.table.columns"));
-                    });
-                    // Column selection grid
-                    egui::Grid::new("column_selection_grid")
-                        .num_columns(3)
-                        .spacing([20.0, 8.0])
-                        .show(ui, |ui| {
-                            let mut count = 0;
-                            for (_column, value) in self.values.iter_mut() {
-                                ui.checkbox(&mut value.selected, t!(value.name.to_string()));
-                                count += 1;
-                                if count % 3 == 0 {
-                                    ui.end_row();
-                                }
-                            }
-                            if count % 3 != 0 {
-                                ui.end_row();
-                            }
+            ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
+                ui.label(t!("app.job_table.table.columns"));
+                ui.label(
+                    egui::RichText::new(t!("app.job_table.columns.drag_hint"))
+                        .small()
+                        .weak(),
+                );
+            });
+
+            // One row per column, dropping a column on another one moves it there
+            for (index, value) in self.columns.iter_mut().enumerate() {
+                let response = ui
+                    .horizontal(|ui| {
+                        ui.dnd_drag_source(Id::new(("job_table_column", index)), index, |ui| {
+                            ui.label("☰");
                         });
-                    ui.end_row();
-                    ui.label("");
-                    ui.horizontal(|ui| {
-                        if ui.button(t!("app.job_table.select_all")).clicked() {
-                            self.select_all();
+                        changed |= ui
+                            .checkbox(&mut value.selected, t!(value.name.to_string()))
+                            .changed();
+                        if value.sort_key.is_duration() {
+                            egui::ComboBox::from_id_salt(("job_table_duration", index))
+                                .selected_text(value.duration_format.get_label())
+                                .show_ui(ui, |ui| {
+                                    for format in DurationFormat::iter() {
+                                        changed |= ui
+                                            .selectable_value(
+                                                &mut value.duration_format,
+                                                format,
+                                                format.get_label(),
+                                            )
+                                            .changed();
+                                    }
+                                });
                         }
-                        if ui.button(t!("app.job_table.select_default")).clicked() {
-                            self.select_default();
+                        if value.width.is_some()
+                            && ui
+                                .small_button("↔")
+                                .on_hover_text(t!("app.job_table.columns.reset_width"))
+                                .clicked()
+                        {
+                            value.width = None;
+                            changed = true;
                         }
-                    });
-                });
+                    })
+                    .response;
+                if let Some(from) = response.dnd_release_payload::<usize>() {
+                    moved = Some((*from, index));
+                }
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button(t!("app.job_table.select_all")).clicked() {
+                    self.select_all();
+                    changed = true;
+                }
+                if ui.button(t!("app.job_table.select_default")).clicked() {
+                    self.select_default();
+                    changed = true;
+                }
+            });
         });
+
+        if let Some((from, to)) = moved {
+            if from != to && from < self.columns.len() {
+                let column = self.columns.remove(from);
+                self.columns.insert(to.min(self.columns.len()), column);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /**
+     * Returns the selected columns, in the order they are shown
+     */
+    pub fn selected(&self) -> impl Iterator<Item = &ColumnInfo> {
+        self.columns.iter().filter(|value| value.selected)
     }
 
     fn select_default(&mut self) {
-        self.values.insert(
-            0,
-            ColumnInfo {
-                name: "app.job_table.table.job_id".to_string(),
-                selected: true,
-                sort_key: SortKey::Id,
-            },
-        );
-        self.values.insert(
-            1,
-            ColumnInfo {
-                name: "app.job_table.table.owner".to_string(),
-                selected: true,
-                sort_key: SortKey::Owner,
-            },
-        );
-        self.values.insert(
-            2,
-            ColumnInfo {
-                name: "app.job_table.table.queue".to_string(),
-                selected: false,
-                sort_key: SortKey::Queue,
-            },
-        );
-        self.values.insert(
-            3,
-            ColumnInfo {
-                name: "app.job_table.table.command".to_string(),
-                selected: false,
-                sort_key: SortKey::Command,
-            },
-        );
-        self.values.insert(
-            4,
-            ColumnInfo {
-                name: "app.job_table.table.state".to_string(),
-                selected: true,
-                sort_key: SortKey::State,
-            },
-        );
-        self.values.insert(
-            5,
-            ColumnInfo {
-                name: "app.job_table.table.message".to_string(),
-                selected: false,
-                sort_key: SortKey::Message,
-            },
-        );
-        self.values.insert(
-            6,
-            ColumnInfo {
-                name: "app.job_table.table.submission_time".to_string(),
-                selected: false,
-                sort_key: SortKey::SubmissionTime,
-            },
-        );
-        self.values.insert(
-            7,
-            ColumnInfo {
-                name: "app.job_table.table.scheduled_start_time".to_string(),
-                selected: true,
-                sort_key: SortKey::ScheduledStartTime,
-            },
-        );
-        self.values.insert(
-            8,
-            ColumnInfo {
-                name: "app.job_table.table.start_time".to_string(),
-                selected: false,
-                sort_key: SortKey::StartTime,
-            },
-        );
-        self.values.insert(
-            9,
-            ColumnInfo {
-                name: "app.job_table.table.stop_time".to_string(),
-                selected: false,
-                sort_key: SortKey::StopTime,
-            },
-        );
-        self.values.insert(
-            10,
-            ColumnInfo {
-                name: "app.job_table.table.wall_time".to_string(),
-                selected: true,
-                sort_key: SortKey::WallTime,
-            },
-        );
-        self.values.insert(
-            11,
-            ColumnInfo {
-                name: "app.job_table.table.exit_code".to_string(),
-                selected: false,
-                sort_key: SortKey::ExitCode,
-            },
-        );
-        self.values.insert(
-            12,
-            ColumnInfo {
-                name: "app.job_table.table.clusters".to_string(),
-                selected: false,
-                sort_key: SortKey::Clusters,
-            },
-        );
+        self.columns = vec![
+            ColumnInfo::new(SortKey::Id, true),
+            ColumnInfo::new(SortKey::Owner, true),
+            ColumnInfo::new(SortKey::Queue, false),
+            ColumnInfo::new(SortKey::Command, false),
+            ColumnInfo::new(SortKey::State, true),
+            ColumnInfo::new(SortKey::Message, false),
+            ColumnInfo::new(SortKey::SubmissionTime, false),
+            ColumnInfo::new(SortKey::ScheduledStartTime, true),
+            ColumnInfo::new(SortKey::StartTime, false),
+            ColumnInfo::new(SortKey::StopTime, false),
+            ColumnInfo::new(SortKey::WallTime, true),
+            ColumnInfo::new(SortKey::Runtime, false),
+            ColumnInfo::new(SortKey::ExitCode, false),
+            ColumnInfo::new(SortKey::Clusters, false),
+        ];
     }
 
     fn select_all(&mut self) {
-        for value in self.values.values_mut() {
+        for value in self.columns.iter_mut() {
             value.selected = true;
         }
     }
 }
+
+impl NamedProfile for ColumnSelection {
+    fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    /**
+     * Adds the columns missing from a profile saved by a previous version, hidden
+     */
+    fn complete(&mut self) {
+        for sort_key in SortKey::iter() {
+            if !self.columns.iter().any(|value| value.sort_key == sort_key) {
+                self.columns.push(ColumnInfo::new(sort_key, false));
+            }
+        }
+    }
+}
+
+/**
+ * The column profiles of the job table and the one used, saved so that the order,
+ * the widths and the formats of the columns are kept between sessions
+ */
+pub type ColumnProfiles = NamedProfiles<ColumnSelection>;
+
+impl Default for ColumnProfiles {
+    fn default() -> Self {
+        ColumnProfiles {
+            selected: 0,
+            profiles: vec![ColumnSelection::default()],
+        }
+    }
+}
+
+impl ColumnProfiles {
+    pub const FILE_PATH: &'static str = "job_table_columns.json";
+}
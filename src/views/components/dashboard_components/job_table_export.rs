@@ -0,0 +1,301 @@
+use super::job_table_col_selection::ColumnSelection;
+use super::job_table_sorting::SortKey;
+use crate::models::data_structure::job::Job;
+use crate::models::utils::date_converter::format_timestamp_rfc3339;
+use crate::views::components::gantt_export::GanttExport;
+use crate::views::components::gantt_selection::csv_field;
+use chrono::Local;
+use eframe::egui;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/**
+ * Format of the file the jobs are exported to
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    JsonLines, // One JSON object per line
+}
+
+impl ExportFormat {
+    pub fn get_label(&self) -> String {
+        match self {
+            ExportFormat::Csv => "CSV".to_string(),
+            ExportFormat::Json => "JSON".to_string(),
+            ExportFormat::JsonLines => "JSON Lines".to_string(),
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::JsonLines => "jsonl",
+        }
+    }
+}
+
+/**
+ * Format of the dates of the exported jobs
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+pub enum TimestampFormat {
+    Iso8601,
+    Epoch, // Seconds since 1970-01-01 UTC
+}
+
+impl TimestampFormat {
+    pub fn get_label(&self) -> String {
+        match self {
+            TimestampFormat::Iso8601 => t!("app.job_table.export.iso8601").to_string(),
+            TimestampFormat::Epoch => t!("app.job_table.export.epoch").to_string(),
+        }
+    }
+
+    /**
+     * Returns the value of a date, null when the date is not set
+     * The ISO-8601 dates are in the display timezone, like the dates of the table
+     */
+    fn value(&self, timestamp: i64) -> Value {
+        if timestamp <= 0 {
+            return Value::Null;
+        }
+        match self {
+            TimestampFormat::Iso8601 => {
+                format_timestamp_rfc3339(timestamp).map_or(Value::Null, Value::from)
+            }
+            TimestampFormat::Epoch => Value::from(timestamp),
+        }
+    }
+}
+
+/**
+ * Returns the name of the field of a column in the exported files
+ */
+fn field_name(key: SortKey) -> &'static str {
+    match key {
+        SortKey::Id => "id",
+        SortKey::Owner => "owner",
+        SortKey::Queue => "queue",
+        SortKey::Command => "command",
+        SortKey::State => "state",
+        SortKey::Message => "message",
+        SortKey::SubmissionTime => "submission_time",
+        SortKey::ScheduledStartTime => "scheduled_start",
+        SortKey::StartTime => "start_time",
+        SortKey::StopTime => "stop_time",
+        SortKey::ExitCode => "exit_code",
+        SortKey::Clusters => "clusters",
+        SortKey::WallTime => "walltime",
+        SortKey::Runtime => "runtime",
+    }
+}
+
+/*
+ * `JobTableExport` writes the jobs of the table to a file of the export folder.
+ * The visible columns are written in their order, followed by the fields derived
+ * from the jobs: the wait time, the runtime, the hosts and the clusters.
+ */
+pub struct JobTableExport {
+    format: ExportFormat,
+    timestamps: TimestampFormat,
+    status: Option<(String, f64)>, // Status message of the last export and the time it was set
+}
+
+impl Default for JobTableExport {
+    fn default() -> Self {
+        JobTableExport {
+            format: ExportFormat::Csv,
+            timestamps: TimestampFormat::Iso8601,
+            status: None,
+        }
+    }
+}
+
+impl JobTableExport {
+    const STATUS_DURATION_S: f64 = 5.0;
+
+    /**
+     * Shows the export menu and the status of the last export
+     */
+    pub fn ui(&mut self, ui: &mut egui::Ui, jobs: &[Job], column_selection: &ColumnSelection) {
+        ui.menu_button(t!("app.job_table.export.button"), |ui| {
+            ui.label(t!("app.job_table.export.format"));
+            for format in ExportFormat::iter() {
+                ui.radio_value(&mut self.format, format, format.get_label());
+            }
+            ui.separator();
+            ui.label(t!("app.job_table.export.timestamps"));
+            for timestamps in TimestampFormat::iter() {
+                ui.radio_value(&mut self.timestamps, timestamps, timestamps.get_label());
+            }
+            ui.separator();
+
+            if ui
+                .add_enabled(
+                    !jobs.is_empty(),
+                    egui::Button::new(t!("app.job_table.export.save", count = jobs.len())),
+                )
+                .clicked()
+            {
+                let message = match self.write(jobs, column_selection) {
+                    Ok(path) => {
+                        t!("app.job_table.export.success", path = path.display()).to_string()
+                    }
+                    Err(error) => t!("app.job_table.export.error", error = error).to_string(),
+                };
+                self.status = Some((message, ui.input(|i| i.time)));
+                ui.close_menu();
+            }
+        });
+
+        if let Some((message, timestamp)) = &self.status {
+            if ui.input(|i| i.time) - timestamp < Self::STATUS_DURATION_S {
+                ui.label(message);
+            } else {
+                self.status = None; // Clear the message after timeout
+            }
+        }
+    }
+
+    /**
+     * Returns the exported fields of a job, in the order of the columns
+     */
+    fn fields(
+        &self,
+        job: &Job,
+        column_selection: &ColumnSelection,
+        now: i64,
+    ) -> Vec<(&'static str, Value)> {
+        let mut fields: Vec<(&'static str, Value)> = column_selection
+            .selected()
+            .map(|value| {
+                let field = match value.sort_key {
+                    SortKey::Id => Value::from(job.id),
+                    SortKey::Owner => Value::from(job.owner.clone()),
+                    SortKey::Queue => Value::from(job.queue.clone()),
+                    SortKey::Command => Value::from(job.command.clone()),
+                    SortKey::State => Value::from(job.state.to_string()),
+                    SortKey::Message => job.message.clone().map_or(Value::Null, Value::from),
+                    SortKey::SubmissionTime => self.timestamps.value(job.submission_time),
+                    SortKey::ScheduledStartTime => self.timestamps.value(job.scheduled_start),
+                    SortKey::StartTime => self.timestamps.value(job.start_time),
+                    SortKey::StopTime => self.timestamps.value(job.stop_time),
+                    SortKey::ExitCode => job.exit_code.map_or(Value::Null, Value::from),
+                    SortKey::Clusters => Value::from(job.clusters.clone()),
+                    SortKey::WallTime => Value::from(job.walltime),
+                    SortKey::Runtime => Value::from(job.get_runtime(now)),
+                };
+                (field_name(value.sort_key), field)
+            })
+            .collect();
+
+        // Derived fields, the waiting jobs wait until now
+        let wait_time = if job.submission_time <= 0 {
+            Value::Null
+        } else if job.start_time > 0 {
+            Value::from((job.start_time - job.submission_time).max(0))
+        } else {
+            Value::from((now - job.submission_time).max(0))
+        };
+        let derived = [
+            ("wait_time", wait_time),
+            ("runtime", Value::from(job.get_runtime(now))),
+            ("hosts", Value::from(job.hosts.clone())),
+            ("clusters", Value::from(job.clusters.clone())),
+        ];
+        for (name, value) in derived {
+            // The columns already exported are not repeated
+            if !fields.iter().any(|(field, _)| *field == name) {
+                fields.push((name, value));
+            }
+        }
+        fields
+    }
+
+    fn write(&self, jobs: &[Job], column_selection: &ColumnSelection) -> Result<PathBuf, String> {
+        let folder = Path::new(GanttExport::EXPORT_FOLDER);
+        if !folder.exists() {
+            std::fs::create_dir_all(folder).map_err(|e| e.to_string())?;
+        }
+        let path = folder.join(format!(
+            "jobs_{}.{}",
+            Local::now().format("%Y%m%d_%H%M%S"),
+            self.format.extension()
+        ));
+
+        let now = chrono::Utc::now().timestamp();
+        let rows: Vec<Vec<(&str, Value)>> = jobs
+            .iter()
+            .filter(|job| job.id != 0)
+            .map(|job| self.fields(job, column_selection, now))
+            .collect();
+        let content = match self.format {
+            ExportFormat::Csv => to_csv(&rows),
+            ExportFormat::Json => format!(
+                "[\n{}\n]\n",
+                rows.iter()
+                    .map(|row| format!("  {}", to_json_object(row)))
+                    .collect::<Vec<_>>()
+                    .join(",\n")
+            ),
+            ExportFormat::JsonLines => rows
+                .iter()
+                .map(|row| format!("{}\n", to_json_object(row)))
+                .collect(),
+        };
+        std::fs::write(&path, content).map_err(|e| e.to_string())?;
+
+        Ok(path)
+    }
+}
+
+/**
+ * Writes a JSON object keeping the order of the fields
+ */
+fn to_json_object(fields: &[(&str, Value)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{}:{}", Value::from(*name), value))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/**
+ * Writes the rows as CSV, the lists being joined with spaces
+ */
+fn to_csv(rows: &[Vec<(&str, Value)>]) -> String {
+    let Some(first) = rows.first() else {
+        return String::new();
+    };
+    let mut csv = first
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(",");
+    csv.push('\n');
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|(_, value)| match value {
+                Value::Null => String::new(),
+                Value::String(text) => csv_field(text),
+                Value::Array(values) => csv_field(
+                    &values
+                        .iter()
+                        .map(|value| value.as_str().map_or(value.to_string(), str::to_string))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                value => value.to_string(),
+            })
+            .collect();
+        csv.push_str(&cells.join(","));
+        csv.push('\n');
+    }
+    csv
+}
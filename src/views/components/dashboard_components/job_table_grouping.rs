@@ -0,0 +1,97 @@
+use crate::models::data_structure::job::{Job, JobState};
+use std::collections::BTreeMap;
+use strum_macros::EnumIter;
+
+/**
+ * A key used to group the rows of the job table
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+pub enum GroupKey {
+    Owner,
+    State,
+    Queue,
+    Cluster,
+    Host,
+}
+
+impl GroupKey {
+    pub fn get_label(&self) -> String {
+        match self {
+            GroupKey::Owner => t!("app.job_table.group.owner").to_string(),
+            GroupKey::State => t!("app.job_table.group.state").to_string(),
+            GroupKey::Queue => t!("app.job_table.group.queue").to_string(),
+            GroupKey::Cluster => t!("app.job_table.group.cluster").to_string(),
+            GroupKey::Host => t!("app.job_table.group.host").to_string(),
+        }
+    }
+
+    /**
+     * Returns the groups of a job
+     * A job using several clusters or hosts appears in the group of each of them
+     */
+    fn values(&self, job: &Job) -> Vec<String> {
+        match self {
+            GroupKey::Owner => vec![job.owner.clone()],
+            GroupKey::State => vec![job.state.get_label()],
+            GroupKey::Queue => vec![job.queue.clone()],
+            GroupKey::Cluster => job.clusters.clone(),
+            GroupKey::Host => job.hosts.clone(),
+        }
+    }
+}
+
+/**
+ * Jobs of a group of the table, with their subtotals
+ */
+pub struct JobGroup {
+    pub name: String,                  // Value of the key shared by the jobs
+    pub jobs: Vec<usize>,              // Indexes of the jobs, in the order they are sorted
+    pub core_hours: f64,               // Core-hours used by the jobs until now
+    states: BTreeMap<JobState, usize>, // Number of jobs of each state
+}
+
+impl JobGroup {
+    /**
+     * Returns the number of jobs of each state, the most frequent state first
+     */
+    pub fn state_breakdown(&self) -> String {
+        let mut states: Vec<(&JobState, &usize)> = self.states.iter().collect();
+        states.sort_by(|a, b| b.1.cmp(a.1));
+        states
+            .iter()
+            .map(|(state, count)| format!("{} {}", state.get_label(), count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/**
+ * Groups the sorted jobs by a key, the groups being sorted by name
+ * The jobs keep their order inside each group, so the sorting applies within the groups
+ * The jobs without a value for the key (e.g. waiting jobs without hosts) are grouped together
+ */
+pub fn group_jobs(jobs: &[Job], key: GroupKey, now: i64) -> Vec<JobGroup> {
+    let mut groups: BTreeMap<String, JobGroup> = BTreeMap::new();
+    for (index, job) in jobs.iter().enumerate() {
+        let mut values = key.values(job);
+        values.retain(|value| !value.is_empty());
+        if values.is_empty() {
+            values.push(t!("app.job_table.group.none").to_string());
+        }
+        values.sort();
+        values.dedup();
+
+        for value in values {
+            let group = groups.entry(value.clone()).or_insert_with(|| JobGroup {
+                name: value,
+                jobs: Vec::new(),
+                core_hours: 0.0,
+                states: BTreeMap::new(),
+            });
+            group.jobs.push(index);
+            group.core_hours += job.get_core_hours(now);
+            *group.states.entry(job.state.clone()).or_default() += 1;
+        }
+    }
+    groups.into_values().collect()
+}
@@ -1,9 +1,12 @@
 use crate::models::data_structure::job::JobState;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use strum_macros::EnumIter;
 
 /**
  * Enum for sorting keys
  */
-#[derive(PartialEq, Copy, Clone)]
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Serialize, Deserialize, EnumIter)]
 pub(crate) enum SortKey {
     Id,
     Owner,
@@ -18,6 +21,7 @@ pub(crate) enum SortKey {
     ExitCode,
     Clusters,
     WallTime,
+    Runtime,
 }
 
 /**
@@ -25,44 +29,67 @@ pub(crate) enum SortKey {
  */
 impl SortKey {
     /**
-     * Sorts the jobs based on the selected key
+     * Compares two jobs on the key, the running jobs running until `now`
      */
-    pub fn sort_jobs<T>(self, jobs: &mut Vec<T>, ascending: bool)
+    pub fn compare<T>(self, a: &T, b: &T, now: i64) -> Ordering
     where
         T: JobSortable,
     {
-        let comparator = |a: &T, b: &T| {
-            let cmp = match self {
-                SortKey::Id => a.get_id().cmp(&b.get_id()), // Compare the job id
-                SortKey::Owner => a.get_owner().cmp(&b.get_owner()), // Compare the job owner
-                SortKey::State => a.get_state().cmp(&b.get_state()), // Compare the job state
-                SortKey::StartTime => a.get_start_time().cmp(&b.get_start_time()), // Compare the job start time
-                SortKey::WallTime => a.get_walltime().cmp(&b.get_walltime()), // Compare the job walltime
-                SortKey::Queue => a.get_queue().cmp(&b.get_queue()), // Compare the job queue
-                SortKey::Command => a.get_command().cmp(&b.get_command()), // Compare the job command
-                SortKey::Message => a.get_message().cmp(&b.get_message()), // Compare the job message
-                SortKey::SubmissionTime => a.get_submission_time().cmp(&b.get_submission_time()), // Compare the job submission time
-                SortKey::ScheduledStartTime => { // Compare the job scheduled start time
-                    a.get_scheduled_start().cmp(&b.get_scheduled_start())
-                }
-                SortKey::StopTime => a.get_stop_time().cmp(&b.get_stop_time()), // Compare the job stop time
-                SortKey::ExitCode => a.get_exit_code().cmp(&b.get_exit_code()), // Compare the job exit code
-                SortKey::Clusters => a.get_clusters().cmp(&b.get_clusters()), // Compare the job clusters
-            };
-
-            // If the sorting is ascending, return the comparison, otherwise return the reverse comparison
-            if ascending {
-                cmp
-            } else {
-                cmp.reverse()
+        match self {
+            SortKey::Id => a.get_id().cmp(b.get_id()), // Compare the job id
+            SortKey::Owner => a.get_owner().cmp(b.get_owner()), // Compare the job owner
+            SortKey::State => a.get_state().cmp(b.get_state()), // Compare the job state
+            SortKey::StartTime => a.get_start_time().cmp(&b.get_start_time()), // Compare the job start time
+            SortKey::WallTime => a.get_walltime().cmp(&b.get_walltime()), // Compare the job walltime
+            SortKey::Queue => a.get_queue().cmp(b.get_queue()),           // Compare the job queue
+            SortKey::Command => a.get_command().cmp(b.get_command()),     // Compare the job command
+            SortKey::Message => a.get_message().cmp(&b.get_message()),    // Compare the job message
+            SortKey::SubmissionTime => a.get_submission_time().cmp(&b.get_submission_time()), // Compare the job submission time
+            SortKey::ScheduledStartTime => {
+                // Compare the job scheduled start time
+                a.get_scheduled_start().cmp(&b.get_scheduled_start())
             }
-        };
-        
-        // Sort the jobs based on the comparator
-        jobs.sort_by(comparator);
+            SortKey::StopTime => a.get_stop_time().cmp(&b.get_stop_time()), // Compare the job stop time
+            SortKey::ExitCode => a.get_exit_code().cmp(b.get_exit_code()), // Compare the job exit code
+            SortKey::Clusters => a.get_clusters().cmp(b.get_clusters()), // Compare the job clusters
+            SortKey::Runtime => a.get_runtime(now).cmp(&b.get_runtime(now)), // Compare the time the jobs ran
+        }
+    }
+
+    /**
+     * Returns true if the values of the column are durations
+     */
+    pub fn is_duration(&self) -> bool {
+        matches!(self, SortKey::WallTime | SortKey::Runtime)
     }
 }
 
+/**
+ * Sorts the jobs on several keys, each key with its direction
+ * The jobs equal on a key are sorted on the next one
+ * The running jobs run until `now`, the same time for the whole sort
+ * so that they always compare the same way
+ */
+pub fn sort_jobs_by_keys<T>(jobs: &mut [T], keys: &[(SortKey, bool)], now: i64)
+where
+    T: JobSortable,
+{
+    jobs.sort_by(|a, b| {
+        keys.iter()
+            .map(|(key, ascending)| {
+                let cmp = key.compare(a, b, now);
+                // If the sorting is ascending, return the comparison, otherwise return the reverse comparison
+                if *ascending {
+                    cmp
+                } else {
+                    cmp.reverse()
+                }
+            })
+            .find(|cmp| cmp.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
 /**
  * Trait for sortable jobs
  */
@@ -81,4 +108,5 @@ pub trait JobSortable {
     fn get_exit_code(&self) -> &Option<i32>;
     fn get_clusters(&self) -> &Vec<String>;
     fn get_end_date(&self) -> i64;
+    fn get_runtime(&self, now: i64) -> i64;
 }
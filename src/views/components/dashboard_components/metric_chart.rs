@@ -1,12 +1,61 @@
-use egui::{Response, RichText, Vec2, Widget};
-use egui_plot::AxisHints;
-use egui_plot::{Bar, BarChart, Plot};
-use std::collections::HashMap;
+use egui::{Color32, Response, RichText, Stroke, Vec2, Widget};
+use egui_plot::{
+    AxisHints, Bar, BarChart, Legend, Line, MarkerShape, Plot, PlotPoint, Points, Polygon,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::f64::consts::TAU;
 
-use crate::models::data_structure::job::Job;
+use crate::models::data_structure::job::{Job, JobState};
+use crate::models::utils::date_converter::{format_duration, format_timestamp_as};
+
+/**
+ * A named series of points of a line, stacked area or scatter chart
+ */
+pub struct Series {
+    pub name: String,
+    pub color: Color32,
+    pub points: Vec<[f64; 2]>,
+}
+
+/**
+ * A slice of a pie chart
+ */
+pub struct Slice {
+    pub name: String,
+    pub value: f64,
+    pub color: Color32,
+}
 
 pub enum ChartType {
     Bar(BarChart),
+    Line(Vec<Series>),
+    StackedArea(Vec<Series>), // Series sharing the same x values, stacked in order
+    Pie { slices: Vec<Slice>, donut: bool },
+    Histogram(BarChart),
+    Scatter(Vec<Series>),
+}
+
+/**
+ * How the values of an axis are written
+ */
+#[derive(Clone, Copy)]
+pub enum AxisFormat {
+    Number,
+    Time,     // Timestamps in seconds
+    Duration, // Durations in seconds
+}
+
+impl AxisFormat {
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            AxisFormat::Number if value.fract() == 0.0 => format!("{:.0}", value),
+            AxisFormat::Number => format!("{:.1}", value),
+            AxisFormat::Time => {
+                format_timestamp_as(value as i64, "%m-%d %H:%M").unwrap_or_default()
+            }
+            AxisFormat::Duration => format_duration(value as i64),
+        }
+    }
 }
 
 pub struct MetricChart {
@@ -14,11 +63,15 @@ pub struct MetricChart {
     pub chart: ChartType,
     pub color: egui::Color32,
     pub labels: Vec<String>,
+    pub x_axis: (String, AxisFormat), // Name and format of the x axis
+    pub y_axis: (String, AxisFormat), // Name and format of the y axis
+    pub interactive: bool,            // Drag to pan and Ctrl+scroll to zoom, double-click resets
 }
 
 impl MetricChart {
     const MIN_WIDTH: f32 = 200.0;
     const MIN_HEIGHT: f32 = 150.0;
+    const DONUT_HOLE: f64 = 0.5; // Inner radius of a donut, the outer radius being 1
 
     pub fn new(title: &str, chart: ChartType, labels: Vec<String>) -> Self {
         MetricChart {
@@ -26,9 +79,27 @@ impl MetricChart {
             chart,
             color: egui::Color32::from_rgb(128, 128, 128),
             labels,
+            x_axis: (String::new(), AxisFormat::Number),
+            y_axis: (String::new(), AxisFormat::Number),
+            interactive: false,
         }
     }
 
+    pub fn x_axis(mut self, name: &str, format: AxisFormat) -> Self {
+        self.x_axis = (name.to_string(), format);
+        self
+    }
+
+    pub fn y_axis(mut self, name: &str, format: AxisFormat) -> Self {
+        self.y_axis = (name.to_string(), format);
+        self
+    }
+
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
     pub fn ui_with_chart(self, ui: &mut egui::Ui, size: Vec2) -> Response {
         egui::Frame::none()
             .fill(if ui.ctx().style().visuals.dark_mode {
@@ -51,26 +122,105 @@ impl MetricChart {
                     ui.add_space(size.y * 0.15);
 
                     let labels = &self.labels;
-                    Plot::new("metric_chart")
-                        .view_aspect(2.0)
-                        .allow_drag(false)
-                        .allow_zoom(false)
-                        .allow_scroll(false)
-                        .show_x(false)
-                        .show_y(false)
-                        .custom_x_axes(vec![AxisHints::new_x().formatter(move |mark, _range| {
-                            let index = mark.value.round() as usize;
-                            if index < labels.len() {
-                                labels[index].to_string()
-                            } else {
-                                // Fallback for out-of-range values
-                                format!("{:.1}", mark.value)
+                    let (x_name, x_format) = self.x_axis.clone();
+                    let (y_name, y_format) = self.y_axis.clone();
+                    let plot = Plot::new("metric_chart")
+                        .height(size.y * 0.7)
+                        .allow_drag(self.interactive)
+                        .allow_zoom(self.interactive)
+                        .allow_boxed_zoom(self.interactive)
+                        .allow_double_click_reset(self.interactive)
+                        .allow_scroll(false);
+                    let plot =
+                        match &self.chart {
+                            ChartType::Bar(_) => {
+                                plot.show_x(false).show_y(false).custom_x_axes(vec![
+                                    AxisHints::new_x().formatter(move |mark, _range| {
+                                        let index = mark.value.round() as usize;
+                                        if index < labels.len() {
+                                            labels[index].to_string()
+                                        } else {
+                                            // Fallback for out-of-range values
+                                            format!("{:.1}", mark.value)
+                                        }
+                                    }),
+                                ])
+                            }
+                            // The pie is drawn in a unit circle, without axes
+                            ChartType::Pie { .. } => plot
+                                .data_aspect(1.0)
+                                .show_axes(false)
+                                .show_grid(false)
+                                .show_x(false)
+                                .show_y(false)
+                                .legend(Legend::default()),
+                            _ => plot
+                                .legend(Legend::default())
+                                .x_axis_label(x_name.clone())
+                                .y_axis_label(y_name.clone())
+                                .x_axis_formatter(move |mark, _range| x_format.format(mark.value))
+                                .y_axis_formatter(move |mark, _range| y_format.format(mark.value))
+                                .label_formatter(move |name, point| {
+                                    let readout = format!(
+                                        "{}: {}\n{}: {}",
+                                        x_name,
+                                        x_format.format(point.x),
+                                        y_name,
+                                        y_format.format(point.y)
+                                    );
+                                    if name.is_empty() {
+                                        readout
+                                    } else {
+                                        format!("{}\n{}", name, readout)
+                                    }
+                                }),
+                        };
+
+                    let response = plot.show(ui, |plot_ui| match self.chart {
+                        ChartType::Bar(bar_chart) | ChartType::Histogram(bar_chart) => {
+                            plot_ui.bar_chart(bar_chart);
+                            None
+                        }
+                        ChartType::Line(series) => {
+                            for series in series {
+                                plot_ui.line(
+                                    Line::new(series.points)
+                                        .name(series.name)
+                                        .color(series.color),
+                                );
+                            }
+                            None
+                        }
+                        ChartType::StackedArea(series) => {
+                            paint_stacked_area(plot_ui, series);
+                            None
+                        }
+                        ChartType::Pie { slices, donut } => {
+                            let hole = if donut { Self::DONUT_HOLE } else { 0.0 };
+                            paint_pie(plot_ui, &slices, hole);
+                            plot_ui
+                                .pointer_coordinate()
+                                .and_then(|point| describe_slice(&slices, hole, point))
+                        }
+                        ChartType::Scatter(series) => {
+                            for series in series {
+                                plot_ui.points(
+                                    Points::new(series.points)
+                                        .name(series.name)
+                                        .color(series.color)
+                                        .shape(MarkerShape::Circle)
+                                        .radius(2.5),
+                                );
                             }
-                        })])
-                        .show(ui, |plot_ui| match self.chart {
-                            ChartType::Bar(bar_chart) => plot_ui.bar_chart(bar_chart),
-                        })
-                        .response // Return the response from the Plot widget
+                            None
+                        }
+                    });
+
+                    // Hover readout of the pie slices
+                    match response.inner {
+                        Some(text) => response.response.on_hover_text(text),
+                        None => response.response,
+                    }
                 });
             })
             .response
@@ -83,6 +233,100 @@ impl Widget for MetricChart {
     }
 }
 
+/**
+ * Paints the series on top of each other, the area between two series is filled with
+ * convex quads as the plot can only fill convex polygons
+ */
+fn paint_stacked_area(plot_ui: &mut egui_plot::PlotUi, series: Vec<Series>) {
+    let mut lower: Vec<[f64; 2]> = Vec::new();
+    for series in series {
+        let upper: Vec<[f64; 2]> = series
+            .points
+            .iter()
+            .enumerate()
+            .map(|(index, [x, y])| [*x, y + lower.get(index).map_or(0.0, |point| point[1])])
+            .collect();
+        for index in 1..upper.len() {
+            let low = |i: usize| lower.get(i).map_or([upper[i][0], 0.0], |point| *point);
+            plot_ui.polygon(
+                Polygon::new(vec![
+                    low(index - 1),
+                    upper[index - 1],
+                    upper[index],
+                    low(index),
+                ])
+                .name(&series.name)
+                .fill_color(series.color.gamma_multiply(0.6))
+                .stroke(Stroke::NONE),
+            );
+        }
+        plot_ui.line(
+            Line::new(upper.clone())
+                .name(&series.name)
+                .color(series.color),
+        );
+        lower = upper;
+    }
+}
+
+/**
+ * Paints the slices in a unit circle, each slice is split in convex quads
+ */
+fn paint_pie(plot_ui: &mut egui_plot::PlotUi, slices: &[Slice], hole: f64) {
+    let total: f64 = slices.iter().map(|slice| slice.value).sum();
+    if total <= 0.0 {
+        return;
+    }
+    let point = |radius: f64, angle: f64| [radius * angle.cos(), radius * angle.sin()];
+
+    let mut start = TAU / 4.0; // Slices start at the top and go clockwise
+    for slice in slices {
+        let sweep = slice.value / total * TAU;
+        let steps = ((sweep / TAU * 64.0).ceil() as usize).max(1);
+        for step in 0..steps {
+            let a = start - sweep * step as f64 / steps as f64;
+            let b = start - sweep * (step + 1) as f64 / steps as f64;
+            plot_ui.polygon(
+                Polygon::new(vec![
+                    point(hole, a),
+                    point(1.0, a),
+                    point(1.0, b),
+                    point(hole, b),
+                ])
+                .name(&slice.name)
+                .fill_color(slice.color)
+                .stroke(Stroke::NONE),
+            );
+        }
+        start -= sweep;
+    }
+}
+
+/**
+ * Returns the name, value and percentage of the slice under a point of the plot
+ */
+fn describe_slice(slices: &[Slice], hole: f64, point: PlotPoint) -> Option<String> {
+    let radius = point.x.hypot(point.y);
+    let total: f64 = slices.iter().map(|slice| slice.value).sum();
+    if radius < hole || radius > 1.0 || total <= 0.0 {
+        return None;
+    }
+    // Clockwise angle from the top, as the slices are painted
+    let angle = (TAU / 4.0 - point.y.atan2(point.x)).rem_euclid(TAU);
+    let mut end = 0.0;
+    slices.iter().find_map(|slice| {
+        end += slice.value / total * TAU;
+        (angle < end).then(|| {
+            format!(
+                "{}: {} ({:.1} %)",
+                slice.name,
+                AxisFormat::Number.format(slice.value),
+                slice.value / total * 100.0
+            )
+        })
+    })
+}
+
 pub fn create_jobstate_chart(jobs: Vec<Job>) -> MetricChart {
     let mut job_states = HashMap::new();
     for job in jobs {
@@ -113,3 +357,211 @@ pub fn create_jobstate_chart(jobs: Vec<Job>) -> MetricChart {
     let plot = BarChart::new(bars).name("Job States");
     MetricChart::new("Job State", ChartType::Bar(plot), labels)
 }
+
+/**
+ * Donut of the number of jobs of each state
+ */
+pub fn create_jobstate_donut_chart(jobs: &[Job]) -> MetricChart {
+    let mut counts: BTreeMap<JobState, usize> = BTreeMap::new();
+    for job in jobs.iter().filter(|job| job.id != 0) {
+        *counts.entry(job.state.clone()).or_default() += 1;
+    }
+    let slices = counts
+        .into_iter()
+        .map(|(state, count)| Slice {
+            name: state.get_label(),
+            value: count as f64,
+            color: state.get_color().1,
+        })
+        .collect();
+
+    MetricChart::new(
+        &t!("app.dashboard.widgets.state_donut"),
+        ChartType::Pie {
+            slices,
+            donut: true,
+        },
+        Vec::new(),
+    )
+}
+
+/**
+ * Number of running and waiting jobs over the window, stacked
+ */
+pub fn create_jobs_over_time_chart(jobs: &[Job], (start_s, end_s): (i64, i64)) -> MetricChart {
+    const NB_SAMPLES: i64 = 100;
+    let step_s = ((end_s - start_s) / NB_SAMPLES).max(1);
+    let mut running = Vec::new();
+    let mut waiting = Vec::new();
+
+    for time in (start_s..=end_s).step_by(step_s as usize) {
+        let not_stopped = |job: &Job| job.stop_time <= 0 || job.stop_time > time;
+        let (mut nb_running, mut nb_waiting) = (0, 0);
+        for job in jobs.iter().filter(|job| job.id != 0 && not_stopped(job)) {
+            if job.start_time > 0 && job.start_time <= time {
+                nb_running += 1;
+            } else if job.submission_time > 0
+                && job.submission_time <= time
+                && (job.start_time <= 0 || job.start_time > time)
+            {
+                nb_waiting += 1;
+            }
+        }
+        running.push([time as f64, nb_running as f64]);
+        waiting.push([time as f64, nb_waiting as f64]);
+    }
+
+    MetricChart::new(
+        &t!("app.dashboard.widgets.jobs_over_time"),
+        ChartType::StackedArea(vec![
+            Series {
+                name: JobState::Running.get_label(),
+                color: JobState::Running.get_color().1,
+                points: running,
+            },
+            Series {
+                name: JobState::Waiting.get_label(),
+                color: JobState::Waiting.get_color().1,
+                points: waiting,
+            },
+        ]),
+        Vec::new(),
+    )
+    .x_axis(&t!("app.dashboard.widgets.time"), AxisFormat::Time)
+    .y_axis(&t!("app.dashboard.widgets.jobs"), AxisFormat::Number)
+    .interactive(true)
+}
+
+/**
+ * Cumulative number of jobs submitted, started and finished over the window
+ */
+pub fn create_throughput_chart(jobs: &[Job], (start_s, end_s): (i64, i64)) -> MetricChart {
+    let cumulative = |times: Vec<i64>, name: String, color: Color32| {
+        let mut times: Vec<i64> = times
+            .into_iter()
+            .filter(|time| (start_s..=end_s).contains(time))
+            .collect();
+        times.sort_unstable();
+        let mut points = vec![[start_s as f64, 0.0]];
+        for (index, time) in times.iter().enumerate() {
+            points.push([*time as f64, (index + 1) as f64]);
+        }
+        points.push([end_s as f64, times.len() as f64]);
+        Series {
+            name,
+            color,
+            points,
+        }
+    };
+    let jobs: Vec<&Job> = jobs.iter().filter(|job| job.id != 0).collect();
+
+    MetricChart::new(
+        &t!("app.dashboard.widgets.throughput"),
+        ChartType::Line(vec![
+            cumulative(
+                jobs.iter().map(|job| job.submission_time).collect(),
+                t!("app.dashboard.widgets.submitted").to_string(),
+                JobState::Waiting.get_color().1,
+            ),
+            cumulative(
+                jobs.iter().map(|job| job.start_time).collect(),
+                t!("app.dashboard.widgets.started").to_string(),
+                JobState::Running.get_color().1,
+            ),
+            cumulative(
+                jobs.iter().map(|job| job.stop_time).collect(),
+                t!("app.dashboard.widgets.finished").to_string(),
+                JobState::Terminated.get_color().1,
+            ),
+        ]),
+        Vec::new(),
+    )
+    .x_axis(&t!("app.dashboard.widgets.time"), AxisFormat::Time)
+    .y_axis(&t!("app.dashboard.widgets.jobs"), AxisFormat::Number)
+    .interactive(true)
+}
+
+/**
+ * Histogram of the time between the submission and the start of the started jobs
+ */
+pub fn create_wait_time_histogram(jobs: &[Job]) -> MetricChart {
+    const NB_BINS: usize = 20;
+    let waits: Vec<f64> = jobs
+        .iter()
+        .filter(|job| job.id != 0 && job.start_time > 0 && job.submission_time > 0)
+        .map(|job| (job.start_time - job.submission_time).max(0) as f64)
+        .collect();
+    let max_wait = waits.iter().copied().fold(0.0, f64::max);
+    let bin_width = (max_wait / NB_BINS as f64).max(1.0);
+
+    let mut counts = [0usize; NB_BINS];
+    for wait in &waits {
+        counts[((wait / bin_width) as usize).min(NB_BINS - 1)] += 1;
+    }
+    let bars = counts
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .map(|(index, count)| {
+            Bar::new((index as f64 + 0.5) * bin_width, *count as f64)
+                .width(bin_width)
+                .name(format!(
+                    "{} - {}",
+                    format_duration((index as f64 * bin_width) as i64),
+                    format_duration(((index + 1) as f64 * bin_width) as i64)
+                ))
+        })
+        .collect();
+
+    MetricChart::new(
+        &t!("app.dashboard.widgets.wait_histogram"),
+        ChartType::Histogram(
+            BarChart::new(bars)
+                .name(t!("app.dashboard.widgets.jobs"))
+                .color(JobState::Waiting.get_color().1),
+        ),
+        Vec::new(),
+    )
+    .x_axis(&t!("app.dashboard.widgets.wait_time"), AxisFormat::Duration)
+    .y_axis(&t!("app.dashboard.widgets.jobs"), AxisFormat::Number)
+}
+
+/**
+ * Runtime of the started jobs against their walltime, one series per state
+ */
+pub fn create_runtime_scatter_chart(jobs: &[Job], now: i64) -> MetricChart {
+    let mut series: BTreeMap<JobState, Vec<[f64; 2]>> = BTreeMap::new();
+    for job in jobs
+        .iter()
+        .filter(|job| job.id != 0 && job.start_time > 0 && job.walltime > 0)
+    {
+        let stop_time = if job.stop_time > 0 {
+            job.stop_time
+        } else {
+            now
+        };
+        let runtime = (stop_time - job.start_time).max(0);
+        series
+            .entry(job.state.clone())
+            .or_default()
+            .push([job.walltime as f64, runtime as f64]);
+    }
+
+    MetricChart::new(
+        &t!("app.dashboard.widgets.runtime_scatter"),
+        ChartType::Scatter(
+            series
+                .into_iter()
+                .map(|(state, points)| Series {
+                    name: state.get_label(),
+                    color: state.get_color().1,
+                    points,
+                })
+                .collect(),
+        ),
+        Vec::new(),
+    )
+    .x_axis(&t!("app.dashboard.widgets.walltime"), AxisFormat::Duration)
+    .y_axis(&t!("app.dashboard.widgets.runtime"), AxisFormat::Duration)
+    .interactive(true)
+}
@@ -1,89 +1,173 @@
-use super::{metric_box::MetricBox, metric_chart::MetricChart};
-use eframe::egui;
+use super::dashboard_layout::{DashboardWidget, NamedLayout};
+use super::metric_box::MetricBox;
+use eframe::egui::{self, Vec2};
 
+/**
+ * Change of a widget asked with its edition buttons
+ */
+enum WidgetEdit {
+    MoveBefore,
+    MoveAfter,
+    Narrower,
+    Wider,
+    Shorter,
+    Taller,
+    Remove,
+}
+
+/*
+ * `MetricGrid` shows the widgets of a layout in reading order, each widget spanning
+ * a number of columns and rows of the grid. A widget too wide for the rest of a row
+ * goes to the next one, the height of a row being the height of its tallest widget.
+ * When editing, each widget has buttons to move, resize and remove it.
+ */
 pub struct MetricGrid {
-    columns: usize,
     spacing: f32,
 }
 
 impl Default for MetricGrid {
     fn default() -> Self {
-        Self {
-            columns: 3,
-            spacing: 10.0,
-        }
+        Self { spacing: 10.0 }
     }
 }
 
 impl MetricGrid {
-    pub fn show<F>(&self, ui: &mut egui::Ui, add_contents: F)
+    /**
+     * Shows the widgets of the layout, returns true if the layout was edited
+     */
+    pub fn show<F>(
+        &self,
+        ui: &mut egui::Ui,
+        layout: &mut NamedLayout,
+        editing: bool,
+        mut add_widget: F,
+    ) -> bool
     where
-        F: FnOnce(&mut MetricGridBuilder),
+        F: FnMut(&mut egui::Ui, &DashboardWidget, Vec2),
     {
+        let columns = layout.columns.clamp(1, NamedLayout::MAX_COLUMNS);
         let available_width = ui.available_width();
 
         // First calculate the total minimum width needed
-        let min_total_width = (MetricBox::MIN_WIDTH * self.columns as f32)
-            + (self.spacing * (self.columns - 1) as f32);
+        let min_total_width =
+            (MetricBox::MIN_WIDTH * columns as f32) + (self.spacing * (columns - 1) as f32);
 
         // If available width is less than minimum, use minimum
         let actual_width = available_width.max(min_total_width);
 
         // Recalculate column width taking spacing into account
-        let column_width =
-            (actual_width - (self.spacing * (self.columns - 1) as f32)) / self.columns as f32;
+        let column_width = (actual_width - (self.spacing * (columns - 1) as f32)) / columns as f32;
+        let size_of = |widget: &DashboardWidget| {
+            let width = widget.width.clamp(1, columns) as f32;
+            let height = widget.height.clamp(1, DashboardWidget::MAX_HEIGHT) as f32;
+            Vec2::new(
+                width * column_width + (width - 1.0) * self.spacing,
+                height * MetricBox::MIN_HEIGHT + (height - 1.0) * self.spacing,
+            )
+        };
 
-        egui::Grid::new("metrics_grid")
-            .spacing([self.spacing, self.spacing])
-            .min_col_width(column_width)
-            .max_col_width(column_width)
-            .show(ui, |ui| {
-                let mut builder = MetricGridBuilder {
-                    ui,
-                    column_width,
-                    current_column: 0,
-                    columns: self.columns,
-                };
-                add_contents(&mut builder);
+        // Split the widgets in rows
+        let mut rows: Vec<Vec<usize>> = Vec::new();
+        let mut used_columns = columns;
+        for (index, widget) in layout.widgets.iter().enumerate() {
+            let width = widget.width.clamp(1, columns);
+            if used_columns + width > columns {
+                rows.push(Vec::new());
+                used_columns = 0;
+            }
+            rows.last_mut().unwrap().push(index);
+            used_columns += width;
+        }
 
-                if builder.current_column > 0 && builder.current_column < builder.columns {
-                    builder.new_row();
+        let mut edit = None;
+        for row in rows {
+            ui.horizontal_top(|ui| {
+                ui.spacing_mut().item_spacing.x = self.spacing;
+                for index in row {
+                    let widget = &layout.widgets[index];
+                    let mut size = size_of(widget);
+                    ui.push_id(index, |ui| {
+                        ui.allocate_ui_with_layout(
+                            size,
+                            egui::Layout::top_down(egui::Align::Min),
+                            |ui| {
+                                if editing {
+                                    let before = ui.cursor().top();
+                                    if let Some(action) = self.edit_buttons_ui(ui) {
+                                        edit = Some((index, action));
+                                    }
+                                    size.y -= ui.cursor().top() - before;
+                                }
+                                add_widget(ui, widget, size);
+                            },
+                        );
+                    });
                 }
             });
-    }
-}
-
-pub struct MetricGridBuilder<'a> {
-    ui: &'a mut egui::Ui,
-    column_width: f32,
-    current_column: usize,
-    columns: usize,
-}
-
-impl<'a> MetricGridBuilder<'a> {
-    pub fn add_metric(&mut self, metric: MetricBox) {
-        if self.current_column >= self.columns {
-            self.new_row();
+            ui.add_space(self.spacing);
         }
 
-        self.ui
-            .add_sized([self.column_width, MetricBox::MIN_HEIGHT], metric);
-        self.current_column += 1;
+        match edit {
+            Some((index, action)) => {
+                Self::apply_edit(layout, index, action);
+                true
+            }
+            None => false,
+        }
     }
 
-    pub fn add_chart(&mut self, chart: MetricChart) {
-        if self.current_column >= self.columns {
-            self.new_row();
-        }
-        self.ui
-            .add_sized([self.column_width, MetricBox::MIN_HEIGHT], chart);
-        self.current_column += 1;
+    fn edit_buttons_ui(&self, ui: &mut egui::Ui) -> Option<WidgetEdit> {
+        let mut action = None;
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 2.0;
+            for (label, hint, edit) in [
+                (
+                    "⏴",
+                    t!("app.dashboard.layout.move_before"),
+                    WidgetEdit::MoveBefore,
+                ),
+                (
+                    "⏵",
+                    t!("app.dashboard.layout.move_after"),
+                    WidgetEdit::MoveAfter,
+                ),
+                (
+                    "⇤",
+                    t!("app.dashboard.layout.narrower"),
+                    WidgetEdit::Narrower,
+                ),
+                ("⇥", t!("app.dashboard.layout.wider"), WidgetEdit::Wider),
+                ("⏶", t!("app.dashboard.layout.shorter"), WidgetEdit::Shorter),
+                ("⏷", t!("app.dashboard.layout.taller"), WidgetEdit::Taller),
+                ("🗑", t!("app.dashboard.layout.remove"), WidgetEdit::Remove),
+            ] {
+                if ui.small_button(label).on_hover_text(hint).clicked() {
+                    action = Some(edit);
+                }
+            }
+        });
+        action
     }
 
-    pub fn new_row(&mut self) {
-        if self.current_column > 0 {
-            self.ui.end_row();
+    fn apply_edit(layout: &mut NamedLayout, index: usize, action: WidgetEdit) {
+        let columns = layout.columns.clamp(1, NamedLayout::MAX_COLUMNS);
+        let nb_widgets = layout.widgets.len();
+        let widget = &mut layout.widgets[index];
+        match action {
+            WidgetEdit::MoveBefore if index > 0 => layout.widgets.swap(index - 1, index),
+            WidgetEdit::MoveAfter if index + 1 < nb_widgets => {
+                layout.widgets.swap(index, index + 1)
+            }
+            WidgetEdit::Narrower => widget.width = widget.width.min(columns).max(2) - 1,
+            WidgetEdit::Wider => widget.width = (widget.width + 1).min(columns),
+            WidgetEdit::Shorter => widget.height = widget.height.max(2) - 1,
+            WidgetEdit::Taller => {
+                widget.height = (widget.height + 1).min(DashboardWidget::MAX_HEIGHT)
+            }
+            WidgetEdit::Remove => {
+                layout.widgets.remove(index);
+            }
+            _ => {}
         }
-        self.current_column = 0;
     }
 }
@@ -3,4 +3,13 @@ pub mod metric_box;
 pub mod metric_grid;
 pub mod metric_chart;
 pub mod job_table_col_selection;
-pub mod job_table_sorting;
\ No newline at end of file
+pub mod job_table_export;
+pub mod job_table_grouping;
+pub mod job_table_sorting;
+pub mod annotation_list;
+pub mod cluster_health;
+pub mod comparison_panel;
+pub mod dashboard_layout;
+pub mod dashboard_widgets;
+pub mod time_series;
+pub mod usage_report;
@@ -0,0 +1,265 @@
+use super::dashboard_widgets::widget_frame;
+use super::metric_chart::AxisFormat;
+use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::data_structure::job::{Job, JobState};
+use crate::views::components::time_buckets::Buckets;
+use eframe::egui::{self, Color32, Vec2};
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+use std::collections::HashMap;
+
+/**
+ * Quantity followed by a time series widget
+ */
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SeriesKind {
+    RunningJobs,
+    WaitingJobs,
+    BusyCores, // Average number of resources used by running jobs, per cluster
+}
+
+impl SeriesKind {
+    pub fn get_label(&self) -> String {
+        match self {
+            SeriesKind::RunningJobs => t!("app.dashboard.widgets.running_over_time").to_string(),
+            SeriesKind::WaitingJobs => t!("app.dashboard.widgets.backlog_over_time").to_string(),
+            SeriesKind::BusyCores => t!("app.dashboard.widgets.busy_cores").to_string(),
+        }
+    }
+
+    /**
+     * Returns the period during which the job is counted, the running jobs end now
+     */
+    fn job_interval(&self, job: &Job, now: i64) -> Option<(i64, i64)> {
+        let end_or_now = |time: i64| if time > 0 { time } else { now };
+        let interval = match self {
+            SeriesKind::RunningJobs | SeriesKind::BusyCores => {
+                (job.start_time > 0).then(|| (job.start_time, end_or_now(job.stop_time)))
+            }
+            SeriesKind::WaitingJobs => (job.submission_time > 0).then(|| {
+                let end = if job.start_time > 0 {
+                    job.start_time
+                } else {
+                    end_or_now(job.stop_time)
+                };
+                (job.submission_time, end)
+            }),
+        };
+        interval.filter(|(start, end)| end > start)
+    }
+
+    /**
+     * Computes the value of each bucket, one series per cluster for the busy cores
+     */
+    fn compute(
+        &self,
+        app: &ApplicationContext,
+        buckets: &Buckets,
+        now: i64,
+    ) -> Vec<(String, Vec<f64>)> {
+        let jobs = app.filtered_jobs.iter().filter(|job| job.id != 0);
+        match self {
+            SeriesKind::RunningJobs | SeriesKind::WaitingJobs => {
+                let mut values = vec![0.0; buckets.count];
+                for (first, last) in jobs.filter_map(|job| {
+                    self.job_interval(job, now)
+                        .and_then(|interval| buckets.range(interval))
+                }) {
+                    for value in &mut values[first..=last] {
+                        *value += 1.0;
+                    }
+                }
+                let state = if *self == SeriesKind::RunningJobs {
+                    JobState::Running
+                } else {
+                    JobState::Waiting
+                };
+                vec![(state.get_label(), values)]
+            }
+            SeriesKind::BusyCores => {
+                // Cluster owning each resource
+                let mut owners: HashMap<u32, usize> = HashMap::new();
+                for (index, cluster) in app.all_clusters.iter().enumerate() {
+                    for resource_id in &cluster.resource_ids {
+                        owners.insert(*resource_id, index);
+                    }
+                }
+                let mut series = vec![vec![0.0; buckets.count]; app.all_clusters.len()];
+
+                // Resources used during a bucket, weighted by the part of the bucket used
+                for job in jobs {
+                    let Some((start, end)) = self.job_interval(job, now) else {
+                        continue;
+                    };
+                    let Some((first, last)) = buckets.range((start, end)) else {
+                        continue;
+                    };
+                    let mut counts: HashMap<usize, f64> = HashMap::new();
+                    for resource_id in &job.assigned_resources {
+                        if let Some(cluster) = owners.get(resource_id) {
+                            *counts.entry(*cluster).or_default() += 1.0;
+                        }
+                    }
+                    let weights: Vec<f64> = (first..=last)
+                        .map(|index| {
+                            let bucket_start = buckets.start(index);
+                            let overlap =
+                                end.min(bucket_start + buckets.bucket_s) - start.max(bucket_start);
+                            overlap as f64 / buckets.bucket_s as f64
+                        })
+                        .collect();
+                    for (cluster, count) in &counts {
+                        for (value, weight) in
+                            series[*cluster][first..=last].iter_mut().zip(&weights)
+                        {
+                            *value += count * weight;
+                        }
+                    }
+                }
+                app.all_clusters
+                    .iter()
+                    .zip(series)
+                    .filter(|(_, values)| values.iter().any(|value| *value > 0.0))
+                    .map(|(cluster, values)| (cluster.name.clone(), values))
+                    .collect()
+            }
+        }
+    }
+
+    /**
+     * Describes the jobs counted in a bucket
+     */
+    fn describe_jobs(
+        &self,
+        app: &ApplicationContext,
+        buckets: &Buckets,
+        index: usize,
+        now: i64,
+    ) -> String {
+        const MAX_JOBS: usize = 15;
+        let bucket = (
+            buckets.start(index),
+            buckets.start(index) + buckets.bucket_s,
+        );
+        let jobs: Vec<&Job> = app
+            .filtered_jobs
+            .iter()
+            .filter(|job| job.id != 0)
+            .filter(|job| {
+                self.job_interval(job, now)
+                    .is_some_and(|(start, end)| start < bucket.1 && end > bucket.0)
+            })
+            .collect();
+
+        let mut lines = vec![format!(
+            "{} - {}",
+            AxisFormat::Time.format(bucket.0 as f64),
+            AxisFormat::Time.format(bucket.1 as f64)
+        )];
+        lines.push(t!("app.dashboard.widgets.jobs_involved", count = jobs.len()).to_string());
+        for job in jobs.iter().take(MAX_JOBS) {
+            lines.push(format!(
+                "#{} {} ({}, {})",
+                job.id,
+                job.owner,
+                job.state.get_label(),
+                job.assigned_resources.len()
+            ));
+        }
+        if jobs.len() > MAX_JOBS {
+            lines.push(format!("… (+{})", jobs.len() - MAX_JOBS));
+        }
+        lines.join("\n")
+    }
+}
+
+/**
+ * Shows the series over the window of the application, the buckets being computed again
+ * for the visible part of the plot when zooming
+ */
+pub fn show_time_series(ui: &mut egui::Ui, kind: SeriesKind, app: &ApplicationContext, size: Vec2) {
+    const BUCKET_WIDTH_POINTS: f32 = 8.0; // Approximate width of a bucket
+    const MAX_BUCKETS: i64 = 200;
+
+    let now = chrono::Utc::now().timestamp();
+    let window = (
+        app.get_start_date().timestamp(),
+        app.get_end_date().timestamp(),
+    );
+    let colors = [
+        Color32::from_rgb(70, 130, 180),
+        Color32::from_rgb(230, 159, 0),
+        Color32::from_rgb(0, 158, 115),
+        Color32::from_rgb(204, 121, 167),
+        Color32::from_rgb(213, 94, 0),
+        Color32::from_rgb(86, 180, 233),
+    ];
+
+    widget_frame(ui, &kind.get_label(), size, |ui| {
+        let y_name = match kind {
+            SeriesKind::BusyCores => t!("app.dashboard.widgets.cores"),
+            _ => t!("app.dashboard.widgets.jobs"),
+        };
+        let response = Plot::new("time_series")
+            .height(size.y - 45.0) // Margins and title of the frame
+            .allow_scroll(false)
+            .include_x(window.0 as f64)
+            .include_x(window.1 as f64)
+            .include_y(0.0)
+            .legend(Legend::default())
+            .x_axis_formatter(|mark, _range| AxisFormat::Time.format(mark.value))
+            .label_formatter(move |name, point| {
+                format!(
+                    "{}\n{}\n{}: {}",
+                    name,
+                    AxisFormat::Time.format(point.x),
+                    y_name,
+                    AxisFormat::Number.format((point.y * 10.0).round() / 10.0)
+                )
+            })
+            .show(ui, |plot_ui| {
+                // The buckets cover the visible part of the window
+                let bounds = plot_ui.plot_bounds();
+                let visible = (
+                    (bounds.min()[0] as i64).max(window.0),
+                    (bounds.max()[0] as i64).min(window.1),
+                );
+                let visible = if visible.1 > visible.0 {
+                    visible
+                } else {
+                    window
+                };
+                let nb_buckets = (plot_ui.response().rect.width() / BUCKET_WIDTH_POINTS) as i64;
+                let buckets = Buckets::new(visible, nb_buckets.clamp(1, MAX_BUCKETS));
+
+                for (index, (name, values)) in
+                    kind.compute(app, &buckets, now).into_iter().enumerate()
+                {
+                    let points: PlotPoints = values
+                        .iter()
+                        .enumerate()
+                        .map(|(index, value)| {
+                            [
+                                (buckets.start(index) as f64) + buckets.bucket_s as f64 / 2.0,
+                                *value,
+                            ]
+                        })
+                        .collect();
+                    plot_ui.line(
+                        Line::new(points)
+                            .name(name)
+                            .color(colors[index % colors.len()]),
+                    );
+                }
+
+                plot_ui
+                    .pointer_coordinate()
+                    .and_then(|point| buckets.index_at(point.x))
+                    .map(|index| kind.describe_jobs(app, &buckets, index, now))
+            });
+
+        // Jobs of the hovered bucket
+        if let Some(text) = response.inner {
+            response.response.on_hover_text(text);
+        }
+    });
+}
@@ -0,0 +1,257 @@
+use super::metric_chart::{ChartType, MetricChart};
+use crate::models::data_structure::cluster::Cluster;
+use crate::models::data_structure::job::Job;
+use crate::models::data_structure::resource_index::ResourceIndex;
+use crate::views::components::gantt_export::GanttExport;
+use crate::views::components::gantt_selection::csv_field;
+use chrono::Local;
+use egui_plot::{Bar, BarChart};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use strum_macros::EnumIter;
+
+/**
+ * Attribute of the jobs the usage is accounted by
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+pub enum UsageKey {
+    Owner,
+    Queue,
+    Project,
+}
+
+impl UsageKey {
+    pub fn get_label(&self) -> String {
+        match self {
+            UsageKey::Owner => t!("app.accounting.owner").to_string(),
+            UsageKey::Queue => t!("app.accounting.queue").to_string(),
+            UsageKey::Project => t!("app.accounting.project").to_string(),
+        }
+    }
+
+    /**
+     * Returns the name of the key in the exported files
+     */
+    fn csv_name(&self) -> &'static str {
+        match self {
+            UsageKey::Owner => "owner",
+            UsageKey::Queue => "queue",
+            UsageKey::Project => "project",
+        }
+    }
+
+    fn value<'a>(&self, job: &'a Job) -> &'a str {
+        match self {
+            UsageKey::Owner => &job.owner,
+            UsageKey::Queue => &job.queue,
+            UsageKey::Project => &job.project,
+        }
+    }
+}
+
+/**
+ * Column of the usage table the rows are sorted by
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UsageColumn {
+    Name,
+    Jobs,
+    CoreHours,
+    GpuHours,
+}
+
+/**
+ * Usage of the jobs sharing the same owner, queue or project
+ */
+#[derive(Clone, Debug, Default)]
+pub struct UsageRow {
+    pub name: String,
+    pub jobs: usize,     // Number of jobs having run during the period
+    pub core_hours: f64, // Core-hours used during the period
+    pub gpu_hours: f64,  // Hours of the resources having a GPU used during the period
+}
+
+/*
+ * `UsageReport` accounts the resources used by the jobs during a period, the part of
+ * a run outside the period being ignored. Each assigned resource counts as a core and
+ * the resources having a GPU are also counted as GPU-hours. The capacity of the
+ * clusters is the number of their resources over the length of the period.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct UsageReport {
+    pub rows: Vec<UsageRow>,
+    pub total: UsageRow,
+    pub core_capacity: f64, // Core-hours the clusters can provide during the period
+    pub gpu_capacity: f64,  // GPU-hours the clusters can provide during the period
+}
+
+impl UsageReport {
+    pub fn compute<'a>(
+        jobs: impl Iterator<Item = &'a Job>,
+        key: UsageKey,
+        (start_s, end_s): (i64, i64),
+        clusters: &[Cluster],
+        resource_index: &ResourceIndex,
+        now: i64,
+    ) -> Self {
+        let mut rows: HashMap<&str, UsageRow> = HashMap::new();
+        for job in jobs.filter(|job| job.id != 0 && job.start_time > 0) {
+            let run_end = if job.stop_time > 0 {
+                job.stop_time
+            } else {
+                now
+            };
+            let overlap_s = (run_end.min(end_s) - job.start_time.max(start_s)).max(0);
+            if overlap_s == 0 {
+                continue;
+            }
+            let hours = overlap_s as f64 / 3600.0;
+            let nb_gpus = job
+                .assigned_resources
+                .iter()
+                .filter(|id| {
+                    resource_index
+                        .get(**id)
+                        .is_some_and(|location| location.gpu)
+                })
+                .count();
+
+            let row = rows.entry(key.value(job)).or_insert_with(|| UsageRow {
+                name: key.value(job).to_string(),
+                ..Default::default()
+            });
+            row.jobs += 1;
+            row.core_hours += job.assigned_resources.len() as f64 * hours;
+            row.gpu_hours += nb_gpus as f64 * hours;
+        }
+
+        let mut rows: Vec<UsageRow> = rows.into_values().collect();
+        sort_rows(&mut rows, UsageColumn::CoreHours, false);
+        let total = UsageRow {
+            name: t!("app.accounting.total").to_string(),
+            jobs: rows.iter().map(|row| row.jobs).sum(),
+            core_hours: rows.iter().map(|row| row.core_hours).sum(),
+            gpu_hours: rows.iter().map(|row| row.gpu_hours).sum(),
+        };
+
+        // Every resource of the clusters, and the ones having a GPU
+        let (nb_resources, nb_gpus) = clusters
+            .iter()
+            .flat_map(|cluster| &cluster.hosts)
+            .flat_map(|host| &host.cpus)
+            .flat_map(|cpu| &cpu.resources)
+            .fold((0, 0), |(nb_resources, nb_gpus), resource| {
+                (
+                    nb_resources + 1,
+                    nb_gpus + resource.gpu_model.is_some() as usize,
+                )
+            });
+        let period_hours = (end_s - start_s).max(0) as f64 / 3600.0;
+
+        UsageReport {
+            rows,
+            total,
+            core_capacity: nb_resources as f64 * period_hours,
+            gpu_capacity: nb_gpus as f64 * period_hours,
+        }
+    }
+
+    /**
+     * Returns true if the clusters have resources with a GPU
+     */
+    pub fn has_gpus(&self) -> bool {
+        self.gpu_capacity > 0.0
+    }
+
+    /**
+     * Returns the share of the capacity of the clusters used by a row, in percent
+     */
+    pub fn capacity_percent(&self, row: &UsageRow) -> f64 {
+        if self.core_capacity > 0.0 {
+            row.core_hours / self.core_capacity * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /**
+     * Creates a bar chart of the rows using the most core-hours
+     */
+    pub fn top_chart(&self, key: UsageKey, top_n: usize) -> MetricChart {
+        let mut rows: Vec<&UsageRow> = self.rows.iter().collect();
+        rows.sort_by(|a, b| b.core_hours.total_cmp(&a.core_hours));
+        rows.truncate(top_n);
+
+        let labels: Vec<String> = rows.iter().map(|row| row.name.clone()).collect();
+        let bars: Vec<Bar> = rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| Bar::new(index as f64, row.core_hours).name(&row.name))
+            .collect();
+        let title = t!(
+            "app.accounting.top_chart",
+            count = top_n,
+            key = key.get_label()
+        );
+        MetricChart::new(
+            &title,
+            ChartType::Bar(BarChart::new(bars).name(t!("app.accounting.core_hours"))),
+            labels,
+        )
+    }
+
+    /**
+     * Writes the rows and their total to a CSV file in the export folder
+     */
+    pub fn write_csv(
+        &self,
+        key: UsageKey,
+        (start_s, end_s): (i64, i64),
+    ) -> Result<PathBuf, String> {
+        let folder = Path::new(GanttExport::EXPORT_FOLDER);
+        if !folder.exists() {
+            std::fs::create_dir_all(folder).map_err(|e| e.to_string())?;
+        }
+        let path = folder.join(format!(
+            "accounting_{}_{}.csv",
+            key.csv_name(),
+            Local::now().format("%Y%m%d_%H%M%S")
+        ));
+
+        let mut csv = format!(
+            "{},period_start,period_end,jobs,core_hours,gpu_hours,capacity_percent\n",
+            key.csv_name()
+        );
+        for row in self.rows.iter().chain(std::iter::once(&self.total)) {
+            csv.push_str(&format!(
+                "{},{},{},{},{:.3},{:.3},{:.3}\n",
+                csv_field(&row.name),
+                start_s,
+                end_s,
+                row.jobs,
+                row.core_hours,
+                row.gpu_hours,
+                self.capacity_percent(row),
+            ));
+        }
+        std::fs::write(&path, csv).map_err(|e| e.to_string())?;
+
+        Ok(path)
+    }
+}
+
+/**
+ * Sorts the rows by a column, the rows having the same value being sorted by name
+ */
+pub fn sort_rows(rows: &mut [UsageRow], column: UsageColumn, ascending: bool) {
+    rows.sort_by(|a, b| {
+        let cmp = match column {
+            UsageColumn::Name => a.name.cmp(&b.name),
+            UsageColumn::Jobs => a.jobs.cmp(&b.jobs),
+            UsageColumn::CoreHours => a.core_hours.total_cmp(&b.core_hours),
+            UsageColumn::GpuHours => a.gpu_hours.total_cmp(&b.gpu_hours),
+        };
+        let cmp = if ascending { cmp } else { cmp.reverse() };
+        cmp.then_with(|| a.name.cmp(&b.name))
+    });
+}
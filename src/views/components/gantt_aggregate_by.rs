@@ -1,108 +1,201 @@
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum AggregateByLevel1Enum {
-    Owner,
+use crate::models::data_structure::{job::Job, resource_index::ResourceLocation};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/**
+ * A key used to group the jobs of the Gantt chart
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum AggregateKey {
+    Site,
     Cluster,
     Host,
+    Cpu,
+    Resource,
+    Owner,
+    Queue,
+    State,
+    Project,
 }
 
-#[derive(PartialEq, Clone, Copy)]
-pub enum AggregateByLevel2Enum {
-    Owner,
-    Host,
-    None,
+impl AggregateKey {
+    pub fn get_label(&self) -> String {
+        match self {
+            AggregateKey::Site => t!("app.gantt.settings.site").to_string(),
+            AggregateKey::Cluster => t!("app.gantt.settings.cluster").to_string(),
+            AggregateKey::Host => t!("app.gantt.settings.host").to_string(),
+            AggregateKey::Cpu => t!("app.gantt.settings.cpu").to_string(),
+            AggregateKey::Resource => t!("app.gantt.settings.resource").to_string(),
+            AggregateKey::Owner => t!("app.gantt.settings.owner").to_string(),
+            AggregateKey::Queue => t!("app.gantt.settings.queue").to_string(),
+            AggregateKey::State => t!("app.gantt.settings.state").to_string(),
+            AggregateKey::Project => t!("app.gantt.settings.project").to_string(),
+        }
+    }
+
+    /**
+     * Returns true if the key groups the jobs by the resources they use
+     * A job then appears in every group owning one of its resources
+     */
+    pub fn is_location(&self) -> bool {
+        matches!(
+            self,
+            AggregateKey::Site
+                | AggregateKey::Cluster
+                | AggregateKey::Host
+                | AggregateKey::Cpu
+                | AggregateKey::Resource
+        )
+    }
+
+    /**
+     * Returns true if the groups of this key have their own resource state
+     */
+    pub fn has_state(&self) -> bool {
+        matches!(
+            self,
+            AggregateKey::Cluster | AggregateKey::Host | AggregateKey::Resource
+        )
+    }
+
+    /**
+     * Returns the group of a job for a key that is not a location
+     */
+    pub fn job_value(&self, job: &Job) -> String {
+        match self {
+            AggregateKey::Owner => job.owner.clone(),
+            AggregateKey::Queue => job.queue.clone(),
+            AggregateKey::State => job.state.get_label(),
+            AggregateKey::Project => job.project.clone(),
+            _ => String::new(),
+        }
+    }
+
+    /**
+     * Returns the group of a resource for a location key
+     */
+    pub fn location_value(&self, resource_id: u32, location: &ResourceLocation) -> String {
+        match self {
+            AggregateKey::Site => location.site.clone(),
+            AggregateKey::Cluster => location.cluster.clone(),
+            AggregateKey::Host => location.host.clone(),
+            AggregateKey::Cpu => location.cpu.clone(),
+            AggregateKey::Resource => resource_id.to_string(),
+            _ => String::new(),
+        }
+    }
 }
 
+/**
+ * Ordered list of the keys used to group the jobs, the first key being the top level
+ */
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct AggregateBy {
-    pub level_1: AggregateByLevel1Enum,
-    pub level_2: AggregateByLevel2Enum,
+    pub keys: Vec<AggregateKey>,
 }
 
 impl Default for AggregateBy {
     fn default() -> Self {
         Self {
-            level_1: AggregateByLevel1Enum::Cluster, // Default value for the first level is Cluster
-            level_2: AggregateByLevel2Enum::Host, // Default value for the second level is Host
+            keys: vec![AggregateKey::Cluster, AggregateKey::Host], // Default levels are Cluster then Host
         }
     }
 }
 
 /*
  * To manage the aggregation levels, wich is similar to a vertical zoom in the Gantt chart (the horizontal zoom being the time range)
- * The rule for aggregation is:
- * The first level must be higher than the second level, the order is:
- * Cluster -> Host -> Owner -> None
+ * Each key can be used once, there is always at least one level
+ * The levels can be reordered, removed, or added from the keys that are not used yet
  */
 impl AggregateBy {
+    /**
+     * Returns true if there is at least one level and each key is used once
+     */
+    pub fn is_valid(&self) -> bool {
+        !self.keys.is_empty()
+            && self
+                .keys
+                .iter()
+                .enumerate()
+                .all(|(index, key)| !self.keys[..index].contains(key))
+    }
+
+    /**
+     * Returns true if the last level groups the jobs by resources
+     */
+    pub fn ends_with_location(&self) -> bool {
+        self.keys.last().is_some_and(|key| key.is_location())
+    }
+
     pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.label(format!("{}:", t!("app.gantt.settings.aggregate_by")));
+
+        let mut move_up = None;
+        let mut remove = None;
+        let nb_keys = self.keys.len();
+
         egui::Grid::new("aggregate_by_grid")
-            .num_columns(3)
+            .num_columns(4)
             .show(ui, |ui| {
-                ui.label(format!("{}:", t!("app.gantt.settings.aggregate_by")));
-                ui.label(format!("{} 1:", t!("app.gantt.settings.level")));
-                let mut on_change_level_1 = false;
-                on_change_level_1 |= ui
-                    .radio_value(&mut self.level_1, AggregateByLevel1Enum::Cluster, "Cluster")
-                    .clicked();
-                on_change_level_1 |= ui
-                    .radio_value(
-                        &mut self.level_1,
-                        AggregateByLevel1Enum::Host,
-                        t!("app.gantt.settings.host"),
-                    )
-                    .clicked();
-                on_change_level_1 |= ui
-                    .radio_value(
-                        &mut self.level_1,
-                        AggregateByLevel1Enum::Owner,
-                        t!("app.gantt.settings.owner"),
-                    )
-                    .clicked();
-                ui.end_row();
-
-                if on_change_level_1 {
-                    self.level_2 = match self.level_1 {
-                        AggregateByLevel1Enum::Cluster => AggregateByLevel2Enum::Host,
-                        AggregateByLevel1Enum::Host => AggregateByLevel2Enum::Owner,
-                        AggregateByLevel1Enum::Owner => AggregateByLevel2Enum::None,
+                for index in 0..nb_keys {
+                    ui.label(format!("{} {}:", t!("app.gantt.settings.level"), index + 1));
+
+                    // Only the keys that are not used by another level can be selected
+                    // (a sub menu is used as combo boxes don't stay open inside a menu)
+                    let current = self.keys[index];
+                    ui.menu_button(current.get_label(), |ui| {
+                        for key in AggregateKey::iter() {
+                            if (key == current || !self.keys.contains(&key))
+                                && ui
+                                    .selectable_label(key == current, key.get_label())
+                                    .clicked()
+                            {
+                                self.keys[index] = key;
+                                ui.close_menu();
+                            }
+                        }
+                    });
+
+                    if ui.add_enabled(index > 0, egui::Button::new("⏶")).clicked() {
+                        move_up = Some(index);
                     }
+                    if ui
+                        .add_enabled(index + 1 < nb_keys, egui::Button::new("⏷"))
+                        .clicked()
+                    {
+                        move_up = Some(index + 1);
+                    }
+                    if ui
+                        .add_enabled(nb_keys > 1, egui::Button::new("🗑"))
+                        .clicked()
+                    {
+                        remove = Some(index);
+                    }
+                    ui.end_row();
                 }
+            });
 
-                ui.label("");
-                match self.level_1 {
-                    AggregateByLevel1Enum::Cluster => {
-                        ui.label(format!("{} 2:", t!("app.gantt.settings.level")));
-                        ui.radio_value(
-                            &mut self.level_2,
-                            AggregateByLevel2Enum::Host,
-                            t!("app.gantt.settings.host"),
-                        );
-                        ui.radio_value(
-                            &mut self.level_2,
-                            AggregateByLevel2Enum::Owner,
-                            t!("app.gantt.settings.owner"),
-                        );
-                        ui.radio_value(
-                            &mut self.level_2,
-                            AggregateByLevel2Enum::None,
-                            t!("app.gantt.settings.none"),
-                        );
-                    }
-                    AggregateByLevel1Enum::Owner => {}
-                    AggregateByLevel1Enum::Host => {
-                        ui.label(format!("{} 2:", t!("app.gantt.settings.level")));
-                        ui.radio_value(
-                            &mut self.level_2,
-                            AggregateByLevel2Enum::Owner,
-                            t!("app.gantt.settings.owner"),
-                        );
-                        ui.radio_value(
-                            &mut self.level_2,
-                            AggregateByLevel2Enum::None,
-                            t!("app.gantt.settings.none"),
-                        );
+        if let Some(index) = move_up {
+            self.keys.swap(index - 1, index);
+        }
+        if let Some(index) = remove {
+            self.keys.remove(index);
+        }
+
+        // Add a level with one of the unused keys
+        let unused_keys: Vec<AggregateKey> = AggregateKey::iter()
+            .filter(|key| !self.keys.contains(key))
+            .collect();
+        if !unused_keys.is_empty() {
+            ui.menu_button(t!("app.gantt.settings.add_level"), |ui| {
+                for key in unused_keys {
+                    if ui.button(key.get_label()).clicked() {
+                        self.keys.push(key);
+                        ui.close_menu();
                     }
                 }
-                ui.end_row();
             });
+        }
     }
 }
@@ -0,0 +1,570 @@
+use crate::models::utils::date_converter::format_timestamp_as;
+use chrono::Local;
+use eframe::egui;
+use egui::{pos2, vec2, Align, Align2, Color32, FontId, Pos2, Rect, Vec2};
+use std::path::{Path, PathBuf};
+
+/**
+ * A job bar of the exported Gantt chart
+ */
+pub struct ExportBar {
+    pub label: String,  // Text painted inside the bar when it fits
+    pub start_s: i64,   // Start timestamp of the bar
+    pub end_s: i64,     // End timestamp of the bar
+    pub color: Color32, // Fill color of the bar
+}
+
+/**
+ * A line of the exported Gantt chart, either a group header or a line of job bars
+ */
+pub enum ExportRow {
+    Header { label: String, level: u8 },
+    Jobs(Vec<ExportBar>),
+}
+
+/**
+ * Everything needed to render the Gantt chart outside of the canvas
+ */
+pub struct GanttExportData {
+    pub title: String,
+    pub start_s: i64,
+    pub end_s: i64,
+    pub rows: Vec<ExportRow>,
+    pub legend: Vec<(String, Color32)>,
+}
+
+/****************************************************************************************************************************/
+// EXPORT COMPONENT
+/****************************************************************************************************************************/
+
+/**
+ * GanttExport writes the current Gantt view to an SVG and a PNG file
+ */
+#[derive(Default)]
+pub struct GanttExport {
+    status: Option<(String, f64)>, // Status message and the time it was set
+}
+
+impl GanttExport {
+    pub const EXPORT_FOLDER: &'static str = "./exports";
+    const STATUS_DURATION_S: f64 = 5.0;
+
+    /**
+     * Shows the export button, returns true if the user asked for an export
+     */
+    pub fn button_ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let clicked = ui.button(t!("app.gantt.export.button")).clicked();
+        if clicked {
+            ui.close_menu();
+        }
+        clicked
+    }
+
+    /**
+     * Shows the result of the last export for a few seconds
+     */
+    pub fn status_ui(&mut self, ui: &mut egui::Ui) {
+        if let Some((message, timestamp)) = &self.status {
+            if ui.input(|i| i.time) - timestamp < Self::STATUS_DURATION_S {
+                ui.label(message);
+            } else {
+                self.status = None; // Clear the message after timeout
+            }
+        }
+    }
+
+    pub fn export(&mut self, ctx: &egui::Context, data: &GanttExportData) {
+        let now = ctx.input(|i| i.time);
+        let message = match write_export_files(ctx, data) {
+            Ok(path) => t!("app.gantt.export.success", path = path.display()).to_string(),
+            Err(error) => t!("app.gantt.export.error", error = error).to_string(),
+        };
+        self.status = Some((message, now));
+    }
+}
+
+/**
+ * Writes the SVG and PNG files, returns the path of the files without extension
+ */
+fn write_export_files(ctx: &egui::Context, data: &GanttExportData) -> Result<PathBuf, String> {
+    let folder = Path::new(GanttExport::EXPORT_FOLDER);
+    if !folder.exists() {
+        std::fs::create_dir_all(folder).map_err(|e| e.to_string())?;
+    }
+    let base = folder.join(format!("gantt_{}", Local::now().format("%Y%m%d_%H%M%S")));
+
+    let scene = build_scene(ctx, data);
+    std::fs::write(base.with_extension("svg"), scene_to_svg(&scene)).map_err(|e| e.to_string())?;
+
+    let (width, height, pixels) = scene_to_rgba(ctx, &scene);
+    image::save_buffer(
+        base.with_extension("png"),
+        &pixels,
+        width,
+        height,
+        image::ExtendedColorType::Rgba8,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(base)
+}
+
+/****************************************************************************************************************************/
+// SCENE
+/****************************************************************************************************************************/
+
+/**
+ * Drawing primitives shared by the SVG and PNG writers (coordinates in points)
+ */
+enum Primitive {
+    Rect {
+        rect: Rect,
+        fill: Color32,
+    },
+    Line {
+        from: Pos2,
+        to: Pos2,
+        width: f32,
+        color: Color32,
+    },
+    Text {
+        pos: Pos2,
+        text: String,
+        size: f32,
+        color: Color32,
+        align: Align2,
+    },
+}
+
+struct Scene {
+    size: Vec2,
+    primitives: Vec<Primitive>,
+}
+
+const WIDTH: f32 = 1600.0; // Width of the exported image
+const MARGIN: f32 = 20.0; // Margin around the chart
+const LABEL_WIDTH: f32 = 220.0; // Width of the column with the group names
+const TITLE_HEIGHT: f32 = 50.0; // Height of the title and the period
+const AXIS_HEIGHT: f32 = 24.0; // Height of the time axis
+const HEADER_HEIGHT: f32 = 22.0; // Height of a group header row
+const ROW_HEIGHT: f32 = 20.0; // Height of a job row
+const LEGEND_ROW_HEIGHT: f32 = 22.0; // Height of a legend line
+const FONT_SIZE: f32 = 12.0;
+const TITLE_FONT_SIZE: f32 = 20.0;
+
+fn text_width(ctx: &egui::Context, text: &str, size: f32) -> f32 {
+    ctx.fonts(|f| {
+        f.layout_no_wrap(text.to_string(), FontId::proportional(size), Color32::BLACK)
+            .size()
+            .x
+    })
+}
+
+/**
+ * Returns the spacing between two ticks of the time axis, in seconds
+ */
+fn tick_spacing_s(range_s: i64) -> i64 {
+    const SPACINGS: [i64; 12] = [
+        60, 300, 600, 1800, 3600, 7200, 10800, 21600, 43200, 86400, 172800, 604800,
+    ];
+    SPACINGS
+        .into_iter()
+        .find(|spacing| range_s / spacing <= 12)
+        .unwrap_or(2_592_000)
+}
+
+fn format_tick(ts: i64) -> String {
+    format_timestamp_as(ts, "%m-%d %H:%M").unwrap_or_default()
+}
+
+fn build_scene(ctx: &egui::Context, data: &GanttExportData) -> Scene {
+    let text_color = Color32::from_gray(20);
+    let dim_color = Color32::from_gray(110);
+    let grid_color = Color32::from_gray(225);
+
+    let plot_left = MARGIN + LABEL_WIDTH;
+    let plot_right = WIDTH - MARGIN;
+    let range_s = (data.end_s - data.start_s).max(1);
+    let x_from_s =
+        |s: i64| plot_left + (plot_right - plot_left) * (s - data.start_s) as f32 / range_s as f32;
+
+    // Vertical layout
+    let axis_top = MARGIN + TITLE_HEIGHT;
+    let rows_top = axis_top + AXIS_HEIGHT;
+    let rows_height: f32 = data
+        .rows
+        .iter()
+        .map(|row| match row {
+            ExportRow::Header { .. } => HEADER_HEIGHT,
+            ExportRow::Jobs(_) => ROW_HEIGHT,
+        })
+        .sum();
+    let rows_bottom = rows_top + rows_height;
+
+    // Legend entries are laid out from left to right and wrap on the chart width
+    let mut legend_positions = Vec::new();
+    let mut legend_x = MARGIN;
+    let mut legend_y = rows_bottom + MARGIN + LEGEND_ROW_HEIGHT;
+    for (label, _) in &data.legend {
+        let entry_width = 16.0 + text_width(ctx, label, FONT_SIZE) + 24.0;
+        if legend_x + entry_width > WIDTH - MARGIN && legend_x > MARGIN {
+            legend_x = MARGIN;
+            legend_y += LEGEND_ROW_HEIGHT;
+        }
+        legend_positions.push(pos2(legend_x, legend_y));
+        legend_x += entry_width;
+    }
+    let height = if data.legend.is_empty() {
+        rows_bottom + MARGIN
+    } else {
+        legend_y + LEGEND_ROW_HEIGHT + MARGIN
+    };
+
+    let mut primitives = vec![Primitive::Rect {
+        rect: Rect::from_min_size(Pos2::ZERO, vec2(WIDTH, height)),
+        fill: Color32::WHITE,
+    }];
+
+    // Title and period
+    primitives.push(Primitive::Text {
+        pos: pos2(MARGIN, MARGIN),
+        text: data.title.clone(),
+        size: TITLE_FONT_SIZE,
+        color: text_color,
+        align: Align2::LEFT_TOP,
+    });
+    primitives.push(Primitive::Text {
+        pos: pos2(MARGIN, MARGIN + TITLE_FONT_SIZE + 8.0),
+        text: format!(
+            "{} → {}",
+            format_tick(data.start_s),
+            format_tick(data.end_s)
+        ),
+        size: FONT_SIZE,
+        color: dim_color,
+        align: Align2::LEFT_TOP,
+    });
+
+    // Time axis and grid
+    let spacing_s = tick_spacing_s(range_s);
+    let mut tick_s = data.start_s - data.start_s.rem_euclid(spacing_s) + spacing_s;
+    while tick_s < data.end_s {
+        let x = x_from_s(tick_s);
+        primitives.push(Primitive::Line {
+            from: pos2(x, axis_top + AXIS_HEIGHT - 6.0),
+            to: pos2(x, rows_bottom),
+            width: 1.0,
+            color: grid_color,
+        });
+        primitives.push(Primitive::Text {
+            pos: pos2(x, axis_top + AXIS_HEIGHT - 8.0),
+            text: format_tick(tick_s),
+            size: FONT_SIZE,
+            color: dim_color,
+            align: Align2::CENTER_BOTTOM,
+        });
+        tick_s += spacing_s;
+    }
+    primitives.push(Primitive::Line {
+        from: pos2(plot_left, rows_top),
+        to: pos2(plot_right, rows_top),
+        width: 1.0,
+        color: dim_color,
+    });
+
+    // Rows
+    let mut y = rows_top;
+    for row in &data.rows {
+        match row {
+            ExportRow::Header { label, level } => {
+                primitives.push(Primitive::Line {
+                    from: pos2(MARGIN, y),
+                    to: pos2(plot_right, y),
+                    width: if *level == 1 { 1.5 } else { 0.5 },
+                    color: dim_color,
+                });
+                primitives.push(Primitive::Text {
+                    pos: pos2(
+                        MARGIN + 16.0 * (*level as f32 - 1.0),
+                        y + HEADER_HEIGHT / 2.0,
+                    ),
+                    text: label.clone(),
+                    size: FONT_SIZE,
+                    color: text_color,
+                    align: Align2::LEFT_CENTER,
+                });
+                y += HEADER_HEIGHT;
+            }
+            ExportRow::Jobs(bars) => {
+                for bar in bars {
+                    let start_x = x_from_s(bar.start_s).max(plot_left);
+                    let end_x = x_from_s(bar.end_s).min(plot_right);
+                    if end_x <= start_x {
+                        continue; // Outside of the exported time window
+                    }
+                    let rect = Rect::from_min_max(
+                        pos2(start_x, y + 2.0),
+                        pos2(end_x.max(start_x + 1.0), y + ROW_HEIGHT - 2.0),
+                    );
+                    primitives.push(Primitive::Rect {
+                        rect,
+                        fill: bar.color,
+                    });
+                    if text_width(ctx, &bar.label, FONT_SIZE) + 6.0 < rect.width() {
+                        primitives.push(Primitive::Text {
+                            pos: rect.center(),
+                            text: bar.label.clone(),
+                            size: FONT_SIZE,
+                            color: text_color,
+                            align: Align2::CENTER_CENTER,
+                        });
+                    }
+                }
+                y += ROW_HEIGHT;
+            }
+        }
+    }
+
+    // Current time line
+    let now_s = chrono::Utc::now().timestamp();
+    if data.start_s <= now_s && now_s <= data.end_s {
+        let x = x_from_s(now_s);
+        primitives.push(Primitive::Line {
+            from: pos2(x, rows_top),
+            to: pos2(x, rows_bottom),
+            width: 2.0,
+            color: Color32::RED,
+        });
+    }
+
+    // Legend
+    if !data.legend.is_empty() {
+        primitives.push(Primitive::Text {
+            pos: pos2(MARGIN, rows_bottom + MARGIN),
+            text: t!("app.gantt.export.legend").to_string(),
+            size: FONT_SIZE,
+            color: text_color,
+            align: Align2::LEFT_TOP,
+        });
+    }
+    for ((label, color), pos) in data.legend.iter().zip(legend_positions) {
+        primitives.push(Primitive::Rect {
+            rect: Rect::from_min_size(pos2(pos.x, pos.y + 4.0), vec2(12.0, 12.0)),
+            fill: *color,
+        });
+        primitives.push(Primitive::Text {
+            pos: pos2(pos.x + 16.0, pos.y + 10.0),
+            text: label.clone(),
+            size: FONT_SIZE,
+            color: text_color,
+            align: Align2::LEFT_CENTER,
+        });
+    }
+
+    Scene {
+        size: vec2(WIDTH, height),
+        primitives,
+    }
+}
+
+/****************************************************************************************************************************/
+// SVG
+/****************************************************************************************************************************/
+
+fn svg_color(color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    format!(
+        "fill=\"#{:02x}{:02x}{:02x}\" fill-opacity=\"{:.3}\"",
+        r,
+        g,
+        b,
+        a as f32 / 255.0
+    )
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn scene_to_svg(scene: &Scene) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = scene.size.x,
+        h = scene.size.y
+    );
+
+    for primitive in &scene.primitives {
+        match primitive {
+            Primitive::Rect { rect, fill } => {
+                svg.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" {}/>\n",
+                    rect.min.x,
+                    rect.min.y,
+                    rect.width(),
+                    rect.height(),
+                    svg_color(*fill)
+                ));
+            }
+            Primitive::Line {
+                from,
+                to,
+                width,
+                color,
+            } => {
+                svg.push_str(&format!(
+                    "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke-width=\"{}\" {}/>\n",
+                    from.x,
+                    from.y,
+                    to.x,
+                    to.y,
+                    width,
+                    svg_color(*color).replace("fill", "stroke")
+                ));
+            }
+            Primitive::Text {
+                pos,
+                text,
+                size,
+                color,
+                align,
+            } => {
+                let anchor = match align.x() {
+                    Align::Min => "start",
+                    Align::Center => "middle",
+                    Align::Max => "end",
+                };
+                let baseline = match align.y() {
+                    Align::Min => "hanging",
+                    Align::Center => "central",
+                    Align::Max => "text-after-edge",
+                };
+                svg.push_str(&format!(
+                    "<text x=\"{:.1}\" y=\"{:.1}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"{}\" dominant-baseline=\"{}\" {}>{}</text>\n",
+                    pos.x,
+                    pos.y,
+                    size,
+                    anchor,
+                    baseline,
+                    svg_color(*color),
+                    escape_xml(text)
+                ));
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/****************************************************************************************************************************/
+// PNG
+/****************************************************************************************************************************/
+
+/**
+ * Blends a color on a pixel of an RGBA buffer
+ */
+fn blend_pixel(pixels: &mut [u8], width: u32, x: i32, y: i32, color: Color32, coverage: f32) {
+    let height = pixels.len() as u32 / (4 * width);
+    if x < 0 || y < 0 || x as u32 >= width || y as u32 >= height {
+        return;
+    }
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    let alpha = coverage * a as f32 / 255.0;
+    let index = 4 * (y as usize * width as usize + x as usize);
+    for (channel, value) in [r, g, b].into_iter().enumerate() {
+        let dst = pixels[index + channel] as f32;
+        pixels[index + channel] = (value as f32 * alpha + dst * (1.0 - alpha)).round() as u8;
+    }
+    pixels[index + 3] = 255;
+}
+
+fn fill_rect(pixels: &mut [u8], width: u32, rect: Rect, color: Color32) {
+    for y in rect.min.y.round() as i32..rect.max.y.round() as i32 {
+        for x in rect.min.x.round() as i32..rect.max.x.round() as i32 {
+            blend_pixel(pixels, width, x, y, color, 1.0);
+        }
+    }
+}
+
+/**
+ * Rasterizes the scene, text is drawn with the glyphs of the egui font atlas
+ * Returns the width, the height and the RGBA pixels of the image
+ */
+fn scene_to_rgba(ctx: &egui::Context, scene: &Scene) -> (u32, u32, Vec<u8>) {
+    let scale = ctx.fonts(|f| f.pixels_per_point());
+    let width = (scene.size.x * scale).ceil() as u32;
+    let height = (scene.size.y * scale).ceil() as u32;
+    let mut pixels = vec![255u8; (width * height * 4) as usize];
+
+    // Lay out every text first so that all the glyphs are in the atlas before reading it
+    let galleys: Vec<_> = scene
+        .primitives
+        .iter()
+        .map(|primitive| match primitive {
+            Primitive::Text {
+                text, size, color, ..
+            } => Some(
+                ctx.fonts(|f| f.layout_no_wrap(text.clone(), FontId::proportional(*size), *color)),
+            ),
+            _ => None,
+        })
+        .collect();
+    let atlas = ctx.fonts(|f| f.image());
+
+    for (primitive, galley) in scene.primitives.iter().zip(galleys) {
+        match primitive {
+            Primitive::Rect { rect, fill } => {
+                fill_rect(&mut pixels, width, *rect * scale, *fill);
+            }
+            Primitive::Line {
+                from,
+                to,
+                width: line_width,
+                color,
+            } => {
+                // Only horizontal and vertical lines are used by the scene
+                let half = (line_width / 2.0).max(0.5);
+                let rect = Rect::from_two_pos(*from, *to).expand(half);
+                fill_rect(&mut pixels, width, rect * scale, *color);
+            }
+            Primitive::Text {
+                pos, color, align, ..
+            } => {
+                let Some(galley) = galley else {
+                    continue;
+                };
+                let origin = align.anchor_size(*pos, galley.size()).min;
+                for row in &galley.rows {
+                    for glyph in &row.glyphs {
+                        let uv = glyph.uv_rect;
+                        if uv.is_nothing() {
+                            continue;
+                        }
+                        let left_top = (origin + glyph.pos.to_vec2() + uv.offset) * scale;
+                        for ty in uv.min[1]..uv.max[1] {
+                            for tx in uv.min[0]..uv.max[0] {
+                                let coverage =
+                                    atlas.pixels[ty as usize * atlas.size[0] + tx as usize];
+                                if coverage > 0.0 {
+                                    blend_pixel(
+                                        &mut pixels,
+                                        width,
+                                        left_top.x.round() as i32 + (tx - uv.min[0]) as i32,
+                                        left_top.y.round() as i32 + (ty - uv.min[1]) as i32,
+                                        *color,
+                                        coverage,
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    (width, height, pixels)
+}
@@ -0,0 +1,167 @@
+use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::utils::utils::get_site_from_host;
+use crate::views::components::gantt_aggregate_by::AggregateKey;
+use crate::views::components::time_buckets::Buckets;
+use eframe::egui::Color32;
+use std::collections::HashMap;
+
+/**
+ * Everything the buckets depend on, they are only computed again when it changes
+ */
+#[derive(Clone, Debug, PartialEq)]
+struct HeatStripKey {
+    generation: u64,         // Generation of the filtered jobs
+    nb_jobs: usize,          // Number of loaded jobs
+    group_key: AggregateKey, // First aggregation level
+    buckets: Buckets,        // Buckets covering the visible span
+    minute: i64,             // Running jobs grow with the time
+}
+
+/**
+ * Occupation of the resources of a group, for each bucket
+ */
+pub struct GroupUsage {
+    pub total: usize,     // Number of resources of the group
+    pub busy: Vec<usize>, // Number of resources used by a job, for each bucket
+}
+
+/*
+ * `GanttHeatStrip` computes, for every first level group of the Gantt chart, the
+ * number of its resources occupied by a job over time buckets.
+ * The buckets are aligned on multiples of their duration so that panning only
+ * computes them again when a new bucket becomes visible.
+ */
+#[derive(Default)]
+pub struct GanttHeatStrip {
+    pub enabled: bool,               // Show a heat strip under the first level headers
+    key: Option<HeatStripKey>,       // Key of the computed buckets
+    indexes: HashMap<String, usize>, // Index of the usage of each group by name
+    usages: Vec<GroupUsage>,         // Usage of each group
+}
+
+impl GanttHeatStrip {
+    pub const HEIGHT: f32 = 8.0; // Height of a strip
+    const BUCKET_WIDTH_POINTS: f32 = 6.0; // Approximate width of a bucket
+
+    /**
+     * Returns true if the groups of the key own a set of resources
+     */
+    pub fn supports(key: AggregateKey) -> bool {
+        matches!(
+            key,
+            AggregateKey::Site | AggregateKey::Cluster | AggregateKey::Host
+        )
+    }
+
+    /**
+     * Computes the buckets covering the visible span if they are outdated
+     */
+    pub fn update(
+        &mut self,
+        app: &ApplicationContext,
+        group_key: AggregateKey,
+        visible: (i64, i64),
+        width_points: f32,
+        now: i64,
+    ) {
+        let nb_buckets = (width_points / Self::BUCKET_WIDTH_POINTS).ceil() as i64;
+        let buckets = Buckets::new(visible, nb_buckets);
+        let nb_buckets = buckets.count;
+
+        let key = HeatStripKey {
+            generation: app.jobs_generation,
+            nb_jobs: app.all_jobs.len(),
+            group_key,
+            buckets,
+            minute: now / 60,
+        };
+        if self.key.as_ref() == Some(&key) {
+            return;
+        }
+
+        // Group owning each resource, as an index in the usages
+        let mut indexes: HashMap<String, usize> = HashMap::new();
+        let mut usages: Vec<GroupUsage> = Vec::new();
+        let mut owners: HashMap<u32, usize> = HashMap::new();
+        for cluster in &app.all_clusters {
+            for host in &cluster.hosts {
+                let name = match group_key {
+                    AggregateKey::Site => get_site_from_host(&host.name),
+                    AggregateKey::Cluster => cluster.name.clone(),
+                    _ => host.name.clone(),
+                };
+                let index = *indexes.entry(name).or_insert_with(|| {
+                    usages.push(GroupUsage {
+                        total: 0,
+                        busy: vec![0; nb_buckets],
+                    });
+                    usages.len() - 1
+                });
+                usages[index].total += host.resource_ids.len();
+                for resource_id in &host.resource_ids {
+                    owners.insert(*resource_id, index);
+                }
+            }
+        }
+
+        // Add the resources of each job to the buckets it overlaps
+        for job in app.all_jobs.iter().filter(|job| job.id != 0) {
+            let segments = job.get_segments(now);
+            let (Some(start), Some(end)) = (
+                segments.iter().map(|segment| segment.start).min(),
+                segments.iter().map(|segment| segment.end).max(),
+            ) else {
+                continue;
+            };
+            let Some((first, last)) = key.buckets.range((start, end)) else {
+                continue;
+            };
+
+            let mut counts: HashMap<usize, usize> = HashMap::new();
+            for resource_id in &job.assigned_resources {
+                if let Some(index) = owners.get(resource_id) {
+                    *counts.entry(*index).or_default() += 1;
+                }
+            }
+            for (index, count) in counts {
+                for busy in &mut usages[index].busy[first..=last] {
+                    *busy += count;
+                }
+            }
+        }
+
+        self.indexes = indexes;
+        self.usages = usages;
+        self.key = Some(key);
+    }
+
+    pub fn usage(&self, name: &str) -> Option<&GroupUsage> {
+        self.indexes.get(name).map(|index| &self.usages[*index])
+    }
+
+    /**
+     * Returns the start of the first bucket and the duration of a bucket
+     */
+    pub fn buckets(&self) -> (i64, i64) {
+        self.key
+            .as_ref()
+            .map_or((0, 1), |key| (key.buckets.first_s, key.buckets.bucket_s))
+    }
+}
+
+/**
+ * Returns the color of a bucket, from green when the group is idle to red when it is full
+ */
+pub fn heat_color(fraction: f32) -> Color32 {
+    let lerp = |a: u8, b: u8, t: f32| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    let (low, high, t) = if fraction < 0.5 {
+        ((60, 170, 90), (230, 200, 50), fraction * 2.0)
+    } else {
+        ((230, 200, 50), (220, 50, 50), (fraction - 0.5) * 2.0)
+    };
+    Color32::from_rgb(
+        lerp(low.0, high.0, t),
+        lerp(low.1, high.1, t),
+        lerp(low.2, high.2, t),
+    )
+}
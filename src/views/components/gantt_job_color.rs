@@ -1,46 +1,652 @@
-#[derive(PartialEq)]
+use crate::models::data_structure::job::{Job, JobState};
+use crate::models::utils::utils::compare_string_with_number;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+#[derive(PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize)]
 pub enum JobColorEnum {
     Random,
     State,
+    Owner,
+    Queue,
+    Cluster,
+    Project,
+    WalltimeOverrun,
+    ExitCode,
+}
+
+impl JobColorEnum {
+    pub fn get_label(&self) -> String {
+        match self {
+            JobColorEnum::Random => t!("app.gantt.settings.job_color_random").to_string(),
+            JobColorEnum::State => t!("app.gantt.settings.job_color_state").to_string(),
+            JobColorEnum::Owner => t!("app.gantt.settings.owner").to_string(),
+            JobColorEnum::Queue => t!("app.gantt.settings.queue").to_string(),
+            JobColorEnum::Cluster => t!("app.gantt.settings.cluster").to_string(),
+            JobColorEnum::Project => t!("app.gantt.settings.project").to_string(),
+            JobColorEnum::WalltimeOverrun => {
+                t!("app.gantt.settings.job_color_walltime").to_string()
+            }
+            JobColorEnum::ExitCode => t!("app.gantt.settings.job_color_exit_code").to_string(),
+        }
+    }
+}
+
+/**
+ * Palette used to pick the colors, the color-blind safe one is the Okabe-Ito palette
+ */
+#[derive(PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum ColorPalette {
+    Default,
+    ColorBlindSafe,
+}
+
+/**
+ * Named colors, their actual value depends on the palette
+ */
+#[derive(PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize)]
+pub enum NamedColor {
+    Blue,
+    Orange,
+    Green,
+    Red,
+    Purple,
+    SkyBlue,
+    Yellow,
+    Grey,
+}
+
+impl NamedColor {
+    pub fn get_label(&self) -> String {
+        match self {
+            NamedColor::Blue => t!("app.gantt.colors.blue").to_string(),
+            NamedColor::Orange => t!("app.gantt.colors.orange").to_string(),
+            NamedColor::Green => t!("app.gantt.colors.green").to_string(),
+            NamedColor::Red => t!("app.gantt.colors.red").to_string(),
+            NamedColor::Purple => t!("app.gantt.colors.purple").to_string(),
+            NamedColor::SkyBlue => t!("app.gantt.colors.sky_blue").to_string(),
+            NamedColor::Yellow => t!("app.gantt.colors.yellow").to_string(),
+            NamedColor::Grey => t!("app.gantt.colors.grey").to_string(),
+        }
+    }
+
+    pub fn get_color(&self, palette: ColorPalette) -> Color32 {
+        match palette {
+            ColorPalette::Default => match self {
+                NamedColor::Blue => Color32::from_rgb(31, 119, 180),
+                NamedColor::Orange => Color32::from_rgb(255, 127, 14),
+                NamedColor::Green => Color32::from_rgb(44, 160, 44),
+                NamedColor::Red => Color32::from_rgb(214, 39, 40),
+                NamedColor::Purple => Color32::from_rgb(148, 103, 189),
+                NamedColor::SkyBlue => Color32::from_rgb(107, 174, 214),
+                NamedColor::Yellow => Color32::from_rgb(237, 201, 72),
+                NamedColor::Grey => Color32::from_rgb(158, 158, 158),
+            },
+            ColorPalette::ColorBlindSafe => match self {
+                NamedColor::Blue => Color32::from_rgb(0, 114, 178),
+                NamedColor::Orange => Color32::from_rgb(230, 159, 0),
+                NamedColor::Green => Color32::from_rgb(0, 158, 115),
+                NamedColor::Red => Color32::from_rgb(213, 94, 0), // Vermillion
+                NamedColor::Purple => Color32::from_rgb(204, 121, 167), // Reddish purple
+                NamedColor::SkyBlue => Color32::from_rgb(86, 180, 233),
+                NamedColor::Yellow => Color32::from_rgb(240, 228, 66),
+                NamedColor::Grey => Color32::from_rgb(153, 153, 153),
+            },
+        }
+    }
+}
+
+/****************************************************************************************************************************/
+// RULES
+/****************************************************************************************************************************/
+
+#[derive(PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize)]
+pub enum RuleField {
+    Owner,
+    Queue,
+    Cluster,
+    Project,
+    State,
+}
+
+impl RuleField {
+    pub fn get_label(&self) -> String {
+        match self {
+            RuleField::Owner => t!("app.gantt.settings.owner").to_string(),
+            RuleField::Queue => t!("app.gantt.settings.queue").to_string(),
+            RuleField::Cluster => t!("app.gantt.settings.cluster").to_string(),
+            RuleField::Project => t!("app.gantt.settings.project").to_string(),
+            RuleField::State => t!("app.gantt.settings.state").to_string(),
+        }
+    }
+
+    /**
+     * Returns the values of the field for a job (a job can be on several clusters)
+     */
+    fn job_values(&self, job: &Job) -> Vec<String> {
+        match self {
+            RuleField::Owner => vec![job.owner.clone()],
+            RuleField::Queue => vec![job.queue.clone()],
+            RuleField::Cluster => job.clusters.clone(),
+            RuleField::Project => vec![job.project.clone()],
+            RuleField::State => vec![job.state.to_string(), job.state.get_label()],
+        }
+    }
 }
 
+#[derive(PartialEq, Clone, Copy, EnumIter, Serialize, Deserialize)]
+pub enum RuleOperator {
+    Equals,
+    NotEquals,
+    In,
+}
+
+impl RuleOperator {
+    pub fn get_symbol(&self) -> &'static str {
+        match self {
+            RuleOperator::Equals => "=",
+            RuleOperator::NotEquals => "≠",
+            RuleOperator::In => "in",
+        }
+    }
+}
+
+/**
+ * A user defined coloring rule, e.g. "queue = besteffort → grey" or "owner in [alice, bob] → blue"
+ */
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorRule {
+    pub field: RuleField,
+    pub operator: RuleOperator,
+    pub value: String, // Compared value, a comma separated list for the "in" operator
+    pub color: NamedColor,
+}
+
+impl Default for ColorRule {
+    fn default() -> Self {
+        Self {
+            field: RuleField::Queue,
+            operator: RuleOperator::Equals,
+            value: String::new(),
+            color: NamedColor::Grey,
+        }
+    }
+}
+
+impl ColorRule {
+    /**
+     * Returns true if the rule applies to the job, values are compared without case
+     * A rule without value is still being edited and never applies
+     */
+    pub fn matches(&self, job: &Job) -> bool {
+        if self.value.trim().is_empty() {
+            return false;
+        }
+        let job_values = self.field.job_values(job);
+        let is_equal = |value: &str| {
+            job_values
+                .iter()
+                .any(|job_value| job_value.eq_ignore_ascii_case(value.trim()))
+        };
+
+        match self.operator {
+            RuleOperator::Equals => is_equal(&self.value),
+            RuleOperator::NotEquals => !is_equal(&self.value),
+            RuleOperator::In => self
+                .value
+                .split(',')
+                .filter(|value| !value.trim().is_empty())
+                .any(is_equal),
+        }
+    }
+
+    pub fn get_label(&self) -> String {
+        let value = if self.operator == RuleOperator::In {
+            format!("[{}]", self.value.trim())
+        } else {
+            self.value.trim().to_string()
+        };
+        format!(
+            "{} {} {}",
+            self.field.get_label(),
+            self.operator.get_symbol(),
+            value
+        )
+    }
+}
+
+/****************************************************************************************************************************/
+// JOB COLOR
+/****************************************************************************************************************************/
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct JobColor {
     pub color: JobColorEnum,
+    pub palette: ColorPalette,
+    pub rules: Vec<ColorRule>, // Rules evaluated in order before the selected job color
+    #[serde(skip)]
+    categories: HashMap<String, usize>, // Index of the color of each value of the legend
+}
+
+/**
+ * The colors given to the values of the legend are not part of the settings
+ */
+impl PartialEq for JobColor {
+    fn eq(&self, other: &Self) -> bool {
+        self.color == other.color && self.palette == other.palette && self.rules == other.rules
+    }
 }
 
 impl Default for JobColor {
     fn default() -> Self {
         Self {
             color: JobColorEnum::Random, // Default value for the job color is Random
+            palette: ColorPalette::Default,
+            rules: Vec::new(),
+            categories: HashMap::new(),
+        }
+    }
+}
+
+/**
+ * Returns the FNV-1a hash of a value, which unlike the default hasher doesn't change between builds
+ */
+fn stable_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/**
+ * Returns a (hovered, normal) pair of colors, the hovered color being lighter
+ */
+fn with_hovered_color(color: Color32) -> (Color32, Color32) {
+    (color.lerp_to_gamma(Color32::WHITE, 0.35), color)
+}
+
+/**
+ * Categories of the walltime overrun coloring
+ */
+#[derive(PartialEq, Clone, Copy, EnumIter)]
+enum WalltimeStatus {
+    NotStarted,
+    Within,
+    Close, // More than 90% of the walltime used
+    Reached,
+}
+
+impl WalltimeStatus {
+    fn from_job(job: &Job) -> Self {
+        if job.start_time <= 0 {
+            return WalltimeStatus::NotStarted;
+        }
+        let end = if job.stop_time > 0 {
+            job.stop_time
+        } else {
+            chrono::Utc::now().timestamp()
+        };
+        let runtime = end - job.start_time;
+        if runtime >= job.walltime {
+            WalltimeStatus::Reached
+        } else if runtime * 10 >= job.walltime * 9 {
+            WalltimeStatus::Close
+        } else {
+            WalltimeStatus::Within
+        }
+    }
+
+    fn get_label(&self) -> String {
+        match self {
+            WalltimeStatus::NotStarted => t!("app.gantt.legend.not_started").to_string(),
+            WalltimeStatus::Within => t!("app.gantt.legend.within_walltime").to_string(),
+            WalltimeStatus::Close => t!("app.gantt.legend.close_to_walltime").to_string(),
+            WalltimeStatus::Reached => t!("app.gantt.legend.walltime_reached").to_string(),
+        }
+    }
+
+    fn get_color(&self) -> NamedColor {
+        match self {
+            WalltimeStatus::NotStarted => NamedColor::Grey,
+            WalltimeStatus::Within => NamedColor::Green,
+            WalltimeStatus::Close => NamedColor::Orange,
+            WalltimeStatus::Reached => NamedColor::Red,
+        }
+    }
+}
+
+/**
+ * Categories of the exit code coloring
+ */
+#[derive(PartialEq, Clone, Copy, EnumIter)]
+enum ExitStatus {
+    None,
+    Success,
+    Failure,
+}
+
+impl ExitStatus {
+    fn from_job(job: &Job) -> Self {
+        match job.exit_code {
+            None => ExitStatus::None,
+            Some(0) => ExitStatus::Success,
+            Some(_) => ExitStatus::Failure,
+        }
+    }
+
+    fn get_label(&self) -> String {
+        match self {
+            ExitStatus::None => t!("app.gantt.legend.no_exit_code").to_string(),
+            ExitStatus::Success => t!("app.gantt.legend.success").to_string(),
+            ExitStatus::Failure => t!("app.gantt.legend.failure").to_string(),
+        }
+    }
+
+    fn get_color(&self) -> NamedColor {
+        match self {
+            ExitStatus::None => NamedColor::Grey,
+            ExitStatus::Success => NamedColor::Green,
+            ExitStatus::Failure => NamedColor::Red,
         }
     }
 }
 
 impl JobColor {
+    /**
+     * Gives a color to each value (owner, queue, ...) of the jobs in the order of the legend,
+     * so that distinct values of the legend get distinct colors
+     */
+    pub fn update_categories(&mut self, jobs: &[Job]) {
+        self.categories = self
+            .category_values(jobs)
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| (value, index))
+            .collect();
+    }
 
     /**
-     * Returns true if the the selected job color is random
+     * Returns the color of a value (owner, queue, ...)
+     * The named colors are used first, then hues spread around the color wheel
+     * A value missing from the legend gets a color from a hash stable between builds
      */
-    pub fn is_random(&self) -> bool {
-        self.color == JobColorEnum::Random
+    fn category_color(&self, value: &str) -> Color32 {
+        const GENERATED_HUES: u64 = 64;
+        let colors: Vec<NamedColor> = NamedColor::iter().collect();
+        let index = match self.categories.get(value) {
+            Some(index) => *index,
+            None => colors.len() + (stable_hash(value) % GENERATED_HUES) as usize,
+        };
+        match colors.get(index) {
+            Some(color) => color.get_color(self.palette),
+            None => {
+                // Golden ratio steps keep the consecutive hues far from each other
+                let hue = ((index - colors.len()) as f32 * 0.618_034).fract();
+                Color32::from(egui::ecolor::Hsva::new(hue, 0.65, 0.85, 1.0))
+            }
+        }
+    }
+
+    /**
+     * Returns the distinct values of the categorical job color, in the order of the legend
+     */
+    fn category_values(&self, jobs: &[Job]) -> Vec<String> {
+        let mut values: Vec<String> = jobs
+            .iter()
+            .filter(|job| job.id != 0)
+            .filter_map(|job| self.category_value(job))
+            .collect();
+        values.sort_by(|a, b| compare_string_with_number(a, b));
+        values.dedup();
+        values
+    }
+
+    /**
+     * Returns the colors of a state (hovered, normal)
+     * The color-blind safe palette merges close states into the same color
+     */
+    fn state_colors(&self, state: &JobState) -> (Color32, Color32) {
+        if self.palette == ColorPalette::Default {
+            return state.get_color();
+        }
+        let color = match state {
+            JobState::Waiting | JobState::Finishing => NamedColor::SkyBlue,
+            JobState::Hold => NamedColor::Yellow,
+            JobState::ToLaunch | JobState::Launching => NamedColor::Orange,
+            JobState::Running => NamedColor::Green,
+            JobState::Terminated => NamedColor::Blue,
+            JobState::ToError | JobState::Error => NamedColor::Red,
+            JobState::ToAckReservation | JobState::Suspended | JobState::Resuming => {
+                NamedColor::Purple
+            }
+            JobState::Unknown => NamedColor::Grey,
+        };
+        with_hovered_color(color.get_color(self.palette))
+    }
+
+    /**
+     * Returns the value of the job used by the categorical job colors
+     */
+    fn category_value(&self, job: &Job) -> Option<String> {
+        match self.color {
+            JobColorEnum::Owner => Some(job.owner.clone()),
+            JobColorEnum::Queue => Some(job.queue.clone()),
+            JobColorEnum::Cluster => Some(job.clusters.first().cloned().unwrap_or_default()),
+            JobColorEnum::Project => Some(job.project.clone()),
+            _ => None,
+        }
+    }
+
+    /**
+     * Returns the colors of a job depending on the rules and the selected job color (hovered, normal)
+     */
+    pub fn get_job_colors(&self, job: &Job) -> (Color32, Color32) {
+        if let Some(rule) = self.rules.iter().find(|rule| rule.matches(job)) {
+            return with_hovered_color(rule.color.get_color(self.palette));
+        }
+
+        match self.color {
+            JobColorEnum::Random => job.get_gantt_color(),
+            JobColorEnum::State => self.state_colors(&job.state),
+            JobColorEnum::WalltimeOverrun => with_hovered_color(
+                WalltimeStatus::from_job(job)
+                    .get_color()
+                    .get_color(self.palette),
+            ),
+            JobColorEnum::ExitCode => with_hovered_color(
+                ExitStatus::from_job(job)
+                    .get_color()
+                    .get_color(self.palette),
+            ),
+            JobColorEnum::Owner
+            | JobColorEnum::Queue
+            | JobColorEnum::Cluster
+            | JobColorEnum::Project => with_hovered_color(
+                self.category_color(&self.category_value(job).unwrap_or_default()),
+            ),
+        }
+    }
+
+    /**
+     * Returns the legend entries (label, color) matching the rules and the selected job color
+     * Random colors have no meaning, so only the rules appear in the legend
+     */
+    pub fn legend(&self, jobs: &[Job]) -> Vec<(String, Color32)> {
+        let mut legend: Vec<(String, Color32)> = self
+            .rules
+            .iter()
+            .map(|rule| (rule.get_label(), rule.color.get_color(self.palette)))
+            .collect();
+
+        match self.color {
+            JobColorEnum::Random => {}
+            JobColorEnum::State => {
+                let mut states: Vec<JobState> = jobs.iter().map(|job| job.state.clone()).collect();
+                states.sort();
+                states.dedup();
+                legend.extend(
+                    states
+                        .iter()
+                        .map(|state| (state.get_label(), self.state_colors(state).1)),
+                );
+            }
+            JobColorEnum::WalltimeOverrun => legend.extend(WalltimeStatus::iter().map(|status| {
+                (
+                    status.get_label(),
+                    status.get_color().get_color(self.palette),
+                )
+            })),
+            JobColorEnum::ExitCode => legend.extend(ExitStatus::iter().map(|status| {
+                (
+                    status.get_label(),
+                    status.get_color().get_color(self.palette),
+                )
+            })),
+            JobColorEnum::Owner
+            | JobColorEnum::Queue
+            | JobColorEnum::Cluster
+            | JobColorEnum::Project => {
+                legend.extend(self.category_values(jobs).into_iter().map(|value| {
+                    let color = self.category_color(&value);
+                    (value, color)
+                }));
+            }
+        }
+
+        legend
+    }
+
+    /**
+     * Shows the legend as a wrapped list of colored squares and labels
+     */
+    pub fn legend_ui(&self, ui: &mut egui::Ui, jobs: &[Job]) {
+        const MAX_ENTRIES: usize = 30; // Avoid filling the screen with hundreds of owners
+
+        let legend = self.legend(jobs);
+        if legend.is_empty() {
+            return;
+        }
+        ui.horizontal_wrapped(|ui| {
+            for (label, color) in legend.iter().take(MAX_ENTRIES) {
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+                ui.painter().rect_filled(rect, 2.0, *color);
+                ui.label(label);
+                ui.add_space(8.0);
+            }
+            if legend.len() > MAX_ENTRIES {
+                ui.label(format!("(+{})", legend.len() - MAX_ENTRIES));
+            }
+        });
     }
 
     pub fn ui(&mut self, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            ui.label(format!("{}:", t!("app.gantt.settings.job_color")));
-            // The job color can be random or based on the job state
-            ui.horizontal(|ui| {
-                ui.radio_value(
-                    &mut self.color,
-                    JobColorEnum::Random,
-                    t!("app.gantt.settings.job_color_random"),
+        ui.label(format!("{}:", t!("app.gantt.settings.job_color")));
+        // The job color can be random, based on the job state, on a job field or on the job status
+        ui.horizontal_wrapped(|ui| {
+            for color in JobColorEnum::iter() {
+                ui.radio_value(&mut self.color, color, color.get_label());
+            }
+        });
+
+        let mut color_blind_safe = self.palette == ColorPalette::ColorBlindSafe;
+        if ui
+            .checkbox(&mut color_blind_safe, t!("app.gantt.settings.color_blind"))
+            .changed()
+        {
+            self.palette = if color_blind_safe {
+                ColorPalette::ColorBlindSafe
+            } else {
+                ColorPalette::Default
+            };
+        }
+
+        ui.collapsing(t!("app.gantt.settings.rules"), |ui| {
+            self.rules_ui(ui);
+        });
+    }
+
+    /**
+     * Edits the ordered list of rules, the first matching rule gives the color of a job
+     */
+    fn rules_ui(&mut self, ui: &mut egui::Ui) {
+        let mut move_up = None;
+        let mut remove = None;
+        let nb_rules = self.rules.len();
+        let palette = self.palette;
+
+        egui::Grid::new("job_color_rules_grid").show(ui, |ui| {
+            for (index, rule) in self.rules.iter_mut().enumerate() {
+                // Sub menus are used as combo boxes don't stay open inside a menu
+                ui.menu_button(rule.field.get_label(), |ui| {
+                    for field in RuleField::iter() {
+                        if ui
+                            .selectable_label(rule.field == field, field.get_label())
+                            .clicked()
+                        {
+                            rule.field = field;
+                            ui.close_menu();
+                        }
+                    }
+                });
+                ui.menu_button(rule.operator.get_symbol(), |ui| {
+                    for operator in RuleOperator::iter() {
+                        if ui
+                            .selectable_label(rule.operator == operator, operator.get_symbol())
+                            .clicked()
+                        {
+                            rule.operator = operator;
+                            ui.close_menu();
+                        }
+                    }
+                });
+                ui.add(
+                    egui::TextEdit::singleline(&mut rule.value)
+                        .hint_text(t!("app.gantt.settings.rule_value_hint"))
+                        .desired_width(120.0),
                 );
-                ui.radio_value(
-                    &mut self.color,
-                    JobColorEnum::State,
-                    t!("app.gantt.settings.job_color_state"),
+                ui.menu_button(
+                    egui::RichText::new(format!("⏹ {}", rule.color.get_label()))
+                        .color(rule.color.get_color(palette)),
+                    |ui| {
+                        for color in NamedColor::iter() {
+                            let label = egui::RichText::new(format!("⏹ {}", color.get_label()))
+                                .color(color.get_color(palette));
+                            if ui.selectable_label(rule.color == color, label).clicked() {
+                                rule.color = color;
+                                ui.close_menu();
+                            }
+                        }
+                    },
                 );
-            });
+
+                if ui.add_enabled(index > 0, egui::Button::new("⏶")).clicked() {
+                    move_up = Some(index);
+                }
+                if ui
+                    .add_enabled(index + 1 < nb_rules, egui::Button::new("⏷"))
+                    .clicked()
+                {
+                    move_up = Some(index + 1);
+                }
+                if ui.button("🗑").clicked() {
+                    remove = Some(index);
+                }
+                ui.end_row();
+            }
         });
+
+        if let Some(index) = move_up {
+            self.rules.swap(index - 1, index);
+        }
+        if let Some(index) = remove {
+            self.rules.remove(index);
+        }
+
+        if ui.button(t!("app.gantt.settings.add_rule")).clicked() {
+            self.rules.push(ColorRule::default());
+        }
     }
 }
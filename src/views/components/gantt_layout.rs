@@ -0,0 +1,318 @@
+use crate::models::data_structure::{job::Job, resource::ResourceState};
+use crate::views::components::gantt_aggregate_by::AggregateKey;
+use crate::views::components::gantt_heat_strip::GanttHeatStrip;
+use crate::views::main_page::gantt::JobGroup;
+use std::collections::BTreeMap;
+
+/**
+ * Sizes used to place the rows, the layout is rebuilt when one of them changes
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayoutMetrics {
+    pub font_size: f32,   // Size of the header text
+    pub line_height: f32, // Height of a line of jobs, spacing included
+    pub rect_height: f32, // Height of a job when the resources are not squashed
+    pub squash: bool,     // All the jobs of a group share one line
+    pub heat_strip: bool, // A heat strip is shown under the first level headers
+}
+
+/**
+ * Everything the layout depends on, compared each frame to know if it must be rebuilt
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutKey {
+    pub generation: u64,         // Generation of the filtered jobs
+    pub keys: Vec<AggregateKey>, // Aggregation levels
+    pub metrics: LayoutMetrics,  // Sizes of the rows
+}
+
+/**
+ * Jobs of a row sorted by start, with the running maximum of their ends,
+ * so that the jobs overlapping a time span are found with a binary search
+ * The start and end are those of the segments painted for each job
+ */
+pub struct JobIntervalIndex {
+    jobs: Vec<Job>,         // Jobs sorted by start
+    spans: Vec<(i64, i64)>, // Start and end of each job
+    max_ends: Vec<i64>,     // Latest end among the jobs up to each index
+}
+
+impl JobIntervalIndex {
+    pub fn new(mut jobs: Vec<Job>) -> Self {
+        jobs.sort_by_cached_key(|job| Self::job_span(job).0);
+        let spans: Vec<(i64, i64)> = jobs.iter().map(Self::job_span).collect();
+        let mut max_end = i64::MIN;
+        let max_ends = spans
+            .iter()
+            .map(|(_, end)| {
+                max_end = max_end.max(*end);
+                max_end
+            })
+            .collect();
+        Self {
+            jobs,
+            spans,
+            max_ends,
+        }
+    }
+
+    /**
+     * Returns the start of the first segment of a job and the end of its last one
+     * The elapsed part of a running job follows the clock, so its end is left open
+     */
+    fn job_span(job: &Job) -> (i64, i64) {
+        let segments = job.get_segments(job.start_time);
+        let start = segments
+            .first()
+            .map_or(job.scheduled_start, |segment| segment.start);
+        let end = if job.start_time > 0 && job.stop_time <= 0 {
+            i64::MAX
+        } else {
+            segments.last().map_or(start, |segment| segment.end)
+        };
+        (start, end)
+    }
+
+    /**
+     * Returns the jobs overlapping the time span
+     */
+    pub fn overlapping(&self, start_s: i64, end_s: i64) -> impl Iterator<Item = &Job> {
+        // Jobs before `first` all end before the span, jobs from `last` all start after it
+        let first = self.max_ends.partition_point(|max_end| *max_end < start_s);
+        let last = self.spans.partition_point(|(start, _)| *start <= end_s);
+        let range = first..last.max(first);
+        self.jobs[range.clone()]
+            .iter()
+            .zip(&self.spans[range])
+            .filter(move |(_, (_, end))| *end >= start_s)
+            .map(|(job, _)| job)
+    }
+
+    pub fn all(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    pub fn contains(&self, job_id: u32) -> bool {
+        self.jobs.iter().any(|job| job.id == job_id)
+    }
+}
+
+/**
+ * A row of the Gantt chart, the positions are relative to the top of the canvas
+ */
+pub enum LayoutRow {
+    Header {
+        path: Vec<String>, // Names of the group and of its parents
+        line_y: f32,       // Position of the separation line above the header
+        indent: f32,       // Horizontal offset of the header text
+        text_y: f32,       // Position of the header text
+        bottom: f32,       // End of the header, where its content starts
+    },
+    HeatStrip {
+        name: String, // Name of the first level group
+        top_y: f32,   // Position of the strip
+    },
+    Jobs {
+        top_y: f32,              // Position of the jobs
+        state: ResourceState,    // State of the group, used to hatch dead and absent resources
+        aggregation_height: f32, // Height of the group header, covered by squashed jobs
+        jobs: JobIntervalIndex,  // Jobs painted on the row
+    },
+}
+
+impl LayoutRow {
+    /**
+     * Returns the vertical extent of the row
+     */
+    pub fn bounds(&self, metrics: &LayoutMetrics) -> (f32, f32) {
+        match self {
+            LayoutRow::Header { line_y, bottom, .. } => (*line_y, *bottom),
+            LayoutRow::HeatStrip { top_y, .. } => (*top_y, top_y + GanttHeatStrip::HEIGHT),
+            LayoutRow::Jobs {
+                top_y,
+                aggregation_height,
+                ..
+            } => {
+                if metrics.squash {
+                    (top_y - aggregation_height, top_y + metrics.line_height)
+                } else {
+                    (*top_y, top_y + metrics.rect_height)
+                }
+            }
+        }
+    }
+}
+
+/*
+ * `GanttLayout` is the flat list of the rows of the Gantt chart, computed once
+ * from the grouped jobs and reused every frame until the jobs, the aggregation
+ * levels, the sizes or the collapsed groups change.
+ * The rows are sorted from top to bottom so that the painter only walks the rows
+ * intersecting the visible part of the scroll area.
+ */
+pub struct GanttLayout {
+    pub key: LayoutKey,
+    pub rows: Vec<LayoutRow>,
+    pub height: f32, // Total height of the rows
+}
+
+impl GanttLayout {
+    /**
+     * Builds the rows of the groups, `start_y` being the space left for the timeline
+     */
+    pub fn build(
+        key: LayoutKey,
+        groups: Vec<JobGroup>,
+        collapsed_groups: &mut BTreeMap<Vec<String>, bool>,
+        start_y: f32,
+    ) -> Self {
+        let mut layout = Self {
+            key,
+            rows: Vec::new(),
+            height: 0.0,
+        };
+        layout.height = layout.add_groups(groups, &mut Vec::new(), start_y, collapsed_groups);
+        layout
+    }
+
+    /**
+     * Adds the rows of the groups of one level, then recursively of their sub groups or their jobs
+     * Mirrors the spacing of the headers and of the jobs of each level
+     */
+    fn add_groups(
+        &mut self,
+        groups: Vec<JobGroup>,
+        path: &mut Vec<String>,
+        mut cursor_y: f32,
+        collapsed_groups: &mut BTreeMap<Vec<String>, bool>,
+    ) -> f32 {
+        let metrics = self.key.metrics;
+        let level = path.len() + 1;
+
+        // The first level has a fixed offset before its header
+        let offset_before_header = if level == 1 {
+            10.0
+        } else {
+            metrics.font_size + 5.0
+        };
+        let indent = 20.0 * (level - 1) as f32;
+
+        for group in groups {
+            // Last level groups leave more space under their header for the jobs
+            let spacing_after_header = if group.children.is_empty() {
+                metrics.font_size + 5.0
+            } else {
+                metrics.font_size
+            };
+
+            let line_y = cursor_y;
+            cursor_y += offset_before_header;
+            let text_y = cursor_y;
+            cursor_y += spacing_after_header;
+
+            path.push(group.name);
+            let is_collapsed = *collapsed_groups.entry(path.clone()).or_insert(false);
+            self.rows.push(LayoutRow::Header {
+                path: path.clone(),
+                line_y,
+                indent,
+                text_y,
+                bottom: cursor_y,
+            });
+
+            // The occupation of the first level groups is shown under their header
+            if level == 1 && metrics.heat_strip {
+                self.rows.push(LayoutRow::HeatStrip {
+                    name: path[0].clone(),
+                    top_y: cursor_y,
+                });
+                cursor_y += GanttHeatStrip::HEIGHT + 2.0;
+            }
+
+            if !is_collapsed && !group.children.is_empty() {
+                cursor_y = self.add_groups(group.children, path, cursor_y, collapsed_groups);
+            } else if !is_collapsed && !group.jobs.is_empty() {
+                let aggregation_height = offset_before_header + spacing_after_header;
+                if metrics.squash {
+                    // All the jobs share the line under the header
+                    self.rows.push(LayoutRow::Jobs {
+                        top_y: cursor_y,
+                        state: group.state,
+                        aggregation_height,
+                        jobs: JobIntervalIndex::new(group.jobs),
+                    });
+                    cursor_y += metrics.line_height;
+                } else {
+                    for job in group.jobs {
+                        self.rows.push(LayoutRow::Jobs {
+                            top_y: cursor_y,
+                            state: group.state,
+                            aggregation_height,
+                            jobs: JobIntervalIndex::new(vec![job]),
+                        });
+                        cursor_y += metrics.line_height;
+                    }
+                }
+            }
+            path.pop();
+
+            if !metrics.squash {
+                cursor_y += spacing_after_header;
+            }
+        }
+
+        cursor_y
+    }
+
+    /**
+     * Returns the rows intersecting the vertical range
+     */
+    pub fn visible_rows(&self, top: f32, bottom: f32) -> impl Iterator<Item = &LayoutRow> {
+        let metrics = self.key.metrics;
+        let first = self
+            .rows
+            .partition_point(|row| row.bounds(&metrics).1 < top);
+        self.rows[first..]
+            .iter()
+            .take_while(move |row| row.bounds(&metrics).0 <= bottom)
+    }
+
+    /**
+     * Returns the position of the first row showing a job
+     */
+    pub fn job_top(&self, job_id: u32) -> Option<f32> {
+        self.rows.iter().find_map(|row| match row {
+            LayoutRow::Jobs { top_y, jobs, .. } if jobs.contains(job_id) => Some(*top_y),
+            _ => None,
+        })
+    }
+
+    /**
+     * Returns the vertical extents of the groups named after one of the names,
+     * from their header to the next header of the same or an upper level
+     */
+    pub fn group_extents(&self, names: &[String]) -> Vec<(f32, f32)> {
+        let mut extents = Vec::new();
+        for (index, row) in self.rows.iter().enumerate() {
+            let LayoutRow::Header { path, line_y, .. } = row else {
+                continue;
+            };
+            if !path.last().is_some_and(|name| names.contains(name)) {
+                continue;
+            }
+            let end = self.rows[index + 1..]
+                .iter()
+                .find_map(|next| match next {
+                    LayoutRow::Header {
+                        path: next_path,
+                        line_y,
+                        ..
+                    } if next_path.len() <= path.len() => Some(*line_y),
+                    _ => None,
+                })
+                .unwrap_or(self.height);
+            extents.push((*line_y, end));
+        }
+        extents
+    }
+}
@@ -0,0 +1,229 @@
+use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::data_structure::job::Job;
+use eframe::egui;
+use egui::{pos2, vec2, Color32, CursorIcon, Rect, Sense, Stroke};
+
+/**
+ * Part of the viewport being dragged
+ */
+#[derive(Clone, Copy, PartialEq)]
+enum MinimapDrag {
+    Move,  // The whole viewport is moved
+    Left,  // The start of the viewport is moved
+    Right, // The end of the viewport is moved
+}
+
+/**
+ * GanttMinimap is a thin strip showing the job density over the whole loaded range,
+ * with a draggable rectangle for the part of the range visible in the Gantt chart
+ */
+#[derive(Default)]
+pub struct GanttMinimap {
+    drag: Option<MinimapDrag>,
+}
+
+impl GanttMinimap {
+    const HEIGHT: f32 = 36.0; // Height of the strip
+    const HANDLE_WIDTH: f32 = 5.0; // Width of the area used to resize the viewport
+    const MIN_VIEWPORT_S: f64 = 60.0; // The viewport can't be smaller than one minute
+
+    /**
+     * Shows the strip, returns true if the user moved or resized the viewport
+     * The view uses the same values as the Gantt options: the canvas width in seconds
+     * and the sideways pan in points, relative to `origin_s` (the start of the canvas)
+     */
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        app: &ApplicationContext,
+        origin_s: i64,
+        canvas_width_points: f32,
+        canvas_width_s: &mut f32,
+        sideways_pan_in_points: &mut f32,
+    ) -> bool {
+        let (rect, response) = ui.allocate_exact_size(
+            vec2(ui.available_width(), Self::HEIGHT),
+            Sense::click_and_drag(),
+        );
+        if canvas_width_points <= 0.0 || *canvas_width_s <= 0.0 {
+            return false; // The Gantt canvas has not been painted yet
+        }
+
+        // Range visible in the Gantt chart
+        let visible_start_s = origin_s as f64
+            + (-*sideways_pan_in_points / canvas_width_points * *canvas_width_s) as f64;
+        let visible_end_s = visible_start_s + *canvas_width_s as f64;
+
+        // The full range covers the loaded periods, their jobs and the visible range
+        // The cached jobs are used, the filtered ones being limited to the visible window
+        let job_range = |job: &Job| {
+            let stop_time = if job.stop_time > 0 {
+                job.stop_time
+            } else {
+                job.scheduled_start + job.walltime
+            };
+            (job.scheduled_start as f64, stop_time as f64)
+        };
+        let jobs: Vec<(f64, f64)> = app
+            .all_jobs
+            .iter()
+            .filter(|job| job.id != 0 && job.scheduled_start > 0)
+            .filter(|job| app.matches_filters(job, (None, None)))
+            .map(job_range)
+            .collect();
+        let (loaded_start_s, loaded_end_s) = app
+            .job_cache
+            .loaded_range()
+            .map_or((visible_start_s, visible_end_s), |(start, end)| {
+                (start as f64, end as f64)
+            });
+        let range_start_s = jobs
+            .iter()
+            .map(|(start, _)| *start)
+            .fold(visible_start_s.min(loaded_start_s), f64::min);
+        let range_end_s = jobs
+            .iter()
+            .map(|(_, end)| *end)
+            .fold(visible_end_s.max(loaded_end_s), f64::max);
+        let range_s = (range_end_s - range_start_s).max(1.0);
+
+        let x_from_s = |s: f64| rect.left() + rect.width() * ((s - range_start_s) / range_s) as f32;
+        let s_from_x =
+            |x: f32| range_start_s + (x - rect.left()) as f64 / rect.width() as f64 * range_s;
+
+        // Interactions with the viewport
+        let viewport = Rect::from_x_y_ranges(
+            x_from_s(visible_start_s)..=x_from_s(visible_end_s),
+            rect.y_range(),
+        );
+        let drag_at = |x: f32| {
+            if (x - viewport.left()).abs() <= Self::HANDLE_WIDTH {
+                Some(MinimapDrag::Left)
+            } else if (x - viewport.right()).abs() <= Self::HANDLE_WIDTH {
+                Some(MinimapDrag::Right)
+            } else if viewport.x_range().contains(x) {
+                Some(MinimapDrag::Move)
+            } else {
+                None
+            }
+        };
+
+        if let Some(pos) = response.hover_pos() {
+            match drag_at(pos.x) {
+                Some(MinimapDrag::Left) | Some(MinimapDrag::Right) => {
+                    ui.ctx().set_cursor_icon(CursorIcon::ResizeHorizontal)
+                }
+                Some(MinimapDrag::Move) => ui.ctx().set_cursor_icon(CursorIcon::Grab),
+                None => {}
+            }
+        }
+
+        let (mut new_start_s, mut new_end_s) = (visible_start_s, visible_end_s);
+        let mut changed = false;
+
+        if response.drag_started() {
+            self.drag = response
+                .interact_pointer_pos()
+                .and_then(|pos| drag_at(pos.x));
+        }
+        if response.dragged() {
+            let delta_s = response.drag_delta().x as f64 / rect.width() as f64 * range_s;
+            match self.drag {
+                Some(MinimapDrag::Move) => {
+                    new_start_s += delta_s;
+                    new_end_s += delta_s;
+                }
+                Some(MinimapDrag::Left) => {
+                    new_start_s = (new_start_s + delta_s).min(new_end_s - Self::MIN_VIEWPORT_S);
+                }
+                Some(MinimapDrag::Right) => {
+                    new_end_s = (new_end_s + delta_s).max(new_start_s + Self::MIN_VIEWPORT_S);
+                }
+                None => {}
+            }
+            changed = self.drag.is_some() && delta_s != 0.0;
+        }
+        if response.drag_stopped() {
+            self.drag = None;
+        }
+
+        // A click outside of the viewport centers it on the clicked time
+        if response.clicked() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                if drag_at(pos.x).is_none() {
+                    let half_width_s = (new_end_s - new_start_s) / 2.0;
+                    new_start_s = s_from_x(pos.x) - half_width_s;
+                    new_end_s = s_from_x(pos.x) + half_width_s;
+                    changed = true;
+                }
+            }
+        }
+
+        if changed {
+            *canvas_width_s = (new_end_s - new_start_s) as f32;
+            *sideways_pan_in_points =
+                -canvas_width_points * (new_start_s - origin_s as f64) as f32 / *canvas_width_s;
+        }
+
+        // Paint the strip
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        painter.rect_filled(rect, 2.0, visuals.extreme_bg_color);
+
+        // Job density, computed with a difference array on the buckets
+        let nb_buckets = (rect.width() / 2.0).max(1.0) as usize;
+        let bucket_from_s = |s: f64| {
+            (((s - range_start_s) / range_s * nb_buckets as f64).floor() as i64)
+                .clamp(0, nb_buckets as i64 - 1) as usize
+        };
+        let mut deltas = vec![0i64; nb_buckets + 1];
+        for (start, end) in &jobs {
+            deltas[bucket_from_s(*start)] += 1;
+            deltas[bucket_from_s(*end) + 1] -= 1;
+        }
+        let mut counts = Vec::with_capacity(nb_buckets);
+        let mut running = 0;
+        for delta in deltas.iter().take(nb_buckets) {
+            running += delta;
+            counts.push(running);
+        }
+        let max_count = counts.iter().copied().max().unwrap_or(0).max(1) as f32;
+        let bucket_width = rect.width() / nb_buckets as f32;
+        let density_color = visuals.selection.bg_fill.gamma_multiply(0.8);
+        for (index, count) in counts.iter().enumerate().filter(|(_, c)| **c > 0) {
+            let height = (rect.height() - 4.0) * *count as f32 / max_count;
+            let x = rect.left() + index as f32 * bucket_width;
+            painter.rect_filled(
+                Rect::from_min_max(
+                    pos2(x, rect.bottom() - height),
+                    pos2(x + bucket_width, rect.bottom()),
+                ),
+                0.0,
+                density_color,
+            );
+        }
+
+        // Current time
+        let now_x = x_from_s(chrono::Utc::now().timestamp() as f64);
+        if rect.x_range().contains(now_x) {
+            painter.line_segment(
+                [pos2(now_x, rect.top()), pos2(now_x, rect.bottom())],
+                Stroke::new(1.5, Color32::RED),
+            );
+        }
+
+        // Viewport
+        let viewport = Rect::from_x_y_ranges(
+            x_from_s(new_start_s)..=x_from_s(new_end_s).max(x_from_s(new_start_s) + 2.0),
+            rect.y_range(),
+        );
+        painter.rect(
+            viewport,
+            2.0,
+            visuals.text_color().gamma_multiply(0.12),
+            Stroke::new(1.5, visuals.strong_text_color()),
+        );
+
+        changed
+    }
+}
@@ -0,0 +1,161 @@
+use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::data_structure::job::Job;
+use eframe::egui;
+use egui::Rect;
+
+/**
+ * Job focused by the search, highlighted in the Gantt chart
+ */
+pub struct FocusedJob {
+    pub job_id: u32,
+    pub since: f64,           // Time of the focus, used to pulse the highlight
+    pub scroll_pending: bool, // True until the canvas has been scrolled to the job
+    pub rect: Option<Rect>,   // Rectangle of the job painted during the last frame
+}
+
+impl FocusedJob {
+    pub const PULSE_DURATION: f64 = 3.0;
+
+    pub fn new(job_id: u32, since: f64) -> Self {
+        Self {
+            job_id,
+            since,
+            scroll_pending: true,
+            rect: None,
+        }
+    }
+}
+
+/**
+ * GanttSearch finds jobs by id, owner or command and cycles through the matches
+ */
+#[derive(Default)]
+pub struct GanttSearch {
+    query: String,
+    matches: Vec<(u32, String)>, // Id and description of the matching jobs
+    current: Option<usize>,      // Index of the selected match
+}
+
+impl GanttSearch {
+    const MAX_LISTED: usize = 100; // Maximum number of matches in the list
+
+    /**
+     * Returns true if the job matches the query (id prefix, owner or command fragment)
+     */
+    fn job_matches(job: &Job, query: &str) -> bool {
+        job.id.to_string().starts_with(query)
+            || job.owner.to_lowercase().contains(query)
+            || job.command.to_lowercase().contains(query)
+    }
+
+    /**
+     * Finds the matching jobs among all the loaded ones, the filtered jobs being limited
+     * to the visible window, so that a job outside of the view can be found
+     */
+    fn update_matches(&mut self, app: &ApplicationContext) {
+        let query = self.query.trim().to_lowercase();
+        let previous = self
+            .current
+            .and_then(|index| self.matches.get(index).map(|m| m.0));
+
+        self.matches.clear();
+        if !query.is_empty() {
+            for job in app.all_jobs.iter().filter(|job| job.id != 0) {
+                if Self::job_matches(job, &query) && app.matches_filters(job, (None, None)) {
+                    self.matches.push((
+                        job.id,
+                        format!("{} - {} - {}", job.id, job.owner, job.command),
+                    ));
+                }
+            }
+        }
+
+        // Keep the selected match if it still matches
+        self.current = previous.and_then(|id| self.matches.iter().position(|m| m.0 == id));
+    }
+
+    /**
+     * Shows the search box, returns the id of the job to focus if the user chose a match
+     */
+    pub fn ui(&mut self, ui: &mut egui::Ui, app: &ApplicationContext) -> Option<u32> {
+        let mut selected = None;
+
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut self.query)
+                .hint_text(t!("app.gantt.search.hint"))
+                .desired_width(180.0),
+        );
+        self.update_matches(app);
+
+        if self.query.trim().is_empty() {
+            return None;
+        }
+
+        // Enter selects the next match
+        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            selected = self.select_next(1);
+        }
+
+        let nb_matches = self.matches.len();
+        if ui
+            .add_enabled(nb_matches > 0, egui::Button::new("⏴"))
+            .on_hover_text(t!("app.gantt.search.previous"))
+            .clicked()
+        {
+            selected = self.select_next(-1);
+        }
+        if ui
+            .add_enabled(nb_matches > 0, egui::Button::new("⏵"))
+            .on_hover_text(t!("app.gantt.search.next"))
+            .clicked()
+        {
+            selected = self.select_next(1);
+        }
+
+        // List of the matches
+        let title = match self.current {
+            Some(index) => format!("{}/{}", index + 1, nb_matches),
+            None => t!("app.gantt.search.matches", count = nb_matches).to_string(),
+        };
+        ui.menu_button(title, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for (index, (job_id, description)) in
+                        self.matches.iter().enumerate().take(Self::MAX_LISTED)
+                    {
+                        if ui
+                            .selectable_label(self.current == Some(index), description)
+                            .clicked()
+                        {
+                            self.current = Some(index);
+                            selected = Some(*job_id);
+                            ui.close_menu();
+                        }
+                    }
+                    if nb_matches > Self::MAX_LISTED {
+                        ui.label(format!("(+{})", nb_matches - Self::MAX_LISTED));
+                    }
+                });
+        });
+
+        selected
+    }
+
+    /**
+     * Moves the selection forward or backward, cycling through the matches
+     */
+    fn select_next(&mut self, step: isize) -> Option<u32> {
+        if self.matches.is_empty() {
+            return None;
+        }
+        let nb_matches = self.matches.len() as isize;
+        let index = match self.current {
+            Some(index) => (index as isize + step).rem_euclid(nb_matches) as usize,
+            None if step < 0 => self.matches.len() - 1,
+            None => 0,
+        };
+        self.current = Some(index);
+        Some(self.matches[index].0)
+    }
+}
@@ -0,0 +1,261 @@
+use crate::models::data_structure::job::{Job, JobState};
+use crate::models::utils::date_converter::{format_duration, format_timestamp};
+use crate::views::components::gantt_export::GanttExport;
+use chrono::Local;
+use eframe::egui;
+use egui::{Pos2, Rect, Response, RichText, Stroke};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/**
+ * Actions available on the selected jobs
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SelectionAction {
+    OpenDetails, // Open the details window of every selected job
+    Export,      // Write the selected jobs to a CSV file
+    Filter,      // Only keep the selected jobs in the application
+    Clear,       // Empty the selection
+}
+
+/**
+ * GanttSelection holds the jobs selected with shift-click or with a rubber band,
+ * and shows their aggregate statistics in a side panel
+ */
+#[derive(Default)]
+pub struct GanttSelection {
+    pub job_ids: BTreeSet<u32>,    // Selected jobs
+    pending_toggle: Option<u32>,   // Job shift-clicked during this frame
+    band_start: Option<Pos2>,      // Start of the rubber band being dragged
+    status: Option<(String, f64)>, // Status message of the last export and the time it was set
+}
+
+impl GanttSelection {
+    const MAX_OWNERS: usize = 10; // Owners listed in the panel
+    const STATUS_DURATION_S: f64 = 5.0;
+
+    pub fn is_selected(&self, job_id: u32) -> bool {
+        self.job_ids.contains(&job_id)
+    }
+
+    /**
+     * Asks to toggle a job, a job painted on several rows is only toggled once per frame
+     */
+    pub fn request_toggle(&mut self, job_id: u32) {
+        if self.pending_toggle.is_none() {
+            self.pending_toggle = Some(job_id);
+        }
+    }
+
+    /**
+     * Updates the rubber band with the canvas response, then applies the pending toggle
+     * The band is started by a shift-drag, every job painted inside it is added on release
+     */
+    pub fn update(
+        &mut self,
+        response: &Response,
+        painter: &egui::Painter,
+        shift: bool,
+        painted_jobs: &[(u32, Rect)],
+    ) {
+        if let Some(job_id) = self.pending_toggle.take() {
+            if !self.job_ids.remove(&job_id) {
+                self.job_ids.insert(job_id);
+            }
+        }
+
+        if response.drag_started() && shift {
+            self.band_start = response.interact_pointer_pos();
+        }
+        let Some(band_start) = self.band_start else {
+            return;
+        };
+        let Some(pointer) = response.interact_pointer_pos().or(response.hover_pos()) else {
+            return;
+        };
+
+        let band = Rect::from_two_pos(band_start, pointer);
+        let selection = painter.ctx().style().visuals.selection;
+        painter.rect(
+            band,
+            0.0,
+            selection.bg_fill.gamma_multiply(0.25),
+            Stroke::new(1.0, selection.stroke.color),
+        );
+
+        if response.drag_stopped() {
+            for (job_id, rect) in painted_jobs {
+                if *job_id != 0 && band.intersects(*rect) {
+                    self.job_ids.insert(*job_id);
+                }
+            }
+            self.band_start = None;
+        }
+    }
+
+    /**
+     * Returns true while a rubber band is dragged, the canvas must not be panned
+     */
+    pub fn is_dragging_band(&self) -> bool {
+        self.band_start.is_some()
+    }
+
+    /**
+     * Shows the statistics of the selected jobs and the actions, returns the chosen action
+     */
+    pub fn panel_ui(&mut self, ui: &mut egui::Ui, jobs: &[Job]) -> Option<SelectionAction> {
+        let mut action = None;
+        let now = chrono::Utc::now().timestamp();
+        let selected: Vec<&Job> = jobs
+            .iter()
+            .filter(|job| job.id != 0 && self.is_selected(job.id))
+            .collect();
+
+        ui.heading(t!("app.gantt.selection.title"));
+        ui.label(t!("app.gantt.selection.count", count = selected.len()));
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            // Total core-hours and time span
+            let core_hours: f64 = selected.iter().map(|job| job.get_core_hours(now)).sum();
+            ui.label(RichText::new(t!("app.gantt.selection.core_hours")).strong());
+            ui.label(format!("{:.1}", core_hours));
+
+            let segments = selected.iter().flat_map(|job| job.get_segments(now));
+            let (start, end) = segments.fold((i64::MAX, i64::MIN), |(start, end), segment| {
+                (start.min(segment.start), end.max(segment.end))
+            });
+            ui.label(RichText::new(t!("app.gantt.selection.time_span")).strong());
+            if start <= end {
+                ui.label(format!(
+                    "{}\n{}\n({})",
+                    format_timestamp(start),
+                    format_timestamp(end),
+                    format_duration(end - start)
+                ));
+            } else {
+                ui.label("-");
+            }
+            ui.separator();
+
+            // Owners, the most frequent first
+            let mut owners: BTreeMap<&str, usize> = BTreeMap::new();
+            for job in &selected {
+                *owners.entry(job.owner.as_str()).or_default() += 1;
+            }
+            let mut owners: Vec<(&str, usize)> = owners.into_iter().collect();
+            owners.sort_by_key(|owner| std::cmp::Reverse(owner.1));
+            ui.label(
+                RichText::new(t!("app.gantt.selection.owners", count = owners.len())).strong(),
+            );
+            for (owner, count) in owners.iter().take(Self::MAX_OWNERS) {
+                ui.label(format!("{} ({})", owner, count));
+            }
+            if owners.len() > Self::MAX_OWNERS {
+                ui.label(format!("(+{})", owners.len() - Self::MAX_OWNERS));
+            }
+            ui.separator();
+
+            // State breakdown
+            let mut states: BTreeMap<JobState, usize> = BTreeMap::new();
+            for job in &selected {
+                *states.entry(job.state.clone()).or_default() += 1;
+            }
+            ui.label(RichText::new(t!("app.gantt.selection.states")).strong());
+            for (state, count) in states {
+                ui.horizontal(|ui| {
+                    ui.colored_label(state.get_color().1, "⏺");
+                    ui.label(format!("{} ({})", state.get_label(), count));
+                });
+            }
+            ui.separator();
+
+            // Actions
+            for (label, selection_action) in [
+                (
+                    t!("app.gantt.selection.open_details"),
+                    SelectionAction::OpenDetails,
+                ),
+                (t!("app.gantt.selection.export"), SelectionAction::Export),
+                (t!("app.gantt.selection.filter"), SelectionAction::Filter),
+                (t!("app.gantt.selection.clear"), SelectionAction::Clear),
+            ] {
+                if ui.button(label).clicked() {
+                    action = Some(selection_action);
+                }
+            }
+
+            if let Some((message, timestamp)) = &self.status {
+                if ui.input(|i| i.time) - timestamp < Self::STATUS_DURATION_S {
+                    ui.label(message);
+                } else {
+                    self.status = None; // Clear the message after timeout
+                }
+            }
+        });
+
+        action
+    }
+
+    /**
+     * Writes the selected jobs to a CSV file in the export folder
+     */
+    pub fn export(&mut self, ctx: &egui::Context, jobs: &[Job]) {
+        let selected: Vec<&Job> = jobs
+            .iter()
+            .filter(|job| job.id != 0 && self.is_selected(job.id))
+            .collect();
+        let message = match write_selection_csv(&selected) {
+            Ok(path) => t!("app.gantt.selection.export_success", path = path.display()).to_string(),
+            Err(error) => t!("app.gantt.selection.export_error", error = error).to_string(),
+        };
+        self.status = Some((message, ctx.input(|i| i.time)));
+    }
+}
+
+/**
+ * Quotes a CSV field when it contains a separator, a quote or a line break
+ */
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn write_selection_csv(jobs: &[&Job]) -> Result<PathBuf, String> {
+    let folder = Path::new(GanttExport::EXPORT_FOLDER);
+    if !folder.exists() {
+        std::fs::create_dir_all(folder).map_err(|e| e.to_string())?;
+    }
+    let path = folder.join(format!(
+        "selection_{}.csv",
+        Local::now().format("%Y%m%d_%H%M%S")
+    ));
+
+    let now = chrono::Utc::now().timestamp();
+    let mut csv = String::from(
+        "id,owner,state,queue,project,submission_time,start_time,stop_time,walltime,resources,core_hours,command\n",
+    );
+    for job in jobs {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{:.3},{}\n",
+            job.id,
+            csv_field(&job.owner),
+            job.state,
+            csv_field(&job.queue),
+            csv_field(&job.project),
+            job.submission_time,
+            job.start_time,
+            job.stop_time,
+            job.walltime,
+            job.assigned_resources.len(),
+            job.get_core_hours(now),
+            csv_field(&job.command),
+        ));
+    }
+    std::fs::write(&path, csv).map_err(|e| e.to_string())?;
+
+    Ok(path)
+}
@@ -0,0 +1,41 @@
+use crate::models::utils::json_file::{load_json, save_json};
+use crate::views::components::gantt_aggregate_by::AggregateBy;
+use crate::views::components::gantt_job_color::JobColor;
+use serde::{Deserialize, Serialize};
+
+/*
+ * `GanttViewState` is the part of the Gantt chart settings kept between two sessions:
+ * the aggregation levels, the coloring, the collapsed groups and the visible time window.
+ * It is stored in a JSON file of the working directory, next to the options.
+ */
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)] // Missing fields keep their default value
+pub struct GanttViewState {
+    pub aggregate_by: AggregateBy,
+    pub job_color: JobColor,
+    pub squash_resources: bool,
+    pub see_all_res: bool,
+    pub heat_strip: bool,
+    pub collapsed_groups: Vec<Vec<String>>, // Paths of the collapsed groups
+    pub window: Option<(i64, i64)>,         // Visible time window, in seconds
+}
+
+impl GanttViewState {
+    pub const FILE_PATH: &'static str = "gantt_view.json";
+
+    /**
+     * Loads the view state from a file, an absent or invalid file gives the default view
+     * Invalid aggregation levels give the default levels
+     */
+    pub fn load_from_file(file_path: &str) -> Self {
+        let mut state: Self = load_json(file_path).unwrap_or_default();
+        if !state.aggregate_by.is_valid() {
+            state.aggregate_by = AggregateBy::default();
+        }
+        state
+    }
+
+    pub fn save_to_file(&self, file_path: &str) -> Result<(), String> {
+        save_json(self, file_path)
+    }
+}
@@ -1,4 +1,16 @@
+pub mod annotation_editor;
+pub mod comparison_picker;
 pub mod dashboard_components;
 pub mod gantt_aggregate_by;
+pub mod gantt_export;
+pub mod gantt_heat_strip;
 pub mod gantt_job_color;
+pub mod gantt_layout;
+pub mod gantt_minimap;
+pub mod gantt_search;
+pub mod gantt_selection;
+pub mod gantt_view_state;
 pub mod job_details;
+pub mod named_profiles;
+pub mod time_buckets;
+pub mod time_range_picker;
@@ -0,0 +1,81 @@
+use crate::models::utils::json_file::{load_json, save_json};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/**
+ * A profile saved under a name, e.g. a layout of the dashboard
+ */
+pub trait NamedProfile: Clone + Serialize + DeserializeOwned {
+    fn set_name(&mut self, name: String);
+
+    /**
+     * Completes a profile saved by a previous version, nothing to do by default
+     */
+    fn complete(&mut self) {}
+}
+
+/*
+ * `NamedProfiles` is a list of named profiles and the one used, stored in a JSON file
+ * of the working directory. There is always at least one profile: an absent or empty
+ * file gives the default profiles and the last profile can't be removed.
+ */
+#[derive(Serialize, Deserialize)]
+pub struct NamedProfiles<T> {
+    pub selected: usize, // Index of the profile used
+    #[serde(alias = "layouts")] // Name of the list in the files of the dashboard layouts
+    pub profiles: Vec<T>,
+}
+
+impl<T: NamedProfile> NamedProfiles<T>
+where
+    Self: Default,
+{
+    /**
+     * Loads the profiles from a file, an absent or invalid file gives the default profiles
+     */
+    pub fn load_from_file(file_path: &str) -> Self {
+        let mut profiles = load_json::<Self>(file_path)
+            .filter(|profiles| !profiles.profiles.is_empty())
+            .unwrap_or_default();
+        for profile in profiles.profiles.iter_mut() {
+            profile.complete();
+        }
+        profiles
+    }
+
+    pub fn save_to_file(&self, file_path: &str) -> Result<(), String> {
+        save_json(self, file_path)
+    }
+}
+
+impl<T: NamedProfile> NamedProfiles<T> {
+    pub fn current(&self) -> &T {
+        &self.profiles[self.selected.min(self.profiles.len() - 1)]
+    }
+
+    pub fn current_mut(&mut self) -> &mut T {
+        let index = self.selected.min(self.profiles.len() - 1);
+        &mut self.profiles[index]
+    }
+
+    /**
+     * Adds a copy of the current profile under a new name and selects it
+     */
+    pub fn add_copy(&mut self, name: String) {
+        let mut profile = self.current().clone();
+        profile.set_name(name);
+        self.profiles.push(profile);
+        self.selected = self.profiles.len() - 1;
+    }
+
+    /**
+     * Removes the current profile, the last profile is never removed
+     */
+    pub fn remove_current(&mut self) {
+        if self.profiles.len() > 1 {
+            self.profiles
+                .remove(self.selected.min(self.profiles.len() - 1));
+            self.selected = self.selected.saturating_sub(1);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+/*
+ * `Buckets` divides a time range into buckets of the same duration, used to follow
+ * a quantity over time. The buckets are aligned on multiples of their duration so that
+ * panning only changes them when a new bucket becomes visible.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Buckets {
+    pub first_s: i64,  // Start of the first bucket
+    pub bucket_s: i64, // Duration of a bucket
+    pub count: usize,  // Number of buckets covering the range
+}
+
+impl Buckets {
+    /**
+     * Returns about `nb_buckets` buckets covering the range
+     */
+    pub fn new((start_s, end_s): (i64, i64), nb_buckets: i64) -> Self {
+        let bucket_s = ((end_s - start_s) / nb_buckets.max(1)).max(1);
+        let first_s = start_s.div_euclid(bucket_s) * bucket_s;
+        Buckets {
+            first_s,
+            bucket_s,
+            count: ((end_s - first_s) / bucket_s + 1) as usize,
+        }
+    }
+
+    /**
+     * Returns the first and last buckets overlapped by an interval
+     */
+    pub fn range(&self, (start_s, end_s): (i64, i64)) -> Option<(usize, usize)> {
+        let last_end_s = self.first_s + self.count as i64 * self.bucket_s;
+        if end_s <= self.first_s || start_s >= last_end_s {
+            return None;
+        }
+        let first = (start_s.max(self.first_s) - self.first_s) / self.bucket_s;
+        let last = (end_s.min(last_end_s) - 1 - self.first_s) / self.bucket_s;
+        Some((first as usize, last as usize))
+    }
+
+    pub fn start(&self, index: usize) -> i64 {
+        self.first_s + index as i64 * self.bucket_s
+    }
+
+    /**
+     * Returns the bucket containing a time, None outside of the buckets
+     */
+    pub fn index_at(&self, time: f64) -> Option<usize> {
+        let index = ((time - self.first_s as f64) / self.bucket_s as f64).floor();
+        (index >= 0.0 && (index as usize) < self.count).then_some(index as usize)
+    }
+}
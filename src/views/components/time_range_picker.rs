@@ -0,0 +1,156 @@
+use crate::models::utils::date_converter::{format_timestamp_as, parse_timestamp};
+use chrono::{DateTime, Duration, Local, TimeZone};
+use eframe::egui;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/**
+ * Preset ranges of the time range picker
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+pub enum TimeRangePreset {
+    Last24Hours,
+    Last7Days,
+    Next12Hours,
+    Custom,
+}
+
+impl TimeRangePreset {
+    pub fn get_label(&self) -> String {
+        match self {
+            TimeRangePreset::Last24Hours => t!("app.menu.time_range.last_24_hours").to_string(),
+            TimeRangePreset::Last7Days => t!("app.menu.time_range.last_7_days").to_string(),
+            TimeRangePreset::Next12Hours => t!("app.menu.time_range.next_12_hours").to_string(),
+            TimeRangePreset::Custom => t!("app.menu.time_range.custom").to_string(),
+        }
+    }
+
+    /**
+     * Returns the range of the preset relative to now, None for the custom range
+     */
+    pub fn get_range(&self, now: DateTime<Local>) -> Option<(DateTime<Local>, DateTime<Local>)> {
+        match self {
+            TimeRangePreset::Last24Hours => Some((now - Duration::hours(24), now)),
+            TimeRangePreset::Last7Days => Some((now - Duration::days(7), now)),
+            TimeRangePreset::Next12Hours => Some((now, now + Duration::hours(12))),
+            TimeRangePreset::Custom => None,
+        }
+    }
+}
+
+/**
+ * TimeRangePicker chooses an absolute time range, from a preset or typed dates
+ */
+#[derive(Default)]
+pub struct TimeRangePicker {
+    selected: Option<TimeRangePreset>, // Last chosen preset, None until the user picks one
+    custom_start: String,              // Start of the custom range, as typed by the user
+    custom_end: String,                // End of the custom range, as typed by the user
+    error: Option<String>,             // Error of the custom range
+}
+
+impl TimeRangePicker {
+    const DATE_FORMAT: &'static str = "%Y-%m-%d %H:%M";
+    pub const MAX_RANGE_DAYS: i64 = 31; // Longest range that can be displayed
+
+    /**
+     * Shows the picker, returns the range chosen by the user
+     * The custom fields are filled with the current range the first time they are shown
+     */
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        current: (DateTime<Local>, DateTime<Local>),
+    ) -> Option<(DateTime<Local>, DateTime<Local>)> {
+        let mut chosen = None;
+        let title = match self.selected {
+            Some(preset) => preset.get_label(),
+            None => t!("app.menu.time_range.button").to_string(),
+        };
+
+        ui.menu_button(format!("🗓 {}", title), |ui| {
+            for preset in TimeRangePreset::iter() {
+                if ui
+                    .selectable_label(self.selected == Some(preset), preset.get_label())
+                    .clicked()
+                {
+                    if let Some(range) = preset.get_range(Local::now()) {
+                        chosen = Some(range);
+                        self.selected = Some(preset);
+                        ui.close_menu();
+                    } else {
+                        self.selected = Some(preset);
+                    }
+                }
+            }
+
+            if self.selected == Some(TimeRangePreset::Custom) {
+                ui.separator();
+                if self.custom_start.is_empty() && self.custom_end.is_empty() {
+                    self.custom_start =
+                        format_timestamp_as(current.0.timestamp(), Self::DATE_FORMAT)
+                            .unwrap_or_default();
+                    self.custom_end = format_timestamp_as(current.1.timestamp(), Self::DATE_FORMAT)
+                        .unwrap_or_default();
+                }
+
+                egui::Grid::new("time_range_custom_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(t!("app.menu.time_range.start"));
+                        ui.text_edit_singleline(&mut self.custom_start);
+                        ui.end_row();
+                        ui.label(t!("app.menu.time_range.end"));
+                        ui.text_edit_singleline(&mut self.custom_end);
+                        ui.end_row();
+                    });
+                ui.label(
+                    egui::RichText::new(t!(
+                        "app.menu.time_range.format",
+                        format = "YYYY-MM-DD HH:MM"
+                    ))
+                    .weak(),
+                );
+
+                if ui.button(t!("app.menu.time_range.apply")).clicked() {
+                    match self.parse_custom_range() {
+                        Ok(range) => {
+                            chosen = Some(range);
+                            self.error = None;
+                            ui.close_menu();
+                        }
+                        Err(error) => self.error = Some(error),
+                    }
+                }
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            }
+        });
+
+        chosen
+    }
+
+    /**
+     * Parses the custom range, the end must be after the start and the range not too long
+     */
+    fn parse_custom_range(&self) -> Result<(DateTime<Local>, DateTime<Local>), String> {
+        let parse = |text: &str| {
+            parse_timestamp(text, Self::DATE_FORMAT)
+                .and_then(|ts| Local.timestamp_opt(ts, 0).single())
+                .ok_or_else(|| t!("app.menu.time_range.invalid_date", date = text).to_string())
+        };
+        let start = parse(&self.custom_start)?;
+        let end = parse(&self.custom_end)?;
+
+        if end <= start {
+            return Err(t!("app.menu.time_range.end_before_start").to_string());
+        }
+        if end - start > Duration::days(Self::MAX_RANGE_DAYS) {
+            return Err(
+                t!("app.menu.time_range.too_long", days = Self::MAX_RANGE_DAYS).to_string(),
+            );
+        }
+        Ok((start, end))
+    }
+}
@@ -0,0 +1,296 @@
+use crate::models::data_structure::application_context::ApplicationContext;
+use crate::models::utils::date_converter::format_timestamp;
+use crate::views::components::dashboard_components::usage_report::{
+    sort_rows, UsageColumn, UsageKey, UsageReport, UsageRow,
+};
+use crate::views::components::time_range_picker::TimeRangePicker;
+use crate::views::view::View;
+use chrono::{DateTime, Local};
+use eframe::egui::{self, RichText, Vec2};
+use egui_extras::{Column, TableBuilder};
+use strum::IntoEnumIterator;
+
+/*
+ * `Accounting` shows the core-hours, and the GPU-hours when the clusters have GPUs,
+ * used per owner, queue or project during a chosen period. The jobs of the period are
+ * fetched like the ones of the displayed window and the filters of the jobs apply.
+ */
+pub struct Accounting {
+    key: UsageKey, // Attribute the usage is accounted by
+    // Accounted period, the displayed window until another one is chosen
+    period: Option<(DateTime<Local>, DateTime<Local>)>,
+    range_picker: TimeRangePicker,
+    sort: (UsageColumn, bool), // Column the rows are sorted by and true if ascending
+    top_n: usize,              // Number of bars of the chart
+    status: Option<(String, f64)>, // Status message of the last export and the time it was set
+}
+
+impl Default for Accounting {
+    fn default() -> Self {
+        Accounting {
+            key: UsageKey::Owner,
+            period: None,
+            range_picker: TimeRangePicker::default(),
+            sort: (UsageColumn::CoreHours, false),
+            top_n: 10,
+            status: None,
+        }
+    }
+}
+
+impl View for Accounting {
+    fn render(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
+        let (start, end) = *self
+            .period
+            .get_or_insert_with(|| (app.get_start_date(), app.get_end_date()));
+        let period = (start.timestamp(), end.timestamp());
+        app.accounting_period = Some(period);
+
+        egui::CentralPanel::default().show(ui.ctx(), |ui| {
+            ui.heading(
+                RichText::new(t!("app.accounting.title"))
+                    .strong()
+                    .size(20.0),
+            );
+            self.period_bar_ui(ui, app, (start, end));
+
+            let now = chrono::Utc::now().timestamp();
+            let jobs = app
+                .all_jobs
+                .iter()
+                .filter(|job| app.matches_filters(job, (Some(period.0), Some(period.1))));
+            let mut report = UsageReport::compute(
+                jobs,
+                self.key,
+                period,
+                &app.all_clusters,
+                &app.resource_index,
+                now,
+            );
+            sort_rows(&mut report.rows, self.sort.0, self.sort.1);
+
+            self.actions_ui(ui, &report, period);
+            ui.separator();
+            summary_ui(ui, &report);
+            ui.add_space(10.0);
+
+            if report.rows.is_empty() {
+                ui.label(t!("app.accounting.empty"));
+                return;
+            }
+            ui.horizontal_top(|ui| {
+                let table_width = ui.available_width() * 0.55;
+                ui.allocate_ui(Vec2::new(table_width, ui.available_height()), |ui| {
+                    self.table_ui(ui, &report);
+                });
+                ui.separator();
+                let size = Vec2::new(ui.available_width(), 350.0);
+                report
+                    .top_chart(self.key, self.top_n)
+                    .ui_with_chart(ui, size);
+            });
+        });
+    }
+}
+
+impl Accounting {
+    const STATUS_DURATION_S: f64 = 5.0; // Time the status message of the export is shown
+
+    /**
+     * Shows the accounted period and the choice of a new one
+     */
+    fn period_bar_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        app: &ApplicationContext,
+        (start, end): (DateTime<Local>, DateTime<Local>),
+    ) {
+        ui.horizontal(|ui| {
+            ui.label(t!(
+                "app.accounting.period",
+                start = format_timestamp(start.timestamp()),
+                end = format_timestamp(end.timestamp())
+            ));
+            if let Some(range) = self.range_picker.ui(ui, (start, end)) {
+                self.period = Some(range);
+            }
+            if ui.button(t!("app.accounting.use_window")).clicked() {
+                self.period = Some((app.get_start_date(), app.get_end_date()));
+            }
+            let now = chrono::Utc::now().timestamp();
+            if !app
+                .job_cache
+                .missing_periods(start.timestamp(), end.timestamp(), now)
+                .is_empty()
+            {
+                ui.add(egui::Spinner::new());
+                ui.label(t!("app.accounting.loading"));
+            }
+        });
+    }
+
+    /**
+     * Shows the choice of the key, the size of the chart and the export
+     */
+    fn actions_ui(&mut self, ui: &mut egui::Ui, report: &UsageReport, period: (i64, i64)) {
+        ui.horizontal(|ui| {
+            ui.label(t!("app.accounting.group_by"));
+            for key in UsageKey::iter() {
+                ui.selectable_value(&mut self.key, key, key.get_label());
+            }
+            ui.separator();
+
+            ui.label(t!("app.accounting.top_n"));
+            ui.add(egui::DragValue::new(&mut self.top_n).range(1..=50));
+            ui.separator();
+
+            if ui
+                .add_enabled(
+                    !report.rows.is_empty(),
+                    egui::Button::new(t!("app.accounting.export")),
+                )
+                .clicked()
+            {
+                let message = match report.write_csv(self.key, period) {
+                    Ok(path) => {
+                        t!("app.accounting.export_success", path = path.display()).to_string()
+                    }
+                    Err(error) => t!("app.accounting.export_error", error = error).to_string(),
+                };
+                self.status = Some((message, ui.input(|i| i.time)));
+            }
+            if let Some((message, timestamp)) = &self.status {
+                if ui.input(|i| i.time) - timestamp < Self::STATUS_DURATION_S {
+                    ui.label(message);
+                } else {
+                    self.status = None; // Clear the message after timeout
+                }
+            }
+        });
+    }
+
+    /**
+     * Shows the rows of the report, the headers sort the rows
+     */
+    fn table_ui(&mut self, ui: &mut egui::Ui, report: &UsageReport) {
+        let mut columns = vec![
+            (UsageColumn::Name, self.key.get_label()),
+            (UsageColumn::Jobs, t!("app.accounting.jobs").to_string()),
+            (
+                UsageColumn::CoreHours,
+                t!("app.accounting.core_hours").to_string(),
+            ),
+        ];
+        if report.has_gpus() {
+            columns.push((
+                UsageColumn::GpuHours,
+                t!("app.accounting.gpu_hours").to_string(),
+            ));
+        }
+
+        let mut table = TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .max_scroll_height(500.0)
+            .column(Column::remainder().at_least(100.0));
+        for _ in 1..=columns.len() {
+            table = table.column(Column::auto().at_least(70.0));
+        }
+
+        table
+            .header(20.0, |mut header| {
+                for (column, label) in &columns {
+                    header.col(|ui| {
+                        let text = if *column == self.sort.0 {
+                            format!("{} {}", label, if self.sort.1 { '⬆' } else { '⬇' })
+                        } else {
+                            label.clone()
+                        };
+                        if ui
+                            .add(egui::Button::new(RichText::new(text).strong()).frame(false))
+                            .clicked()
+                        {
+                            // The numbers are sorted from the largest first
+                            self.sort = if self.sort.0 == *column {
+                                (*column, !self.sort.1)
+                            } else {
+                                (*column, *column == UsageColumn::Name)
+                            };
+                        }
+                    });
+                }
+                header.col(|ui| {
+                    ui.label(RichText::new(t!("app.accounting.capacity")).strong());
+                });
+            })
+            .body(|mut body| {
+                let rows = report.rows.iter().map(|row| (row, false));
+                for (row, is_total) in rows.chain(std::iter::once((&report.total, true))) {
+                    body.row(18.0, |mut table_row| {
+                        row_ui(&mut table_row, report, row, is_total);
+                    });
+                }
+            });
+    }
+}
+
+/**
+ * Adds the cells of a row of the report
+ */
+fn row_ui(
+    table_row: &mut egui_extras::TableRow,
+    report: &UsageReport,
+    row: &UsageRow,
+    is_total: bool,
+) {
+    let text = |value: String| {
+        if is_total {
+            RichText::new(value).strong()
+        } else {
+            RichText::new(value)
+        }
+    };
+    table_row.col(|ui| {
+        ui.label(text(row.name.clone()));
+    });
+    table_row.col(|ui| {
+        ui.label(text(row.jobs.to_string()));
+    });
+    table_row.col(|ui| {
+        ui.label(text(format!("{:.1}", row.core_hours)));
+    });
+    if report.has_gpus() {
+        table_row.col(|ui| {
+            ui.label(text(format!("{:.1}", row.gpu_hours)));
+        });
+    }
+    table_row.col(|ui| {
+        ui.label(text(format!("{:.1} %", report.capacity_percent(row))));
+    });
+}
+
+/**
+ * Shows the usage of the period against the capacity of the clusters
+ */
+fn summary_ui(ui: &mut egui::Ui, report: &UsageReport) {
+    ui.horizontal(|ui| {
+        ui.label(t!(
+            "app.accounting.core_summary",
+            used = format!("{:.1}", report.total.core_hours),
+            capacity = format!("{:.1}", report.core_capacity),
+            percent = format!("{:.1}", report.capacity_percent(&report.total))
+        ));
+        if report.has_gpus() {
+            ui.separator();
+            ui.label(t!(
+                "app.accounting.gpu_summary",
+                used = format!("{:.1}", report.total.gpu_hours),
+                capacity = format!("{:.1}", report.gpu_capacity),
+                percent = format!(
+                    "{:.1}",
+                    report.total.gpu_hours / report.gpu_capacity * 100.0
+                )
+            ));
+        }
+    });
+}
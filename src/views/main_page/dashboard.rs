@@ -1,7 +1,11 @@
-use crate::models::data_structure::job::JobState;
+use crate::views::components::dashboard_components::annotation_list::show_annotation_list;
+use crate::views::components::dashboard_components::cluster_health::show_cluster_health;
+use crate::views::components::dashboard_components::comparison_panel::show_comparison_panel;
+use crate::views::components::dashboard_components::dashboard_layout::{
+    DashboardLayouts, DashboardWidget, NamedLayout, WidgetKind,
+};
+use crate::views::components::dashboard_components::dashboard_widgets::show_widget;
 use crate::views::components::dashboard_components::job_table::JobTable;
-use crate::views::components::dashboard_components::metric_box::MetricBox;
-use crate::views::components::dashboard_components::metric_chart::create_jobstate_chart;
 use crate::views::components::dashboard_components::metric_grid::MetricGrid;
 use crate::{models::data_structure::application_context::ApplicationContext, views::view::View};
 use eframe::egui::{self, RichText};
@@ -10,7 +14,10 @@ use strum::IntoEnumIterator;
 pub struct Dashboard {
     job_table: JobTable,
     metric_grid: MetricGrid,
-    show_chart: bool,
+    layouts: DashboardLayouts, // Layouts of the widgets, saved in a file
+    editing: bool,             // Shows the buttons to edit the current layout
+    new_layout_name: String,   // Name of the next layout created
+    error: Option<String>,     // Error of the last save
 }
 
 impl Default for Dashboard {
@@ -18,7 +25,10 @@ impl Default for Dashboard {
         Dashboard {
             job_table: JobTable::default(),
             metric_grid: MetricGrid::default(),
-            show_chart: false,
+            layouts: DashboardLayouts::load_from_file(DashboardLayouts::FILE_PATH),
+            editing: false,
+            new_layout_name: String::new(),
+            error: None,
         }
     }
 }
@@ -28,66 +38,36 @@ impl View for Dashboard {
         egui::CentralPanel::default().show(ui.ctx(), |ui| {
             ui.heading(RichText::new(t!("app.dashboard.title")).strong().size(20.0));
 
-            // Button to toggle between the job chart and the job state metrics
-            if ui
-                .button(if self.show_chart {
-                    t!("app.dashboard.show_metrics")
-                } else {
-                    t!("app.dashboard.show_charts")
-                })
-                .clicked()
-            {
-                self.show_chart = !self.show_chart;
+            let mut changed = self.layout_bar_ui(ui);
+
+            let layout = self.layouts.current_mut();
+            changed |= self
+                .metric_grid
+                .show(ui, layout, self.editing, |ui, widget, size| {
+                    show_widget(ui, widget.kind, app, size)
+                });
+
+            if changed {
+                self.error = self
+                    .layouts
+                    .save_to_file(DashboardLayouts::FILE_PATH)
+                    .err()
+                    .map(|error| t!("app.dashboard.layout.save_error", error = error).to_string());
             }
 
-            self.metric_grid.show(ui, |grid| {
-                if self.show_chart {
-                    // Add the job state chart
-                    let chart = create_jobstate_chart(app.filtered_jobs.clone());
-                    grid.add_chart(chart);
-                } else {
-                    // Add total jobs metric
-                    grid.add_metric(MetricBox::new(
-                        t!("app.dashboard.total_jobs").to_string(),
-                        app.filtered_jobs.len().to_string(),
-                        egui::Color32::from_rgb(128, 128, 128),
-                    ));
-
-                    // Add the job state metrics
-                    for state in JobState::iter() {
-                        let count = app
-                            .filtered_jobs
-                            .iter()
-                            .filter(|j| j.state == state)
-                            .count();
-
-                        if count > 0 {
-                            let translation_key =
-                                format!("app.job_state.{}", state.to_string().to_lowercase());
-                            grid.add_metric(MetricBox::new(
-                                t!(&translation_key).to_string(),
-                                count.to_string(),
-                                state.get_color().1,
-                            ));
-                        }
-                    }
-                    let start_time = app.get_start_date();
-                    let end_time = app.get_end_date();
-
-                    // Add time range metrics
-                    grid.add_metric(MetricBox::new(
-                        t!("app.dashboard.start_time").to_string(),
-                        start_time.format("%Y-%m-%d %H:%M").to_string(),
-                        egui::Color32::from_rgb(70, 130, 180),
-                    ));
-
-                    grid.add_metric(MetricBox::new(
-                        t!("app.dashboard.end_time").to_string(),
-                        end_time.format("%Y-%m-%d %H:%M").to_string(),
-                        egui::Color32::from_rgb(70, 130, 180),
-                    ));
-                }
-            });
+            ui.add_space(10.0);
+
+            // Ongoing and upcoming maintenance windows and notes
+            show_annotation_list(ui, &app.annotations);
+
+            // Alive, dead, absent and suspected hosts and resources of each cluster
+            show_cluster_health(ui, app);
+
+            // Statistics of the current window next to the compared one
+            if let Some(offset_s) = app.comparison_offset_s {
+                show_comparison_panel(ui, app, offset_s);
+                ui.add_space(10.0);
+            }
 
             ui.add_space(10.0);
             ui.separator();
@@ -97,3 +77,83 @@ impl View for Dashboard {
         });
     }
 }
+
+impl Dashboard {
+    /**
+     * Shows the choice of the layout and, when editing, the actions on the layouts
+     * Returns true if the layouts were changed
+     */
+    fn layout_bar_ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label(t!("app.dashboard.layout.title"));
+            let selected = self.layouts.selected;
+            egui::ComboBox::from_id_salt("dashboard_layout")
+                .selected_text(self.layouts.current().name.clone())
+                .show_ui(ui, |ui| {
+                    for (index, layout) in self.layouts.profiles.iter().enumerate() {
+                        ui.selectable_value(&mut self.layouts.selected, index, &layout.name);
+                    }
+                });
+            changed |= self.layouts.selected != selected;
+
+            ui.toggle_value(&mut self.editing, t!("app.dashboard.layout.edit"));
+            if !self.editing {
+                return;
+            }
+            ui.separator();
+
+            // Catalogue of the widgets
+            ui.menu_button(t!("app.dashboard.layout.add_widget"), |ui| {
+                for kind in WidgetKind::iter() {
+                    if ui.button(kind.get_label()).clicked() {
+                        let layout = self.layouts.current_mut();
+                        layout.widgets.push(DashboardWidget::new(kind));
+                        changed = true;
+                        ui.close_menu();
+                    }
+                }
+            });
+
+            ui.label(t!("app.dashboard.layout.columns"));
+            let layout = self.layouts.current_mut();
+            changed |= ui
+                .add(egui::DragValue::new(&mut layout.columns).range(1..=NamedLayout::MAX_COLUMNS))
+                .changed();
+            ui.separator();
+
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_layout_name)
+                    .hint_text(t!("app.dashboard.layout.name_hint"))
+                    .desired_width(120.0),
+            );
+            let name = self.new_layout_name.trim().to_string();
+            if ui
+                .add_enabled(
+                    !name.is_empty(),
+                    egui::Button::new(t!("app.dashboard.layout.save_as")),
+                )
+                .clicked()
+            {
+                self.layouts.add_copy(name);
+                self.new_layout_name.clear();
+                changed = true;
+            }
+            if ui
+                .add_enabled(
+                    self.layouts.profiles.len() > 1,
+                    egui::Button::new(t!("app.dashboard.layout.delete")),
+                )
+                .clicked()
+            {
+                self.layouts.remove_current();
+                changed = true;
+            }
+        });
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        ui.add_space(5.0);
+        changed
+    }
+}
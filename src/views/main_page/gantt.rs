@@ -1,30 +1,36 @@
 use crate::models::data_structure::cluster::Cluster;
 use crate::models::data_structure::resource::ResourceState;
-use crate::models::utils::date_converter::format_timestamp;
-use crate::models::utils::utils::cluster_contain_host;
+use crate::models::utils::date_converter::{format_duration, format_timestamp, format_timestamp_as};
 use crate::models::utils::utils::compare_string_with_number;
-use crate::models::utils::utils::contains_cluster;
-use crate::models::utils::utils::contains_host;
 use crate::models::utils::utils::get_all_clusters;
 use crate::models::utils::utils::get_all_hosts;
 use crate::models::utils::utils::get_all_resources;
-use crate::models::utils::utils::get_cluster_from_name;
 use crate::models::utils::utils::get_cluster_state_from_name;
 use crate::models::utils::utils::get_host_state_from_name;
 use crate::models::utils::utils::get_tree_structure_for_job;
 use crate::views::view::View;
 use crate::{
     models::data_structure::{
+        annotation::Annotations,
         application_context::ApplicationContext,
-        job::{Job, JobState},
+        job::{Job, JobSegment, JobSegmentKind, JobState},
     },
     views::components::{
-        gantt_aggregate_by::{AggregateBy, AggregateByLevel1Enum, AggregateByLevel2Enum},
+        annotation_editor::describe_period,
+        gantt_aggregate_by::{AggregateBy, AggregateKey},
+        gantt_export::{ExportBar, ExportRow, GanttExport, GanttExportData},
+        gantt_heat_strip::{heat_color, GanttHeatStrip},
         gantt_job_color::JobColor,
+        gantt_layout::{GanttLayout, LayoutKey, LayoutMetrics, LayoutRow},
+        gantt_minimap::GanttMinimap,
+        gantt_search::{FocusedJob, GanttSearch},
+        gantt_selection::{GanttSelection, SelectionAction},
+        gantt_view_state::GanttViewState,
         job_details::JobDetailsWindow,
+        time_range_picker::TimeRangePicker,
     },
 };
-use chrono::{DateTime, Local, TimeZone};
+use chrono::{Local, TimeZone};
 use eframe::egui;
 use egui::{
     lerp, pos2, remap_clamp, Align2, Color32, FontId, Frame, PointerButton, Pos2, Rect, Response,
@@ -39,18 +45,22 @@ pub struct GanttChart {
     options: Options,                           // options for the GanttChart
     job_details_windows: Vec<JobDetailsWindow>, // job details windows
 
-    // Tracks which top-level categories (e.g., owners, hosts, or clusters) are collapsed in the Gantt view
-    // Key: The category name (e.g., "user1" for owner, "host1" for host)
+    // Tracks which groups are collapsed in the Gantt view, at any aggregation level
+    // Key: Path of the group from the first level - e.g., ["cluster1", "host1"]
     // Value: true if collapsed (hidden), false if expanded (visible)
-    collapsed_jobs_level_1: BTreeMap<String, bool>,
-
-    // Tracks which second-level subcategories are collapsed within their parent categories
-    // Key: Tuple of (parent_category, subcategory) - e.g., ("cluster1", "host1")
-    // Value: true if collapsed (hidden), false if expanded (visible)
-    collapsed_jobs_level_2: BTreeMap<(String, String), bool>,
+    collapsed_groups: BTreeMap<Vec<String>, bool>,
 
     initial_start_s: Option<i64>, // Initial start timestamp
     initial_end_s: Option<i64>, // Initial end timestamp
+    export: GanttExport,        // SVG and PNG export of the current view
+    minimap: GanttMinimap,      // Overview strip of the loaded range
+    canvas_width_points: f32,   // Width of the canvas during the last frame
+    search: GanttSearch,        // Search box to find and focus a job
+    layout: Option<GanttLayout>, // Rows of the chart, rebuilt when the jobs or settings change
+    comparison: Option<Box<GanttChart>>, // Chart of the compared window, shown below this one
+    saved_view: GanttViewState,          // View state of the last save
+    view_changed_at: Option<f64>,        // Time of the first change not saved yet
+    restored_window: Option<(i64, i64)>, // Window of the last session, set on the first frame
 }
 
 /**
@@ -61,10 +71,18 @@ impl Default for GanttChart {
         GanttChart {
             options: Default::default(),
             job_details_windows: Vec::new(),
-            collapsed_jobs_level_1: BTreeMap::new(),
-            collapsed_jobs_level_2: BTreeMap::new(),
+            collapsed_groups: BTreeMap::new(),
             initial_start_s: None,
             initial_end_s: None,
+            export: GanttExport::default(),
+            minimap: GanttMinimap::default(),
+            canvas_width_points: 0.0,
+            search: GanttSearch::default(),
+            layout: None,
+            comparison: None,
+            saved_view: GanttViewState::default(),
+            view_changed_at: None,
+            restored_window: None,
         }
     }
 }
@@ -74,16 +92,76 @@ impl Default for GanttChart {
  */
 impl View for GanttChart {
     fn render(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
+        // Statistics and actions of the selected jobs
+        if !self.options.selection.job_ids.is_empty() {
+            let action = egui::SidePanel::right("gantt_selection_panel")
+                .default_width(220.0)
+                .show_inside(ui, |ui| self.options.selection.panel_ui(ui, &app.all_jobs))
+                .inner;
+            match action {
+                Some(SelectionAction::OpenDetails) => {
+                    for job in app
+                        .all_jobs
+                        .iter()
+                        .filter(|job| self.options.selection.is_selected(job.id))
+                    {
+                        if !self.job_details_windows.iter().any(|w| w.job.id == job.id) {
+                            self.job_details_windows.push(JobDetailsWindow::new(
+                                job.clone(),
+                                get_tree_structure_for_job(job, &app.all_clusters),
+                            ));
+                        }
+                    }
+                }
+                Some(SelectionAction::Export) => {
+                    self.options.selection.export(ui.ctx(), &app.all_jobs);
+                }
+                Some(SelectionAction::Filter) => {
+                    app.filters.job_ids = Some(self.options.selection.job_ids.clone());
+                    app.filter_jobs();
+                    app.jobs_generation += 1;
+                }
+                Some(SelectionAction::Clear) => self.options.selection.job_ids.clear(),
+                None => {}
+            }
+        }
+
         ui.heading(RichText::new(t!("app.gantt.title")).strong());
 
-        let reset_view = false;
+        let mut export_requested = false;
 
         // Initialize initial timestamps if not already done
         if self.initial_start_s.is_none() {
+            // The saved window is ignored if it is not a valid range
+            let max_range_s = TimeRangePicker::MAX_RANGE_DAYS * 24 * 60 * 60;
+            if let Some((start_s, end_s)) = self
+                .restored_window
+                .take()
+                .filter(|(start_s, end_s)| start_s < end_s && end_s - start_s <= max_range_s)
+            {
+                if let (Some(start), Some(end)) = (
+                    Local.timestamp_opt(start_s, 0).single(),
+                    Local.timestamp_opt(end_s, 0).single(),
+                ) {
+                    app.set_localdate(start, end);
+                }
+            }
             self.initial_start_s = Some(app.get_start_date().timestamp());
             self.initial_end_s = Some(app.get_end_date().timestamp());
         }
 
+        // Zoom to the range chosen with the time range picker
+        if let Some((start, end)) = app.requested_time_range.take() {
+            let origin_s = self.initial_start_s.unwrap();
+            self.options.zoom_to_relative_s_range = Some((
+                ui.input(|i| i.time),
+                (
+                    (start.timestamp() - origin_s) as f64,
+                    (end.timestamp() - origin_s) as f64,
+                ),
+            ));
+        }
+
         // Settings menu
         ui.horizontal(|ui| {
             ui.menu_button(t!("app.gantt.settings.title"), |ui| {
@@ -93,12 +171,8 @@ impl View for GanttChart {
                 self.options.aggregate_by.ui(ui);
                 ui.separator();
 
-                // If last aggregation level is set to cluster or host
-                if (self.options.aggregate_by.level_1 != AggregateByLevel1Enum::Owner
-                    && self.options.aggregate_by.level_2 == AggregateByLevel2Enum::None)
-                    || (self.options.aggregate_by.level_1 == AggregateByLevel1Enum::Cluster
-                        && self.options.aggregate_by.level_2 == AggregateByLevel2Enum::Host)
-                {
+                // If last aggregation level groups the jobs by resources (site, cluster, host, ...)
+                if self.options.aggregate_by.ends_with_location() {
                     if ui
                         .checkbox(
                             &mut self.options.see_all_res,
@@ -106,27 +180,8 @@ impl View for GanttChart {
                         )
                         .clicked()
                     {
-                        if self.options.see_all_res {
-                            app.all_jobs.push(Job {
-                                id: 0,
-                                owner: "all_resources".to_string(),
-                                state: JobState::Unknown,
-                                scheduled_start: 0,
-                                walltime: 0,
-                                hosts: get_all_hosts(&app.all_clusters),
-                                clusters: get_all_clusters(&app.all_clusters),
-                                command: String::new(),
-                                message: None,
-                                queue: String::new(),
-                                assigned_resources: get_all_resources(&app.all_clusters),
-                                submission_time: 0,
-                                start_time: 0,
-                                stop_time: 0,
-                                exit_code: None,
-                                gantt_color: Color32::TRANSPARENT,
-                                main_resource_state: ResourceState::Unknown,
-                            });
-                        } else {
+                        // The job using every resource is added back below
+                        if !self.options.see_all_res {
                             app.all_jobs.retain(|job| job.id != 0);
                         }
                     }
@@ -142,17 +197,63 @@ impl View for GanttChart {
                     &mut self.options.squash_resources,
                     t!("app.gantt.settings.squash_resources"),
                 );
+
+                // The heat strip needs first level groups owning resources
+                let first_key = self.options.aggregate_by.keys.first().copied();
+                if first_key.is_some_and(GanttHeatStrip::supports) {
+                    ui.checkbox(
+                        &mut self.options.heat_strip.enabled,
+                        t!("app.gantt.settings.heat_strip"),
+                    );
+                }
                 ui.separator();
 
                 // Job color component (random, state)
                 self.options.job_color.ui(ui);
+                ui.separator();
+
+                // Export the current view
+                export_requested = self.export.button_ui(ui);
+                ui.separator();
+
+                if ui.button(t!("app.gantt.settings.reset_view")).clicked() {
+                    self.reset_view(app);
+                    ui.close_menu();
+                }
             });
 
             // Help button
             ui.menu_button("❓", |ui| {
                 ui.label(t!("app.gantt.help"));
+                ui.separator();
+                ui.label(RichText::new(t!("app.gantt.shortcuts.title")).strong());
+                egui::Grid::new("gantt_shortcuts_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        for (keys, action) in [
+                            ("⏴ ⏵", t!("app.gantt.shortcuts.pan")),
+                            ("⏶ ⏷", t!("app.gantt.shortcuts.scroll")),
+                            ("+ -", t!("app.gantt.shortcuts.zoom")),
+                            ("Home", t!("app.gantt.shortcuts.now")),
+                            ("Tab / Shift+Tab", t!("app.gantt.shortcuts.next_job")),
+                            ("Enter", t!("app.gantt.shortcuts.details")),
+                        ] {
+                            ui.label(RichText::new(keys).monospace());
+                            ui.label(action);
+                            ui.end_row();
+                        }
+                    });
+                ui.label(RichText::new(t!("app.gantt.shortcuts.focus_hint")).weak());
             });
 
+            self.export.status_ui(ui);
+
+            // Search a job and focus it
+            ui.separator();
+            if let Some(job_id) = self.search.ui(ui, app) {
+                self.focus_job(ui.input(|i| i.time), app, job_id);
+            }
+
             // Reset to now button
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.button(t!("app.gantt.now")).clicked() {
@@ -167,7 +268,154 @@ impl View for GanttChart {
             });
         });
 
-        // Canvas
+        // The resources without job are shown with a job using every resource, added
+        // once the clusters are loaded and again after the dashboard removed it
+        if self.options.see_all_res
+            && !app.all_clusters.is_empty()
+            && !app.all_jobs.iter().any(|job| job.id == 0)
+        {
+            app.all_jobs.push(all_resources_job(&app.all_clusters));
+        }
+
+        // Legend of the job colors, the values of the legend get their colors in its order
+        self.options.job_color.update_categories(&app.filtered_jobs);
+        self.options.job_color.legend_ui(ui, &app.filtered_jobs);
+
+        // Overview of the loaded range, its viewport pans and zooms the canvas
+        if self.minimap.ui(
+            ui,
+            app,
+            self.initial_start_s.unwrap(),
+            self.canvas_width_points,
+            &mut self.options.canvas_width_s,
+            &mut self.options.sideways_pan_in_points,
+        ) {
+            self.options.zoom_to_relative_s_range = None;
+        }
+
+        // Canvas, above the compared window when comparing
+        match app.comparison_offset_s {
+            Some(offset_s) => self.comparison_ui(ui, app, offset_s),
+            None => self.canvas_ui(ui, app),
+        }
+
+        if export_requested {
+            let data = self.build_export_data(app);
+            self.export.export(ui.ctx(), &data);
+        }
+
+        // Part to display the details of a job when clicked
+        self.job_details_windows.retain(|w| w.is_open());
+
+        // Display job detail windows
+        for window in self.job_details_windows.iter_mut() {
+            window.ui(ui);
+        }
+
+        self.save_view_state(ui, app);
+    }
+}
+
+impl GanttChart {
+    const SAVE_DELAY_S: f64 = 2.0; // Delay between a change of the view and its save
+
+    /**
+     * Creates the chart with the view state of the last session
+     */
+    pub fn new() -> Self {
+        let mut gantt = GanttChart::default();
+        let state = GanttViewState::load_from_file(GanttViewState::FILE_PATH);
+        gantt.apply_view_state(&state);
+        gantt.restored_window = state.window;
+        gantt.saved_view = state;
+        gantt
+    }
+
+    /**
+     * Returns the current view state, to be compared with the saved one
+     */
+    fn view_state(&self, app: &ApplicationContext) -> GanttViewState {
+        GanttViewState {
+            aggregate_by: self.options.aggregate_by.clone(),
+            job_color: self.options.job_color.clone(),
+            squash_resources: self.options.squash_resources,
+            see_all_res: self.options.see_all_res,
+            heat_strip: self.options.heat_strip.enabled,
+            collapsed_groups: self
+                .collapsed_groups
+                .iter()
+                .filter(|(_, collapsed)| **collapsed)
+                .map(|(path, _)| path.clone())
+                .collect(),
+            window: Some((
+                app.get_start_date().timestamp(),
+                app.get_end_date().timestamp(),
+            )),
+        }
+    }
+
+    fn apply_view_state(&mut self, state: &GanttViewState) {
+        self.options.aggregate_by = state.aggregate_by.clone();
+        self.options.job_color = state.job_color.clone();
+        self.options.squash_resources = state.squash_resources;
+        self.options.see_all_res = state.see_all_res;
+        self.options.heat_strip.enabled = state.heat_strip;
+        self.collapsed_groups = state
+            .collapsed_groups
+            .iter()
+            .map(|path| (path.clone(), true))
+            .collect();
+        self.layout = None;
+    }
+
+    /**
+     * Saves the view state once it stopped changing for a while, so that panning
+     * does not write the file at every frame
+     */
+    fn save_view_state(&mut self, ui: &egui::Ui, app: &ApplicationContext) {
+        let state = self.view_state(app);
+        if state == self.saved_view {
+            self.view_changed_at = None;
+            return;
+        }
+        let now = ui.input(|i| i.time);
+        let changed_at = *self.view_changed_at.get_or_insert(now);
+        if now - changed_at >= Self::SAVE_DELAY_S {
+            // The view is saved again at the next change if the file can't be written
+            let _ = state.save_to_file(GanttViewState::FILE_PATH);
+            self.saved_view = state;
+            self.view_changed_at = None;
+        }
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_secs_f64(Self::SAVE_DELAY_S));
+    }
+
+    /**
+     * Goes back to the default settings and to the default time window around now
+     */
+    fn reset_view(&mut self, app: &mut ApplicationContext) {
+        self.apply_view_state(&GanttViewState::default());
+        app.all_jobs.retain(|job| job.id != 0);
+
+        let now = Local::now();
+        let (start, end) = (
+            now - chrono::Duration::hours(1),
+            now + chrono::Duration::hours(1),
+        );
+        app.set_localdate(start, end);
+        self.initial_start_s = Some(start.timestamp());
+        self.initial_end_s = Some(end.timestamp());
+        self.options.canvas_width_s = 0.0;
+        self.options.sideways_pan_in_points = 0.0;
+        self.options.zoom_to_relative_s_range = None;
+    }
+
+    /**
+     * Shows the canvas with the jobs and the timeline
+     */
+    fn canvas_ui(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
+        let reset_view = false;
+
         Frame::canvas(ui.style()).show(ui, |ui| {
             ui.visuals_mut().clip_rect_margin = 0.0;
 
@@ -195,6 +443,8 @@ impl View for GanttChart {
                     font_id: TextStyle::Body.resolve(ui.style()),
                 };
 
+                self.canvas_width_points = info.canvas.width();
+
                 // When we reset the view, we want to zoom to the full range
                 if reset_view {
                     self.options.zoom_to_relative_s_range = Some((
@@ -203,8 +453,31 @@ impl View for GanttChart {
                     ));
                 }
 
-                // Interact with the canvas
+                // Interact with the canvas, the keyboard is used once the canvas has the focus
                 interact_with_canvas(&mut self.options, &info.response, &info);
+                if info.response.clicked() || info.response.drag_started() {
+                    info.response.request_focus();
+                }
+                if info.response.has_focus() {
+                    ui.memory_mut(|memory| {
+                        memory.set_focus_lock_filter(
+                            info.response.id,
+                            egui::EventFilter {
+                                tab: true,
+                                horizontal_arrows: true,
+                                vertical_arrows: true,
+                                escape: false,
+                            },
+                        )
+                    });
+                    interact_with_keyboard(
+                        &mut self.options,
+                        &info,
+                        ui,
+                        app,
+                        &mut self.job_details_windows,
+                    );
+                }
 
                 // Put the timeline
                 let where_to_put_timeline = info.painter.add(Shape::Noop);
@@ -217,8 +490,8 @@ impl View for GanttChart {
                     fixed_timeline_y,
                     (min_s, max_s),
                     &mut self.job_details_windows,
-                    &mut self.collapsed_jobs_level_1,
-                    &mut self.collapsed_jobs_level_2,
+                    &mut self.collapsed_groups,
+                    &mut self.layout,
                     &app.all_clusters,
                 );
 
@@ -232,13 +505,49 @@ impl View for GanttChart {
                 info.painter
                     .set(where_to_put_timeline, Shape::Vec(timeline));
 
+                // Maintenance windows and notes, over their clusters and hosts
+                paint_annotations(
+                    &info,
+                    &self.options,
+                    self.layout.as_ref(),
+                    &app.annotations,
+                    used_rect,
+                );
+
                 // Adding a line to show the current time AFTER all other elements
                 let current_time_line = paint_current_time_line(&info, &self.options, used_rect);
                 info.painter.add(current_time_line);
 
+                // Select the jobs shift-clicked or inside the rubber band
+                let shift = ui.input(|i| i.modifiers.shift);
+                self.options.selection.update(
+                    &info.response,
+                    &info.painter,
+                    shift,
+                    &self.options.painted_jobs,
+                );
+
                 ui.allocate_rect(used_rect, Sense::hover());
 
-                {
+                // Scroll to the job focused by the search, its row is found in the layout
+                // when it is outside of the scroll area and therefore not painted
+                if let Some(focused) = self.options.focused_job.as_mut() {
+                    let rect = focused.rect.or_else(|| {
+                        let layout = self.layout.as_ref()?;
+                        let top_y = layout.job_top(focused.job_id)?;
+                        Some(Rect::from_min_size(
+                            pos2(canvas.min.x, canvas.top() + top_y),
+                            egui::vec2(canvas.width(), self.options.rect_height),
+                        ))
+                    });
+                    if let (true, Some(rect)) = (focused.scroll_pending, rect) {
+                        ui.scroll_to_rect(rect, Some(egui::Align::Center));
+                        focused.scroll_pending = false;
+                    }
+                }
+
+                // The compared window follows the current one and keeps the application dates
+                if !self.options.compared_window {
                     // Calculate the visible time range from the canvas parameters
                     let visible_start_s = info.start_s
                         + ((-self.options.sideways_pan_in_points / info.canvas.width())
@@ -252,15 +561,183 @@ impl View for GanttChart {
                 }
             });
         });
+    }
 
-        // Part to display the details of a job when clicked
-        self.job_details_windows.retain(|w| w.is_open());
+    /**
+     * Shows the current window above the compared one, the compared window has the
+     * same settings and shares the horizontal zoom and pan of the current one
+     */
+    fn comparison_ui(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext, offset_s: i64) {
+        let label_height = ui.spacing().interact_size.y;
+        let height = ((ui.available_height() - label_height) / 2.0).max(100.0);
+        ui.allocate_ui(egui::vec2(ui.available_width(), height), |ui| {
+            self.canvas_ui(ui, app)
+        });
 
-        // Display job detail windows
-        for window in self.job_details_windows.iter_mut() {
+        let comparison = self.comparison.get_or_insert_with(Default::default);
+        comparison.options.compared_window = true;
+        comparison.options.aggregate_by = self.options.aggregate_by.clone();
+        comparison.options.job_color = self.options.job_color.clone();
+        comparison.options.squash_resources = self.options.squash_resources;
+        comparison.options.heat_strip.enabled = self.options.heat_strip.enabled;
+        comparison.options.canvas_width_s = self.options.canvas_width_s;
+        comparison.options.sideways_pan_in_points = self.options.sideways_pan_in_points;
+        comparison.initial_start_s = self.initial_start_s.map(|start_s| start_s + offset_s);
+        comparison.initial_end_s = self.initial_end_s.map(|end_s| end_s + offset_s);
+
+        let start_s = app.get_start_date().timestamp() + offset_s;
+        let end_s = app.get_end_date().timestamp() + offset_s;
+        ui.label(
+            RichText::new(t!(
+                "app.gantt.comparison.title",
+                start = format_timestamp(start_s),
+                end = format_timestamp(end_s)
+            ))
+            .strong(),
+        );
+        ui.push_id("gantt_comparison", |ui| comparison.canvas_ui(ui, app));
+
+        // Panning or zooming the compared window moves the current one too
+        if comparison.options.canvas_width_s != self.options.canvas_width_s
+            || comparison.options.sideways_pan_in_points != self.options.sideways_pan_in_points
+        {
+            self.options.canvas_width_s = comparison.options.canvas_width_s;
+            self.options.sideways_pan_in_points = comparison.options.sideways_pan_in_points;
+            self.options.zoom_to_relative_s_range = None;
+        }
+
+        comparison.job_details_windows.retain(|w| w.is_open());
+        for window in comparison.job_details_windows.iter_mut() {
             window.ui(ui);
         }
     }
+
+    /**
+     * Zooms to a job, expands the groups containing it and highlights it
+     * The job is looked for among all the loaded jobs, like the search
+     */
+    fn focus_job(&mut self, time: f64, app: &ApplicationContext, job_id: u32) {
+        let jobs: Vec<Job> = app
+            .all_jobs
+            .iter()
+            .filter(|job| app.matches_filters(job, (None, None)))
+            .cloned()
+            .collect();
+        let Some(job) = jobs.iter().find(|job| job.id == job_id) else {
+            return;
+        };
+
+        // Zoom to the job with a margin on both sides
+        let stop_time = if job.stop_time > 0 {
+            job.stop_time
+        } else {
+            job.scheduled_start + job.walltime
+        };
+        let margin_s = ((stop_time - job.scheduled_start) / 5).max(5 * 60);
+        let origin_s = self.initial_start_s.unwrap_or(0);
+        self.options.zoom_to_relative_s_range = Some((
+            time,
+            (
+                (job.scheduled_start - margin_s - origin_s) as f64,
+                (stop_time + margin_s - origin_s) as f64,
+            ),
+        ));
+
+        // Expand every group containing the job, and their parents
+        let groups = group_jobs(&self.options.aggregate_by, app, &jobs);
+        let mut paths = Vec::new();
+        collect_job_paths(&groups, job_id, &mut Vec::new(), &mut paths);
+        for path in paths {
+            for depth in 1..=path.len() {
+                self.collapsed_groups.insert(path[..depth].to_vec(), false);
+            }
+        }
+
+        self.options.focused_job = Some(FocusedJob::new(job_id, time));
+        self.layout = None; // Rebuilt with the expanded groups
+    }
+
+    /**
+     * Builds the rows of the current view for the export
+     * All the rows are exported, even those outside of the screen, collapsed groups stay collapsed
+     */
+    fn build_export_data(&self, app: &ApplicationContext) -> GanttExportData {
+        let mut rows = Vec::new();
+        let groups = group_jobs(&self.options.aggregate_by, app, &app.filtered_jobs);
+        let now = chrono::Utc::now().timestamp();
+        self.export_rows(&groups, now, &mut Vec::new(), &mut rows);
+
+        GanttExportData {
+            title: t!("app.gantt.title").to_string(),
+            start_s: app.get_start_date().timestamp(),
+            end_s: app.get_end_date().timestamp(),
+            rows,
+            legend: self.options.job_color.legend(&app.filtered_jobs),
+        }
+    }
+
+    /**
+     * Recursively adds the header of each group then its sub groups or its jobs
+     */
+    fn export_rows(
+        &self,
+        groups: &[JobGroup],
+        now: i64,
+        path: &mut Vec<String>,
+        rows: &mut Vec<ExportRow>,
+    ) {
+        for group in groups {
+            path.push(group.name.clone());
+            rows.push(ExportRow::Header {
+                label: group.name.clone(),
+                level: path.len() as u8,
+            });
+
+            if !self.collapsed_groups.get(path).copied().unwrap_or(false) {
+                if !group.children.is_empty() {
+                    self.export_rows(&group.children, now, path, rows);
+                } else if self.options.squash_resources {
+                    rows.push(ExportRow::Jobs(
+                        group.jobs.iter().flat_map(|job| self.export_bars(job, now)).collect(),
+                    ));
+                } else {
+                    rows.extend(
+                        group
+                            .jobs
+                            .iter()
+                            .map(|job| ExportRow::Jobs(self.export_bars(job, now))),
+                    );
+                }
+            }
+            path.pop();
+        }
+    }
+
+    /**
+     * Returns one bar per segment of the job, colored like the canvas
+     * The label is only given to the first segment
+     */
+    fn export_bars(&self, job: &Job, now: i64) -> Vec<ExportBar> {
+        let color = self.options.job_color.get_job_colors(job).1;
+        job.get_segments(now)
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| ExportBar {
+                label: if index == 0 {
+                    format!("{} ({})", job.owner, job.id)
+                } else {
+                    String::new()
+                },
+                start_s: segment.start,
+                end_s: segment.end,
+                color: match segment.kind {
+                    JobSegmentKind::Elapsed => color,
+                    JobSegmentKind::Remaining => color.gamma_multiply(0.35),
+                    JobSegmentKind::Predicted => color.gamma_multiply(0.2),
+                },
+            })
+            .collect()
+    }
 }
 
 /****************************************************************************************************************************/
@@ -293,6 +770,9 @@ impl Info {
 // OPTIONS
 /****************************************************************************************************************************/
 
+// Longest range visible in the canvas, the jobs outside of the loaded periods are fetched in the background
+const MAX_CANVAS_WIDTH_S: f32 = (TimeRangePicker::MAX_RANGE_DAYS * 24 * 60 * 60) as f32;
+
 /**
  * Options struct
  */
@@ -311,6 +791,12 @@ pub struct Options {
     pub previous_hovered_job: Option<Job>,                     // Previous hovered job
     pub current_hovered_resource_state: Option<ResourceState>, // Current hovered resource state
     pub squash_resources: bool,                                // Squash resources
+    pub focused_job: Option<FocusedJob>,                       // Job focused by the search
+    pub keyboard_job: Option<u32>,                             // Job selected with the keyboard
+    pub painted_jobs: Vec<(u32, Rect)>,                        // Jobs painted in the last frame
+    pub selection: GanttSelection,                             // Jobs selected by the user
+    pub heat_strip: GanttHeatStrip,                            // Occupation of the groups
+    pub compared_window: bool,                                 // Shows the compared window
     #[cfg_attr(feature = "serde", serde(skip))]
     zoom_to_relative_s_range: Option<(f64, (f64, f64))>, // Zoom to relative s range
 }
@@ -335,11 +821,43 @@ impl Default for Options {
             previous_hovered_job: None,       // no previous hovered job by default
             squash_resources: false,          // don't squash resources by default
             see_all_res: false,
+            focused_job: None,  // no focused job by default
+            keyboard_job: None, // no job selected with the keyboard by default
+            painted_jobs: Vec::new(),
+            selection: GanttSelection::default(),
+            heat_strip: GanttHeatStrip::default(),
+            compared_window: false,
             current_hovered_resource_state: None, // no hovered resource stae by default
         }
     }
 }
 
+/**
+ * Returns the job using every resource, shown to see the resources without job
+ */
+fn all_resources_job(clusters: &Vec<Cluster>) -> Job {
+    Job {
+        id: 0,
+        owner: "all_resources".to_string(),
+        state: JobState::Unknown,
+        scheduled_start: 0,
+        walltime: 0,
+        hosts: get_all_hosts(clusters),
+        clusters: get_all_clusters(clusters),
+        command: String::new(),
+        message: None,
+        queue: String::new(),
+        project: String::new(),
+        assigned_resources: get_all_resources(clusters),
+        submission_time: 0,
+        start_time: 0,
+        stop_time: 0,
+        exit_code: None,
+        gantt_color: Color32::TRANSPARENT,
+        main_resource_state: ResourceState::Unknown,
+    }
+}
+
 /****************************************************************************************************************************/
 // CANVAS PAINTING
 /****************************************************************************************************************************/
@@ -354,8 +872,8 @@ fn ui_canvas(
     fixed_timeline_y: f32,
     (min_ns, max_ns): (i64, i64),
     details_window: &mut Vec<JobDetailsWindow>,
-    collapsed_jobs_level_1: &mut BTreeMap<String, bool>,
-    collapsed_jobs_level_2: &mut BTreeMap<(String, String), bool>,
+    collapsed_groups: &mut BTreeMap<Vec<String>, bool>,
+    layout: &mut Option<GanttLayout>,
     all_cluster: &Vec<Cluster>,
 ) -> f32 {
     if options.canvas_width_s <= 0.0 {
@@ -363,262 +881,234 @@ fn ui_canvas(
         options.zoom_to_relative_s_range = None;
     }
 
-    let mut cursor_y = info.canvas.top();
-    cursor_y += info.text_height;
+    // The focused job rectangle is updated while painting
+    if let Some(focused) = options.focused_job.as_mut() {
+        focused.rect = None;
+    }
+    options.painted_jobs.clear();
+
+    // Occupation of the first level groups over the visible time span
+    let first_key = options.aggregate_by.keys.first().copied();
+    let heat_strip = options.heat_strip.enabled && first_key.is_some_and(GanttHeatStrip::supports);
+    if let (true, Some(first_key)) = (heat_strip, first_key) {
+        let visible_start_s = info.start_s
+            + ((-options.sideways_pan_in_points / info.canvas.width()) * options.canvas_width_s)
+                as i64;
+        let visible_end_s = visible_start_s + options.canvas_width_s as i64;
+        options.heat_strip.update(
+            app,
+            first_key,
+            (visible_start_s, visible_end_s),
+            info.canvas.width(),
+            chrono::Utc::now().timestamp(),
+        );
+    }
 
-    // Get filtered jobs to display
-    let jobs = app.filtered_jobs.clone();
+    // The rows are only rebuilt when the jobs, the aggregation keys or the sizes change
+    let key = LayoutKey {
+        generation: if options.compared_window {
+            app.comparison_generation
+        } else {
+            app.jobs_generation
+        },
+        keys: options.aggregate_by.keys.clone(),
+        metrics: LayoutMetrics {
+            font_size: app.font_size as f32,
+            line_height: info.text_height + 5.0 + options.spacing,
+            rect_height: options.rect_height,
+            squash: options.squash_resources,
+            heat_strip,
+        },
+    };
+    if layout.as_ref().is_some_and(|layout| layout.key != key) {
+        *layout = None;
+    }
+    let current_layout = layout.get_or_insert_with(|| {
+        let groups = group_jobs(&options.aggregate_by, app, displayed_jobs(options, app));
+        let start_y = info.text_height + app.font_size as f32;
+        GanttLayout::build(key, groups, collapsed_groups, start_y)
+    });
+    let max_y = info.canvas.top() + current_layout.height;
+
+    // Paint the rows visible in the scroll area
+    if paint_layout_rows(
+        info,
+        options,
+        current_layout,
+        details_window,
+        collapsed_groups,
+        all_cluster,
+    ) {
+        *layout = None; // A group was collapsed or expanded
+    }
 
-    match options.aggregate_by.level_1 {
+    // Paint tooltip for hovered job/resource state
+    paint_tooltip(info, options);
 
-        // Aggregate by owner as level 1 only
-        AggregateByLevel1Enum::Owner => {
-            let mut jobs_by_owner: BTreeMap<String, Vec<Job>> = BTreeMap::new();
-            // for each job, we add it to the corresponding owner
-            for job in jobs {
-                jobs_by_owner
-                    .entry(job.owner.clone())
-                    .or_insert_with(Vec::new)
-                    .push(job.clone());
-            }
-            // Paint the aggregated jobs with owner as level 1
-            cursor_y = paint_aggregated_jobs_level_1(
-                info,
-                options,
-                jobs_by_owner,
-                cursor_y,
-                details_window,
-                collapsed_jobs_level_1,
-                app.font_size,
-                all_cluster,
-                AggregateByLevel1Enum::Owner,
-            );
-        }
+    // Update previous hovered job for next frame
+    options.previous_hovered_job = options.current_hovered_job.clone();
+    options.current_hovered_job = None; // Reset for next frame, but keep previous_hovered_job
 
-        // Aggregate by host as level 1
-        AggregateByLevel1Enum::Host => {
-            match options.aggregate_by.level_2 {
-
-                // Aggregate by owner as level 2
-                AggregateByLevel2Enum::Owner => {
-                    let mut jobs_by_host_by_owner: BTreeMap<String, BTreeMap<String, Vec<Job>>> =
-                        BTreeMap::new();
-                    let filtered_clusters = app.filters.clusters.clone().unwrap_or_default();
-                    // for each job, we add it to the corresponding host and owner
-                    for job in jobs {
-                        for host in job.hosts.iter() {
-                            if filtered_clusters.len() != 0
-                                && !contains_host(&filtered_clusters, host)
-                            {
-                                continue;
-                            }
-                            jobs_by_host_by_owner
-                                .entry(host.clone())
-                                .or_insert_with(BTreeMap::new)
-                                .entry(job.owner.clone())
-                                .or_insert_with(Vec::new)
-                                .push(job.clone());
-                        }
-                    }
+    // Paint the timeline text on top of everything
+    paint_timeline_text_on_top(info, options, fixed_timeline_y);
 
-                    // Paint the aggregated jobs with host as level 1 and owner as level 2
-                    cursor_y = paint_aggregated_jobs_level_2(
-                        info,
-                        options,
-                        jobs_by_host_by_owner,
-                        cursor_y,
-                        details_window,
-                        collapsed_jobs_level_1,
-                        collapsed_jobs_level_2,
-                        app.font_size,
-                        all_cluster,
-                        AggregateByLevel1Enum::Host,
-                        AggregateByLevel2Enum::Owner,
-                    );
-                }
+    max_y
+}
 
-                // No aggregation as level 2 so we only aggregate by host as level 1
-                AggregateByLevel2Enum::None => {
-                    let mut jobs_by_host: BTreeMap<String, Vec<Job>> = BTreeMap::new();
-                    let filtered_clusters = app.filters.clusters.clone().unwrap_or_default();
-
-                    // for each job, we add it to the corresponding host
-                    for job in jobs {
-                        for host in job.hosts.iter() {
-                            if filtered_clusters.len() != 0
-                                && !contains_host(&filtered_clusters, host)
-                            {
-                                continue;
-                            }
-                            jobs_by_host
-                                .entry(host.clone())
-                                .or_insert_with(Vec::new)
-                                .push(job.clone());
-                        }
-                    }
+/**
+ * A group of jobs of the Gantt chart, for one value of an aggregation key
+ */
+pub(crate) struct JobGroup {
+    pub name: String,            // Value of the aggregation key (e.g. the cluster name)
+    pub state: ResourceState,    // State of the innermost cluster, host or resource
+    pub jobs: Vec<Job>,          // Jobs of the group, only filled for the last level
+    pub children: Vec<JobGroup>, // Sub groups for the next aggregation key
+}
 
-                    // Paint the aggregated jobs with host as level 1
-                    cursor_y = paint_aggregated_jobs_level_1(
-                        info,
-                        options,
-                        jobs_by_host,
-                        cursor_y,
-                        details_window,
-                        collapsed_jobs_level_1,
-                        app.font_size,
-                        all_cluster,
-                        AggregateByLevel1Enum::Host,
-                    );
-                }
-                AggregateByLevel2Enum::Host => {
-                    // nothing to do here
-                }
-            }
-        }
+/**
+ * Returns the jobs shown by the chart, the compared window has its own jobs
+ */
+fn displayed_jobs<'a>(options: &Options, app: &'a ApplicationContext) -> &'a [Job] {
+    if options.compared_window {
+        &app.comparison_jobs
+    } else {
+        &app.filtered_jobs
+    }
+}
 
-        // Aggregate by cluster as level 1
-        AggregateByLevel1Enum::Cluster => match options.aggregate_by.level_2 {
-
-            // Aggregate by owner as level 2
-            AggregateByLevel2Enum::Owner => {
-                let mut jobs_by_cluster_by_owner: BTreeMap<String, BTreeMap<String, Vec<Job>>> =
-                    BTreeMap::new();
-                let filtered_clusters = app.filters.clusters.clone().unwrap_or_default();
-
-                // for each job, we add it to the corresponding cluster and owner
-                for job in jobs {
-                    for cluster in job.clusters.iter() {
-                        if filtered_clusters.len() != 0
-                            && contains_cluster(&filtered_clusters, cluster)
-                        {
-                            continue;
-                        }
-                        jobs_by_cluster_by_owner
-                            .entry(cluster.clone())
-                            .or_insert_with(BTreeMap::new)
-                            .entry(job.owner.clone())
-                            .or_insert_with(Vec::new)
-                            .push(job.clone());
-                    }
-                }
+/**
+ * Groups the jobs according to the aggregation keys
+ * A job appears in every group (site, cluster, host, ...) owning one of its resources
+ */
+pub(crate) fn group_jobs(
+    aggregate_by: &AggregateBy,
+    app: &ApplicationContext,
+    jobs: &[Job],
+) -> Vec<JobGroup> {
+    // Only the resources passing the resource selection are used to place the jobs
+    let jobs = jobs
+        .iter()
+        .map(|job| {
+            let resources = job
+                .assigned_resources
+                .iter()
+                .copied()
+                .filter(|id| app.filters.matches_resource(*id, &app.resource_index))
+                .collect();
+            (job, resources)
+        })
+        .collect();
+
+    group_jobs_by_keys(&aggregate_by.keys, jobs, ResourceState::Alive, app)
+}
 
-                // Paint the aggregated jobs with cluster as level 1 and owner as level 2
-                cursor_y = paint_aggregated_jobs_level_2(
-                    info,
-                    options,
-                    jobs_by_cluster_by_owner,
-                    cursor_y,
-                    details_window,
-                    collapsed_jobs_level_1,
-                    collapsed_jobs_level_2,
-                    app.font_size,
-                    all_cluster,
-                    AggregateByLevel1Enum::Cluster,
-                    AggregateByLevel2Enum::Owner,
-                );
+/**
+ * Collects the paths of the last level groups containing a job
+ */
+fn collect_job_paths(
+    groups: &[JobGroup],
+    job_id: u32,
+    path: &mut Vec<String>,
+    paths: &mut Vec<Vec<String>>,
+) {
+    for group in groups {
+        path.push(group.name.clone());
+        if group.children.is_empty() {
+            if group.jobs.iter().any(|job| job.id == job_id) {
+                paths.push(path.clone());
             }
+        } else {
+            collect_job_paths(&group.children, job_id, path, paths);
+        }
+        path.pop();
+    }
+}
 
-            // No aggregation as level 2 so we only aggregate by cluster as level 1
-            AggregateByLevel2Enum::None => {
-                let mut jobs_by_cluster: BTreeMap<String, Vec<Job>> = BTreeMap::new();
-                let filtered_clusters = app.filters.clusters.clone().unwrap_or_default();
-
-                // for each job, we add it to the corresponding cluster
-                for job in jobs {
-                    for cluster in job.clusters.iter() {
-                        if filtered_clusters.len() != 0
-                            && contains_cluster(&filtered_clusters, cluster)
-                        {
-                            continue;
-                        }
-                        jobs_by_cluster
-                            .entry(cluster.clone())
-                            .or_insert_with(Vec::new)
-                            .push(job.clone());
-                    }
-                }
-
-                // Paint the aggregated jobs with cluster as level 1
-                cursor_y = paint_aggregated_jobs_level_1(
-                    info,
-                    options,
-                    jobs_by_cluster,
-                    cursor_y,
-                    details_window,
-                    collapsed_jobs_level_1,
-                    app.font_size,
-                    all_cluster,
-                    AggregateByLevel1Enum::Cluster,
-                );
-            }
+/**
+ * Groups the jobs by the first key, then recursively by the remaining keys
+ * Each job comes with the resources it uses in the current group, so that nested
+ * location keys (e.g. cluster then host) only keep the hosts belonging to the cluster
+ */
+fn group_jobs_by_keys(
+    keys: &[AggregateKey],
+    jobs: Vec<(&Job, Vec<u32>)>,
+    parent_state: ResourceState,
+    app: &ApplicationContext,
+) -> Vec<JobGroup> {
+    let Some((key, next_keys)) = keys.split_first() else {
+        return Vec::new();
+    };
 
-            // Aggregate by host as level 2
-            AggregateByLevel2Enum::Host => {
-                let mut jobs_by_cluster_by_host: BTreeMap<String, BTreeMap<String, Vec<Job>>> =
-                    BTreeMap::new();
-                let filtered_clusters = app.filters.clusters.clone().unwrap_or_default();
-
-                // for each job, we add it to the corresponding cluster and host
-                for job in jobs {
-                    for cluster in job.clusters.iter() {
-                        for host in job.hosts.iter() {
-                            if filtered_clusters.len() != 0
-                                && !contains_host(&filtered_clusters, host)
-                            {
-                                continue;
-                            }
-                            // We don't add the host to the cluster if this host doesn't belong to the cluster
-                            let curr_cluster =
-                                get_cluster_from_name(&app.all_clusters, &cluster).unwrap();
-
-                            if cluster_contain_host(&curr_cluster, &host) {
-                                jobs_by_cluster_by_host
-                                    .entry(cluster.clone())
-                                    .or_insert_with(BTreeMap::new)
-                                    .entry(host.clone())
-                                    .or_insert_with(Vec::new)
-                                    .push(job.clone());
-                            }
-                        }
-                    }
+    let mut groups: BTreeMap<String, Vec<(&Job, Vec<u32>)>> = BTreeMap::new();
+    for (job, resources) in jobs {
+        if key.is_location() {
+            // Split the resources of the job between the groups owning them
+            let mut resources_by_group: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+            for id in resources {
+                if let Some(location) = app.resource_index.get(id) {
+                    resources_by_group
+                        .entry(key.location_value(id, location))
+                        .or_default()
+                        .push(id);
                 }
-
-                // Paint the aggregated jobs with cluster as level 1 and host as level 2
-                cursor_y = paint_aggregated_jobs_level_2(
-                    info,
-                    options,
-                    jobs_by_cluster_by_host,
-                    cursor_y,
-                    details_window,
-                    collapsed_jobs_level_1,
-                    collapsed_jobs_level_2,
-                    app.font_size,
-                    all_cluster,
-                    AggregateByLevel1Enum::Cluster,
-                    AggregateByLevel2Enum::Host,
-                );
             }
-        },
+            for (name, ids) in resources_by_group {
+                groups.entry(name).or_default().push((job, ids));
+            }
+        } else {
+            groups
+                .entry(key.job_value(job))
+                .or_default()
+                .push((job, resources));
+        }
     }
 
-    // Paint tooltip for hovered job/resource state
-    paint_tooltip(info, options);
-
-    // Update previous hovered job for next frame
-    options.previous_hovered_job = options.current_hovered_job.clone();
-    options.current_hovered_job = None; // Reset for next frame, but keep previous_hovered_job
-
-    // Paint the timeline text on top of everything
-    paint_timeline_text_on_top(info, options, fixed_timeline_y);
+    // Sort the groups
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by(|(a, _), (b, _)| compare_string_with_number(a, b));
+
+    groups
+        .into_iter()
+        .map(|(name, jobs)| {
+            // Get the state of the resource, the keys without state inherit it from their parent
+            let state = match key {
+                AggregateKey::Cluster => get_cluster_state_from_name(&app.all_clusters, &name),
+                AggregateKey::Host => get_host_state_from_name(&app.all_clusters, &name),
+                AggregateKey::Resource => name
+                    .parse()
+                    .ok()
+                    .and_then(|id| app.resource_index.get(id))
+                    .map_or(ResourceState::Unknown, |location| location.state),
+                _ => parent_state,
+            };
 
-    cursor_y
+            if next_keys.is_empty() {
+                JobGroup {
+                    name,
+                    state,
+                    jobs: jobs.into_iter().map(|(job, _)| job.clone()).collect(),
+                    children: Vec::new(),
+                }
+            } else {
+                JobGroup {
+                    children: group_jobs_by_keys(next_keys, jobs, state, app),
+                    name,
+                    state,
+                    jobs: Vec::new(),
+                }
+            }
+        })
+        .collect()
 }
 
 /**
  * Interacts with the canvas
  */
 fn interact_with_canvas(options: &mut Options, response: &Response, info: &Info) {
-    if response.drag_delta().x != 0.0 {
+    // A shift-drag draws a rubber band to select jobs instead of panning
+    let is_selecting =
+        options.selection.is_dragging_band() || info.ctx.input(|i| i.modifiers.shift);
+    if response.drag_delta().x != 0.0 && !is_selecting {
         options.sideways_pan_in_points += response.drag_delta().x;
         options.zoom_to_relative_s_range = None;
     }
@@ -640,8 +1130,7 @@ fn interact_with_canvas(options: &mut Options, response: &Response, info: &Info)
             let new_width = options.canvas_width_s / zoom_factor;
 
             // Apply a limit to the zoom
-            let max_canvas_width = 2 * 24 * 60 * 60; // 2 days in seconds
-            if new_width <= max_canvas_width as f32 {
+            if new_width <= MAX_CANVAS_WIDTH_S {
                 options.canvas_width_s = new_width;
 
                 if let Some(mouse_pos) = response.hover_pos() {
@@ -687,6 +1176,125 @@ fn interact_with_canvas(options: &mut Options, response: &Response, info: &Info)
     }
 }
 
+/**
+ * Handles the keyboard shortcuts of the canvas, only called while the canvas has the focus
+ * Arrows pan and scroll, +/- zoom around the current time, Home centers on the current time,
+ * Tab/Shift+Tab select the next/previous job and Enter opens the details of the selected job
+ */
+fn interact_with_keyboard(
+    options: &mut Options,
+    info: &Info,
+    ui: &mut egui::Ui,
+    app: &ApplicationContext,
+    details_window: &mut Vec<JobDetailsWindow>,
+) {
+    const PAN_RATIO: f32 = 0.1; // Part of the canvas panned by an arrow
+    const SCROLL_STEP: f32 = 60.0; // Points scrolled by an arrow
+    const ZOOM_STEP: f32 = 1.25; // Zoom factor of a +/- press
+
+    let canvas_width = info.canvas.width();
+    let (left, right, up, down, zoom_in, zoom_out, home, tab, shift, enter) = info.ctx.input(|i| {
+        (
+            i.key_pressed(egui::Key::ArrowLeft),
+            i.key_pressed(egui::Key::ArrowRight),
+            i.key_pressed(egui::Key::ArrowUp),
+            i.key_pressed(egui::Key::ArrowDown),
+            i.key_pressed(egui::Key::Plus) || i.key_pressed(egui::Key::Equals),
+            i.key_pressed(egui::Key::Minus),
+            i.key_pressed(egui::Key::Home),
+            i.key_pressed(egui::Key::Tab),
+            i.modifiers.shift,
+            i.key_pressed(egui::Key::Enter),
+        )
+    });
+
+    // Sideways pan and vertical scroll
+    if left || right {
+        let direction = if left { 1.0 } else { -1.0 };
+        options.sideways_pan_in_points += direction * PAN_RATIO * canvas_width;
+        options.zoom_to_relative_s_range = None;
+    }
+    if up || down {
+        let direction = if up { 1.0 } else { -1.0 };
+        ui.scroll_with_delta(egui::vec2(0.0, direction * SCROLL_STEP));
+    }
+
+    // Zoom around the current time line
+    let now_s = chrono::Utc::now().timestamp();
+    if zoom_in || zoom_out {
+        let zoom_factor = if zoom_in {
+            ZOOM_STEP
+        } else {
+            ZOOM_STEP.recip()
+        };
+        let new_width = options.canvas_width_s / zoom_factor;
+        if new_width <= MAX_CANVAS_WIDTH_S {
+            let zoom_center = info.point_from_s(options, now_s) - info.canvas.min.x;
+            options.canvas_width_s = new_width;
+            options.sideways_pan_in_points =
+                (options.sideways_pan_in_points - zoom_center) * zoom_factor + zoom_center;
+        }
+        options.zoom_to_relative_s_range = None;
+    }
+
+    // Center the current time, keeping the zoom level
+    if home {
+        let half_width_s = options.canvas_width_s as f64 / 2.0;
+        let now_relative_s = (now_s - info.start_s) as f64;
+        options.zoom_to_relative_s_range = Some((
+            info.ctx.input(|i| i.time),
+            (now_relative_s - half_width_s, now_relative_s + half_width_s),
+        ));
+    }
+
+    // Cycle through the jobs painted during the last frame, in the order of the rows
+    if tab {
+        let mut seen = std::collections::HashSet::new();
+        let jobs: Vec<(u32, Rect)> = options
+            .painted_jobs
+            .iter()
+            .filter(|(id, _)| *id != 0 && seen.insert(*id))
+            .copied()
+            .collect();
+        if !jobs.is_empty() {
+            let current = options
+                .keyboard_job
+                .and_then(|id| jobs.iter().position(|(job_id, _)| *job_id == id));
+            let index = match current {
+                Some(index) if shift => (index + jobs.len() - 1) % jobs.len(),
+                Some(index) => (index + 1) % jobs.len(),
+                None if shift => jobs.len() - 1,
+                None => 0,
+            };
+            let (job_id, rect) = jobs[index];
+            options.keyboard_job = Some(job_id);
+
+            // Bring the job into view
+            ui.scroll_to_rect(rect, None);
+            if rect.max.x < info.canvas.min.x || rect.min.x > info.canvas.max.x {
+                options.sideways_pan_in_points +=
+                    info.canvas.min.x + 0.2 * canvas_width - rect.min.x;
+                options.zoom_to_relative_s_range = None;
+            }
+        }
+    }
+
+    // Open the details of the selected job
+    if enter {
+        if let Some(job) = options
+            .keyboard_job
+            .and_then(|id| displayed_jobs(options, app).iter().find(|job| job.id == id))
+        {
+            if !details_window.iter().any(|w| w.job.id == job.id) {
+                details_window.push(JobDetailsWindow::new(
+                    job.clone(),
+                    get_tree_structure_for_job(job, &app.all_clusters),
+                ));
+            }
+        }
+    }
+}
+
 /**
  * ThemeColors struct
  */
@@ -756,6 +1364,26 @@ fn paint_tooltip(info: &Info, options: &mut Options) {
             t!("app.details.tooltip.walltime"),
             job.walltime
         ));
+
+        // Explain each part of the job bar
+        for segment in job.get_segments(chrono::Utc::now().timestamp()) {
+            let duration = format_duration(segment.end - segment.start);
+            let explanation = match segment.kind {
+                JobSegmentKind::Predicted => t!(
+                    "app.gantt.segments.predicted",
+                    start = format_timestamp(segment.start),
+                    duration = duration
+                ),
+                JobSegmentKind::Elapsed => t!("app.gantt.segments.elapsed", duration = duration),
+                JobSegmentKind::Remaining => {
+                    t!("app.gantt.segments.remaining", duration = duration)
+                }
+            };
+            tooltip_text.push_str(&format!("\n{}", explanation));
+        }
+        if job.is_killed_at_walltime() {
+            tooltip_text.push_str(&format!("\n{}", t!("app.gantt.segments.killed")));
+        }
     }
 
     // Add resource state info if there's a hovered resource state
@@ -763,20 +1391,18 @@ fn paint_tooltip(info: &Info, options: &mut Options) {
         if !tooltip_text.is_empty() {
             tooltip_text.push_str("\n");
         }
+        // The state comes from the innermost aggregation key having a state
+        let state_key = options
+            .aggregate_by
+            .keys
+            .iter()
+            .rev()
+            .find(|key| key.has_state())
+            .copied()
+            .unwrap_or(AggregateKey::Resource);
         tooltip_text.push_str(&format!(
             "{} State: {:?}",
-            if (options.aggregate_by.level_2 == AggregateByLevel2Enum::None
-                && options.aggregate_by.level_1 == AggregateByLevel1Enum::Host)
-                || options.aggregate_by.level_2 == AggregateByLevel2Enum::Host
-            {
-                "Host"
-            } else if options.aggregate_by.level_2 == AggregateByLevel2Enum::None
-                && options.aggregate_by.level_1 == AggregateByLevel1Enum::Cluster
-            {
-                "Cluster"
-            } else {
-                "Resource"
-            },
+            state_key.get_label(),
             resource_state
         ));
         options.current_hovered_resource_state = None; // Reset for next frame
@@ -799,366 +1425,213 @@ fn paint_tooltip(info: &Info, options: &mut Options) {
 }
 
 /**
- * Paints jobs with 1 level of aggregation
+ * Header of a group, painted after the jobs when the resources are squashed
+ */
+struct GroupHeader {
+    path: Vec<String>, // Names of the group and of its parents
+    pos: Pos2,         // Position of the header
+    collapsed: bool,   // Collapsed state when the header was stored
+}
+
+/**
+ * Paints the rows of the layout intersecting the visible part of the scroll area
+ * Only the jobs overlapping the visible time span are painted, except on the rows of
+ * dead or absent resources whose hatching covers the whole width
+ * Returns true if a group was collapsed or expanded
  */
-fn paint_aggregated_jobs_level_1(
+fn paint_layout_rows(
     info: &Info,
     options: &mut Options,
-    jobs: BTreeMap<String, Vec<Job>>,
-    mut cursor_y: f32,
+    layout: &GanttLayout,
     details_window: &mut Vec<JobDetailsWindow>,
-    collapsed_jobs: &mut BTreeMap<String, bool>,
-    font_size: i32,
+    collapsed_groups: &mut BTreeMap<Vec<String>, bool>,
     all_cluster: &Vec<Cluster>,
-    aggregate_by: AggregateByLevel1Enum,
-) -> f32 {
+) -> bool {
     let theme_colors = get_theme_colors(&info.ctx.style());
-
-    let spacing_between_level_1 = 5.0 + font_size as f32; // Spacing between level 1 key
-    let spacing_between_jobs = 5.0; // Spacing between jobs
-    let offset_level_1 = 10.0; // Offset for level 1 key
-
-    cursor_y += spacing_between_level_1;
-
-    // Sort the level 1 keys
-    let mut sorted_level_1: Vec<String> = jobs.keys().cloned().collect();
-    sorted_level_1.sort_by(|a, b| compare_string_with_number(&a, &b));
-
-    let aggregation_height = font_size as f32 + 5.0 + offset_level_1;
-
-    // Store header positions and data to draw them last if squashing is enabled
-    let mut header_data = Vec::new();
-
-    // Display jobs
-    for level_1 in sorted_level_1 {
-        let job_list = jobs.get(&level_1).unwrap();
-        // Draw a line to separate
-        info.painter.line_segment(
-            [
-                pos2(info.canvas.min.x, cursor_y),
-                pos2(info.canvas.max.x, cursor_y),
-            ],
-            Stroke::new(1.5, theme_colors.aggregated_line_level_1), // More marked line
-        );
-
-        cursor_y += offset_level_1;
-
-        let text_pos = pos2(info.canvas.min.x, cursor_y);
-
-        // Check if the section is collapsed
-        let is_collapsed = collapsed_jobs.entry(level_1.clone()).or_insert(false);
-
-        // Store header info for later if squashing
-        if options.squash_resources {
-            header_data.push((level_1.clone(), text_pos, *is_collapsed));
-        } else {
-            // Paint the job info immediately if not squashing
-            paint_job_info(info, &level_1, text_pos, is_collapsed, 1);
-        }
-
-        cursor_y += spacing_between_level_1; // Spacing after the owner
-
-        let state;
-
-        // Get the state of the resource
-        if aggregate_by == AggregateByLevel1Enum::Owner {
-            state = ResourceState::Alive;
-        } else if aggregate_by == AggregateByLevel1Enum::Host {
-            state = get_host_state_from_name(all_cluster, &level_1);
-        } else {
-            state = get_cluster_state_from_name(all_cluster, &level_1);
-        }
-
-        // Only show jobs if section is not collapsed
-        if !*is_collapsed {
-            // Save the initial cursor position for squashed rendering
-            let initial_job_y = cursor_y;
-
-            for job in job_list {
-                // When squashing, use the initial Y position for all jobs in this level
-                let job_start_y = if options.squash_resources {
-                    initial_job_y
+    let top = info.canvas.top();
+    let visible = info.painter.clip_rect();
+
+    // Visible time span, with a margin for the jobs ending or starting right outside of it
+    let visible_start_s = info.start_s
+        + ((-options.sideways_pan_in_points / info.canvas.width()) * options.canvas_width_s) as i64;
+    let margin_s = (options.canvas_width_s * 0.05) as i64;
+    let span_start_s = visible_start_s - margin_s;
+    let span_end_s = visible_start_s + options.canvas_width_s as i64 + margin_s;
+
+    let mut headers = Vec::new();
+    let mut heat_strips = Vec::new();
+    let mut toggled = false;
+
+    for row in layout.visible_rows(visible.top() - top, visible.bottom() - top) {
+        match row {
+            LayoutRow::Header {
+                path,
+                line_y,
+                indent,
+                text_y,
+                ..
+            } => {
+                let level = path.len() as u8;
+
+                // Draw a line to separate, more marked for the first level
+                let line_stroke = if level == 1 {
+                    Stroke::new(1.5, theme_colors.aggregated_line_level_1)
                 } else {
-                    cursor_y
+                    Stroke::new(0.5, theme_colors.aggregated_line_level_2) // Line more discreet
                 };
-
-                // Draw the job
-                paint_job(
-                    info,
-                    options,
-                    &job,
-                    job_start_y,
-                    details_window,
-                    all_cluster,
-                    state,
-                    aggregation_height,
+                info.painter.line_segment(
+                    [
+                        pos2(info.canvas.min.x, top + line_y),
+                        pos2(info.canvas.max.x, top + line_y),
+                    ],
+                    line_stroke,
                 );
 
-                // Only increment cursor if not squashing
-                if !options.squash_resources {
-                    cursor_y += info.text_height + spacing_between_jobs + options.spacing;
+                // Either store header info for later or paint immediately
+                let text_pos = pos2(info.canvas.min.x + indent, top + text_y);
+                let collapsed = collapsed_groups.get(path).copied().unwrap_or(false);
+                if options.squash_resources {
+                    headers.push(GroupHeader {
+                        path: path.clone(),
+                        pos: text_pos,
+                        collapsed,
+                    });
+                } else {
+                    let name = path.last().cloned().unwrap_or_default();
+                    let mut is_collapsed = collapsed;
+                    paint_job_info(info, &name, text_pos, &mut is_collapsed, level);
+                    if is_collapsed != collapsed {
+                        collapsed_groups.insert(path.clone(), is_collapsed);
+                        toggled = true;
+                    }
                 }
             }
-
-            // If we're squashing, just add one line of spacing after all jobs
-            if options.squash_resources && !job_list.is_empty() {
-                cursor_y += info.text_height + spacing_between_jobs + options.spacing;
-            }
-            if !options.squash_resources {
-                cursor_y += spacing_between_level_1;
+            LayoutRow::HeatStrip { name, top_y } => heat_strips.push((name, top + top_y)),
+            LayoutRow::Jobs {
+                top_y,
+                state,
+                aggregation_height,
+                jobs,
+            } => {
+                let row_jobs: Vec<&Job> =
+                    if *state == ResourceState::Dead || *state == ResourceState::Absent {
+                        jobs.all().iter().collect()
+                    } else {
+                        jobs.overlapping(span_start_s, span_end_s).collect()
+                    };
+                for job in row_jobs {
+                    paint_job(
+                        info,
+                        options,
+                        job,
+                        top + top_y,
+                        details_window,
+                        all_cluster,
+                        *state,
+                        *aggregation_height,
+                    );
+                }
             }
         }
-        if !options.squash_resources {
-            cursor_y += spacing_between_level_1;
-        }
     }
 
-    // Now draw all headers on top if squashing is enabled
-    if options.squash_resources {
-        for (name, pos, is_collapsed) in header_data {
-            // Create a background to make the text more readable
-            let galley = info.ctx.fonts(|f| {
-                let collapsed_symbol = if is_collapsed { "⏵" } else { "⏷" };
-                let label = format!("{} {}", collapsed_symbol, name);
-                f.layout_no_wrap(label, info.font_id.clone(), theme_colors.text_dim)
-            });
-            let rect = Rect::from_min_size(pos, galley.size());
-            info.painter
-                .rect_filled(rect.expand(4.0), 4.0, theme_colors.background_timeline);
-
-            // Then paint the job info
-            let mut is_collapsed_copy = is_collapsed;
-            paint_job_info(info, &name, pos, &mut is_collapsed_copy, 1);
-            // Update the real collapsed state if changed
-            if is_collapsed_copy != is_collapsed {
-                *collapsed_jobs.get_mut(&name).unwrap() = is_collapsed_copy;
-            }
-        }
+    // Draw the heat strips and the headers on top of the squashed jobs
+    for (name, y) in heat_strips {
+        paint_heat_strip(info, options, name, y);
     }
-
-    cursor_y
+    toggled | paint_group_headers(info, headers, collapsed_groups)
 }
 
 /**
- * Paints jobs with 2 levels of aggregation
+ * Paints the occupation of a first level group, one colored bucket per time span
+ * Hovering a bucket shows the number of busy resources of the group
  */
-fn paint_aggregated_jobs_level_2(
-    info: &Info,
-    options: &mut Options,
-    jobs: BTreeMap<String, BTreeMap<String, Vec<Job>>>,
-    mut cursor_y: f32,
-    details_window: &mut Vec<JobDetailsWindow>,
-    collapsed_jobs_level_1: &mut BTreeMap<String, bool>,
-    collapsed_jobs_level_2: &mut BTreeMap<(String, String), bool>,
-    font_size: i32,
-    all_cluster: &Vec<Cluster>,
-    aggregate_by_level_1: AggregateByLevel1Enum,
-    aggregate_by_level_2: AggregateByLevel2Enum,
-) -> f32 {
-    let theme_colors = get_theme_colors(&info.ctx.style());
-
-    let spacing_between_level_1 = font_size as f32; // Spacing between level 1 key
-    let spacing_between_level_2 = font_size as f32 + 5.0; // Spacing between level 2 key
-    let spacing_between_jobs = 5.0; // Spacing between jobs
-    let offset_level_1 = 10.0; // Offset for level 1 key
-
-    cursor_y += spacing_between_level_1;
-
-    // Sort the level 1 keys
-    let mut sorted_level_1: Vec<String> = jobs.keys().cloned().collect();
-    sorted_level_1.sort_by(|a, b| compare_string_with_number(&a, &b));
-
-    // Store header positions and data to draw them last if squashing is enabled
-    let mut header_data_level_1 = Vec::new();
-    let mut header_data_level_2 = Vec::new();
-
-    for level_1 in sorted_level_1 {
-        let level_2_map = jobs.get(&level_1).unwrap();
-        let level_1_key = level_1.clone();
-
-        // Draw a line to separate
-        info.painter.line_segment(
-            [
-                pos2(info.canvas.min.x, cursor_y),
-                pos2(info.canvas.max.x, cursor_y),
-            ],
-            Stroke::new(1.5, theme_colors.aggregated_line_level_1), // More marked line
+fn paint_heat_strip(info: &Info, options: &Options, name: &str, y: f32) {
+    let Some(usage) = options.heat_strip.usage(name) else {
+        return;
+    };
+    if usage.total == 0 {
+        return;
+    }
+    let (first_bucket_s, bucket_s) = options.heat_strip.buckets();
+    let hover_pos = info.response.hover_pos();
+
+    for (index, busy) in usage.busy.iter().enumerate() {
+        let start_s = first_bucket_s + index as i64 * bucket_s;
+        let rect = Rect::from_x_y_ranges(
+            info.point_from_s(options, start_s)..=info.point_from_s(options, start_s + bucket_s),
+            y..=y + GanttHeatStrip::HEIGHT,
         );
-
-        cursor_y += offset_level_1;
-
-        let text_pos = pos2(info.canvas.min.x, cursor_y);
-
-        // Check if the level 1 is collapsed
-        let is_collapsed_level_1 = collapsed_jobs_level_1
-            .entry(level_1.clone())
-            .or_insert(false);
-
-        // Either store header info for later or paint immediately
-        if options.squash_resources {
-            header_data_level_1.push((level_1.clone(), text_pos, *is_collapsed_level_1));
+        let busy = (*busy).min(usage.total);
+        let fraction = busy as f32 / usage.total as f32;
+        let color = if busy == 0 {
+            heat_color(0.0).gamma_multiply(0.3)
         } else {
-            // Paint the job info immediately if not squashing
-            paint_job_info(info, &level_1, text_pos, is_collapsed_level_1, 1);
-        }
-
-        cursor_y += spacing_between_level_1;
-
-        // Only show jobs if section is not collapsed
-        if !*is_collapsed_level_1 {
-            // Sort the level 2 keys
-            let mut sorted_level_2: Vec<_> = level_2_map.keys().collect();
-            sorted_level_2.sort_by(|a, b| compare_string_with_number(&a, &b));
-
-            // Display level 2
-            for level_2 in sorted_level_2 {
-                if let Some(job_list) = level_2_map.get(level_2) {
-                    // Draw a line to separate
-                    info.painter.line_segment(
-                        [
-                            pos2(info.canvas.min.x, cursor_y),
-                            pos2(info.canvas.max.x, cursor_y),
-                        ],
-                        Stroke::new(0.5, theme_colors.aggregated_line_level_2), // Line more discreet
-                    );
-
-                    cursor_y += spacing_between_level_2;
-
-                    let text_pos = pos2(info.canvas.min.x + 20.0, cursor_y);
-
-                    // Check if the level 2 is collapsed
-                    let is_collapsed_level_2 = collapsed_jobs_level_2
-                        .entry((level_1_key.to_string(), level_2.to_string()))
-                        .or_insert(false);
-
-                    // Either store header info for later or paint immediately
-                    if options.squash_resources {
-                        header_data_level_2.push((
-                            level_1_key.clone(),
-                            level_2.to_string(),
-                            text_pos,
-                            *is_collapsed_level_2,
-                        ));
-                    } else {
-                        // Paint the job info immediately if not squashing
-                        paint_job_info(
-                            info,
-                            &level_2.to_string(),
-                            text_pos,
-                            is_collapsed_level_2,
-                            2,
-                        );
-                    }
-
-                    cursor_y += spacing_between_level_2;
-
-                    let state;
-
-                    // Get the state of the resource
-                    if aggregate_by_level_2 == AggregateByLevel2Enum::Host {
-                        state = get_host_state_from_name(all_cluster, &level_2);
-                    } else if aggregate_by_level_2 == AggregateByLevel2Enum::None {
-                        if aggregate_by_level_1 == AggregateByLevel1Enum::Host {
-                            state = get_host_state_from_name(all_cluster, &level_1);
-                        } else if aggregate_by_level_1 == AggregateByLevel1Enum::Cluster {
-                            state = get_cluster_state_from_name(all_cluster, &level_1);
-                        } else {
-                            state = ResourceState::Alive;
-                        }
-                    } else {
-                        state = ResourceState::Alive;
-                    }
-
-                    // Only show jobs if section is not collapsed
-                    if !*is_collapsed_level_2 {
-                        // Save the initial cursor position for squashed rendering
-                        let initial_job_y = cursor_y;
-
-                        // Display jobs
-                        for job in job_list.iter() {
-                            // When squashing, use the initial Y position for all jobs in this level
-                            let job_start_y = if options.squash_resources {
-                                initial_job_y
-                            } else {
-                                cursor_y
-                            };
-
-                            // Adjust aggregation height for the first job of the second level
-                            let adjusted_aggregation_height = spacing_between_level_2 * 2.0;
-
-                            // Draw the job
-                            paint_job(
-                                info,
-                                options,
-                                job,
-                                job_start_y,
-                                details_window,
-                                all_cluster,
-                                state,
-                                adjusted_aggregation_height,
-                            );
-
-                            // Only increment cursor if not squashing
-                            if !options.squash_resources && !job_list.is_empty() {
-                                cursor_y +=
-                                    info.text_height + spacing_between_jobs + options.spacing;
-                            }
-                        }
+            heat_color(fraction)
+        };
+        info.painter.rect_filled(rect, 0.0, color);
 
-                        // If we're squashing, just add one line of spacing after all jobs
-                        if options.squash_resources {
-                            cursor_y += info.text_height + spacing_between_jobs + options.spacing;
-                        }
-                    }
-                    if !options.squash_resources {
-                        cursor_y += spacing_between_level_2;
-                    }
-                }
-            }
-        }
-        if !options.squash_resources {
-            cursor_y += spacing_between_level_1;
+        if hover_pos.is_some_and(|pos| rect.contains(pos)) {
+            egui::show_tooltip_at_pointer(
+                &info.ctx,
+                info.response.layer_id,
+                egui::Id::new("heat_strip_tooltip"),
+                |ui| {
+                    ui.label(t!(
+                        "app.gantt.heat_strip.tooltip",
+                        name = name,
+                        busy = busy,
+                        total = usage.total,
+                        percent = format!("{:.0}", fraction * 100.0),
+                        start = format_timestamp(start_s),
+                        end = format_timestamp(start_s + bucket_s)
+                    ));
+                },
+            );
         }
     }
+}
 
-    // Now draw all headers on top if squashing is enabled
-    if options.squash_resources {
-        // First, draw level 1 headers
-        for (name, pos, is_collapsed) in header_data_level_1 {
-            // Create a background to make the text more readable
+/**
+ * Paints the headers stored while squashing, from the first level to the last one
+ * Returns true if a group was collapsed or expanded
+ */
+fn paint_group_headers(
+    info: &Info,
+    mut headers: Vec<GroupHeader>,
+    collapsed_groups: &mut BTreeMap<Vec<String>, bool>,
+) -> bool {
+    let mut toggled = false;
+    let theme_colors = get_theme_colors(&info.ctx.style());
+    headers.sort_by_key(|header| header.path.len());
+
+    for header in headers {
+        let level = header.path.len() as u8;
+        let name = header.path.last().cloned().unwrap_or_default();
+
+        // Create a background to make the first level text more readable
+        if level == 1 {
             let galley = info.ctx.fonts(|f| {
-                let collapsed_symbol = if is_collapsed { "⏵" } else { "⏷" };
+                let collapsed_symbol = if header.collapsed { "⏵" } else { "⏷" };
                 let label = format!("{} {}", collapsed_symbol, name);
                 f.layout_no_wrap(label, info.font_id.clone(), theme_colors.text_dim)
             });
-            let rect = Rect::from_min_size(pos, galley.size());
+            let rect = Rect::from_min_size(header.pos, galley.size());
             info.painter
                 .rect_filled(rect.expand(4.0), 4.0, theme_colors.background_timeline);
-
-            // Then paint the job info
-            let mut is_collapsed_copy = is_collapsed;
-            paint_job_info(info, &name, pos, &mut is_collapsed_copy, 1);
-            // Update the real collapsed state if changed
-            if is_collapsed_copy != is_collapsed {
-                *collapsed_jobs_level_1.get_mut(&name).unwrap() = is_collapsed_copy;
-            }
         }
 
-        // Then draw level 2 headers on top of everything
-        for (level_1_key, level_2_key, pos, is_collapsed) in header_data_level_2 {
-            // Then paint the job info
-            let mut is_collapsed_copy = is_collapsed;
-            paint_job_info(info, &level_2_key, pos, &mut is_collapsed_copy, 2);
-            // Update the real collapsed state if changed
-            if is_collapsed_copy != is_collapsed {
-                *collapsed_jobs_level_2
-                    .get_mut(&(level_1_key, level_2_key))
-                    .unwrap() = is_collapsed_copy;
-            }
+        // Then paint the job info
+        let mut is_collapsed = header.collapsed;
+        paint_job_info(info, &name, header.pos, &mut is_collapsed, level);
+        // Update the real collapsed state if changed
+        if is_collapsed != header.collapsed {
+            collapsed_groups.insert(header.path, is_collapsed);
+            toggled = true;
         }
     }
 
-    cursor_y
+    toggled
 }
 
 #[derive(PartialEq)]
@@ -1182,14 +1655,13 @@ fn paint_job(
     aggregation_height: f32,
 ) -> PaintResult {
     let theme_colors = get_theme_colors(&info.ctx.style());
-    let start_x = info.point_from_s(options, job.scheduled_start); // Start time
-    // If the job is not finished, we use the scheduled start time + walltime otherwise we use the stop time
-    let stop_time = if job.stop_time > 0 {
-        job.stop_time
-    } else {
-        job.scheduled_start + job.walltime
+    // The job is split into its predicted, elapsed and remaining parts
+    let segments = job.get_segments(chrono::Utc::now().timestamp());
+    let (Some(first_segment), Some(last_segment)) = (segments.first(), segments.last()) else {
+        return PaintResult::Culled;
     };
-    let end_x = info.point_from_s(options, stop_time);
+    let start_x = info.point_from_s(options, first_segment.start); // Start time
+    let end_x = info.point_from_s(options, last_segment.end); // End of the last part
     let width = end_x - start_x; // Width of the job
 
     if width < options.cull_width {
@@ -1262,8 +1734,14 @@ fn paint_job(
         }
     }
 
+    // Shift-click selects the job instead of zooming to it
+    let shift = info.ctx.input(|i| i.modifiers.shift);
+    if is_job_trully_hovered && shift && info.response.clicked() {
+        options.selection.request_toggle(job.id);
+    }
+
     // Zoom to job if clicked
-    if is_job_hovered && info.response.clicked() {
+    if is_job_hovered && info.response.clicked() && !shift {
         // Zoom to job
         let job_duration_s = job.walltime as f64;
         let job_start_s = job.scheduled_start as f64;
@@ -1282,11 +1760,7 @@ fn paint_job(
     }
 
     // Caculate the color of the job depending the selected job color int gantt component
-    let (hovered_color, normal_color) = if options.job_color.is_random() {
-        job.get_gantt_color()
-    } else {
-        job.state.get_color()
-    };
+    let (hovered_color, normal_color) = options.job_color.get_job_colors(job);
 
     // If the job is hovered, we make it brighter
     let fill_color = if is_job_hovered {
@@ -1295,8 +1769,69 @@ fn paint_job(
         normal_color
     };
 
-    // Paint the job rectangle
-    info.painter.rect_filled(rect, rounding, fill_color);
+    // Paint the job rectangle, one part after the other
+    paint_job_segments(info, options, &segments, rect, rounding, fill_color);
+    options.painted_jobs.push((job.id, rect));
+
+    // Mark the end of a job killed when reaching its walltime
+    if job.is_killed_at_walltime() {
+        let marker_color = Color32::from_rgb(200, 0, 0);
+        info.painter.line_segment(
+            [
+                pos2(rect.right(), rect.top() - 2.0),
+                pos2(rect.right(), rect.bottom() + 2.0),
+            ],
+            Stroke::new(2.5, marker_color),
+        );
+        info.painter.add(Shape::convex_polygon(
+            vec![
+                pos2(rect.right() - 4.0, rect.top() - 5.0),
+                pos2(rect.right() + 4.0, rect.top() - 5.0),
+                pos2(rect.right(), rect.top() + 1.0),
+            ],
+            marker_color,
+            Stroke::NONE,
+        ));
+    }
+
+    // Outline the selected jobs
+    if options.selection.is_selected(job.id) {
+        info.painter.rect_stroke(
+            rect.expand(1.5),
+            rounding,
+            Stroke::new(2.5, info.ctx.style().visuals.selection.stroke.color),
+        );
+    }
+
+    // Outline the job selected with the keyboard
+    if options.keyboard_job == Some(job.id) {
+        info.painter.rect_stroke(
+            rect.expand(2.0),
+            rounding,
+            Stroke::new(2.0, theme_colors.line),
+        );
+    }
+
+    // Pulse the job focused by the search
+    if let Some(focused) = options
+        .focused_job
+        .as_mut()
+        .filter(|focused| focused.job_id == job.id)
+    {
+        if focused.rect.is_none() {
+            focused.rect = Some(rect);
+        }
+        let elapsed = info.ctx.input(|i| i.time) - focused.since;
+        if elapsed < FocusedJob::PULSE_DURATION {
+            let alpha = 0.5 + 0.5 * (elapsed * std::f64::consts::TAU * 1.5).cos() as f32;
+            info.painter.rect_stroke(
+                rect.expand(3.0),
+                rounding,
+                Stroke::new(3.0, Color32::from_rgb(255, 200, 0).gamma_multiply(alpha)),
+            );
+            info.ctx.request_repaint();
+        }
+    }
 
     // Paint ressource hatch
     if state == ResourceState::Dead || state == ResourceState::Absent {
@@ -1391,6 +1926,86 @@ fn paint_job(
     }
 }
 
+/**
+ * Paints the parts of a job inside its rectangle
+ * The predicted run is hatched, the elapsed run is solid and the remaining walltime is faded
+ */
+fn paint_job_segments(
+    info: &Info,
+    options: &Options,
+    segments: &[JobSegment],
+    rect: Rect,
+    rounding: f32,
+    color: Color32,
+) {
+    let nb_segments = segments.len();
+    for (index, segment) in segments.iter().enumerate() {
+        let (is_first, is_last) = (index == 0, index + 1 == nb_segments);
+
+        // The outer edges follow the job rectangle, which has a minimum width
+        let left = if is_first {
+            rect.left()
+        } else {
+            info.point_from_s(options, segment.start)
+        };
+        let right = if is_last {
+            rect.right()
+        } else {
+            info.point_from_s(options, segment.end)
+        };
+        let segment_rect = Rect::from_x_y_ranges(left..=right.max(left), rect.y_range());
+
+        // Only the outer corners are rounded
+        let corner = |rounded: bool| if rounded { rounding } else { 0.0 };
+        let segment_rounding = egui::Rounding {
+            nw: corner(is_first),
+            sw: corner(is_first),
+            ne: corner(is_last),
+            se: corner(is_last),
+        };
+
+        match segment.kind {
+            JobSegmentKind::Elapsed => {
+                info.painter
+                    .rect_filled(segment_rect, segment_rounding, color);
+            }
+            JobSegmentKind::Remaining => {
+                info.painter.rect_filled(
+                    segment_rect,
+                    segment_rounding,
+                    color.gamma_multiply(0.35),
+                );
+            }
+            JobSegmentKind::Predicted => {
+                info.painter
+                    .rect_filled(segment_rect, segment_rounding, color.gamma_multiply(0.2));
+
+                // Diagonal hatching, only on the visible part of the segment
+                let clip_rect = segment_rect.intersect(info.painter.clip_rect());
+                let painter = info.painter.with_clip_rect(clip_rect);
+                let spacing = 6.0;
+                let height = segment_rect.height();
+                let mut x = segment_rect.left() - height;
+                if clip_rect.left() - height > x {
+                    x += ((clip_rect.left() - height - x) / spacing).floor() * spacing;
+                }
+                while x < clip_rect.right() {
+                    painter.line_segment(
+                        [
+                            pos2(x, segment_rect.bottom()),
+                            pos2(x + height, segment_rect.top()),
+                        ],
+                        Stroke::new(1.5, color),
+                    );
+                    x += spacing;
+                }
+                info.painter
+                    .rect_stroke(segment_rect, segment_rounding, Stroke::new(1.0, color));
+            }
+        }
+    }
+}
+
 /**
  * Paints a job info appearing on the left side of the canvas
  */
@@ -1644,6 +2259,117 @@ fn paint_current_time_line(info: &Info, options: &Options, canvas: Rect) -> egui
     )
 }
 
+/**
+ * Paints the annotations overlapping the visible time span, as shaded bands for the
+ * periods and as markers for the instants, with their label and a tooltip
+ * A scoped annotation only covers the rows of its clusters and hosts, or the whole
+ * height when none of them is shown with the current aggregation
+ */
+fn paint_annotations(
+    info: &Info,
+    options: &Options,
+    layout: Option<&GanttLayout>,
+    annotations: &Annotations,
+    canvas: Rect,
+) {
+    let visible = info.painter.clip_rect();
+    let visible_start_s = info.start_s
+        + ((-options.sideways_pan_in_points / info.canvas.width()) * options.canvas_width_s) as i64;
+    let visible_end_s = visible_start_s + options.canvas_width_s as i64;
+    let label_min_y = visible.top() + info.text_height + 8.0; // Under the fixed timeline
+    let hover_pos = info.response.hover_pos();
+    let mut hovered = Vec::new();
+
+    for annotation in annotations.overlapping(visible_start_s, visible_end_s) {
+        let color = annotation.kind.get_color();
+        let start_x = info.point_from_s(options, annotation.start);
+        let end_x = info.point_from_s(options, annotation.get_end());
+
+        let mut extents = match layout {
+            Some(layout) if !annotation.is_global() => {
+                layout.group_extents(&annotation.scope_names())
+            }
+            _ => Vec::new(),
+        };
+        if extents.is_empty() {
+            extents.push((0.0, canvas.height()));
+        }
+
+        for (top, bottom) in extents {
+            let rect = Rect::from_x_y_ranges(
+                start_x..=end_x,
+                (canvas.top() + top)..=(canvas.top() + bottom),
+            );
+            if !rect.expand(3.0).intersects(visible) {
+                continue;
+            }
+
+            if annotation.end.is_some() {
+                info.painter.rect(
+                    rect,
+                    0.0,
+                    color.gamma_multiply(0.15),
+                    Stroke::new(1.0, color.gamma_multiply(0.6)),
+                );
+            } else {
+                // An instant is a vertical marker with a triangle on top
+                let marker_top = rect.top().max(label_min_y);
+                info.painter.line_segment(
+                    [pos2(start_x, rect.top()), pos2(start_x, rect.bottom())],
+                    Stroke::new(2.0, color),
+                );
+                info.painter.add(Shape::convex_polygon(
+                    vec![
+                        pos2(start_x - 5.0, marker_top),
+                        pos2(start_x + 5.0, marker_top),
+                        pos2(start_x, marker_top + 6.0),
+                    ],
+                    color,
+                    Stroke::NONE,
+                ));
+            }
+
+            // Label at the top of the visible part of the band
+            let label_y = rect.top().max(label_min_y);
+            if label_y < rect.bottom() {
+                info.painter.text(
+                    pos2(start_x.max(visible.left()) + 6.0, label_y),
+                    Align2::LEFT_TOP,
+                    &annotation.label,
+                    info.font_id.clone(),
+                    color,
+                );
+            }
+
+            if hover_pos.is_some_and(|pos| rect.expand2(egui::vec2(3.0, 0.0)).contains(pos)) {
+                hovered.push(annotation);
+            }
+        }
+    }
+
+    // The tooltip of the jobs takes precedence
+    if hovered.is_empty() || options.previous_hovered_job.is_some() {
+        return;
+    }
+    hovered.dedup_by_key(|annotation| annotation.id);
+    egui::show_tooltip_at_pointer(
+        &info.ctx,
+        info.response.layer_id,
+        egui::Id::new("annotation_tooltip"),
+        |ui| {
+            ui.set_max_width(500.0);
+            for annotation in hovered {
+                ui.colored_label(annotation.kind.get_color(), annotation.kind.get_label());
+                ui.strong(&annotation.label);
+                ui.label(describe_period(annotation));
+                if !annotation.description.is_empty() {
+                    ui.label(&annotation.description);
+                }
+            }
+        },
+    );
+}
+
 /**
  * Converts a timestamp to a string
  */
@@ -1651,12 +2377,7 @@ fn grid_text(ts: i64) -> String {
     if ts == 0 {
         "N/A".to_string()
     } else {
-        if let Some(dt) = DateTime::from_timestamp(ts, 0) {
-            dt.with_timezone(&Local)
-                .format("%Y-%m-%d %H:%M:%S")
-                .to_string()
-        } else {
-            "Invalid timestamp".to_string()
-        }
+        format_timestamp_as(ts, "%Y-%m-%d %H:%M:%S")
+            .unwrap_or_else(|| "Invalid timestamp".to_string())
     }
 }
@@ -1,3 +1,4 @@
 pub mod dashboard;
 pub mod gantt;
-pub mod anthentification;
\ No newline at end of file
+pub mod anthentification;
+pub mod accounting;
\ No newline at end of file
@@ -1,7 +1,11 @@
 use crate::models::utils::utils::compare_string_with_number;
 
 use crate::models::data_structure::{
-    application_context::ApplicationContext, cluster::Cluster, filters::JobFilters, job::JobState,
+    application_context::ApplicationContext,
+    cluster::Cluster,
+    filters::{JobFilters, SelectionState},
+    host::Host,
+    job::JobState,
 };
 use eframe::egui::{self, Grid};
 use egui::ScrollArea;
@@ -71,17 +75,36 @@ impl Filtering {
                         self.render_cluster_menu(ui, app);
                     });
 
+                    // Restriction to the jobs selected in the Gantt chart
+                    if let Some(nb_selected) = app.filters.job_ids.as_ref().map(|ids| ids.len()) {
+                        ui.add_space(10.0);
+                        ui.horizontal(|ui| {
+                            ui.label(t!("app.filter.selected_jobs", count = nb_selected));
+                            if ui.button(t!("app.filter.remove_selection")).clicked() {
+                                app.filters.job_ids = None;
+                                app.filter_jobs();
+                                app.jobs_generation += 1;
+                            }
+                        });
+                    }
+
                     ui.add_space(20.0);
 
                     ui.horizontal(|ui| {
                         if ui.button(t!("app.filters.apply")).clicked() {
-                            app.filters = JobFilters::copy(&self.temp_filters); // add the temporary filters to the app filters
+                            // add the temporary filters to the app filters, keeping the jobs selected in the Gantt
+                            app.filters = JobFilters {
+                                job_ids: app.filters.job_ids.take(),
+                                ..JobFilters::copy(&self.temp_filters)
+                            };
                             app.filter_jobs(); // Filter the jobs
+                            app.jobs_generation += 1; // The resources kept for each job may have changed
                             self.open = false; // Close the window
                         }
                         if ui.button(t!("app.filters.reset")).clicked() {
                             self.reset_filters(); // Reset the filters
                             app.filters = JobFilters::default();
+                            app.jobs_generation += 1;
                         }
                     });
                 });
@@ -162,32 +185,24 @@ impl Filtering {
     /*
      * Render the cluster menu
      * This menu is used to select the clusters on which the jobs will be filtered
+     * A cluster checkbox is indeterminate when only some of its hosts or resources are selected
      */
     fn render_cluster_menu(&mut self, ui: &mut egui::Ui, app: &mut ApplicationContext) {
         ui.set_max_width(124.0);
 
         for cluster in &app.all_clusters {
             ui.horizontal(|ui| {
-                let mut is_selected = self
-                    .temp_filters
-                    .clusters
-                    .as_ref()
-                    .map_or(false, |clusters| {
-                        clusters.iter().any(|c| c.name == cluster.name)
-                    });
+                let selection = self.temp_filters.cluster_selection(cluster);
+                let mut is_selected = selection == SelectionState::Selected;
 
-                if ui.checkbox(&mut is_selected, "").changed() {
-                    if is_selected {
-                        if let Some(clusters) = &mut self.temp_filters.clusters {
-                            clusters.push(cluster.clone());
-                        } else {
-                            self.temp_filters.clusters = Some(vec![cluster.clone()]);
-                        }
-                    } else {
-                        if let Some(clusters) = &mut self.temp_filters.clusters {
-                            clusters.retain(|c| c.name != cluster.name);
-                        }
-                    }
+                if ui
+                    .add(
+                        egui::Checkbox::new(&mut is_selected, "")
+                            .indeterminate(selection == SelectionState::Partial),
+                    )
+                    .changed()
+                {
+                    self.temp_filters.set_cluster_selected(cluster, is_selected);
                 }
 
                 ui.label(&cluster.name);
@@ -208,19 +223,13 @@ impl Filtering {
     fn render_host_menu(&mut self, ui: &mut egui::Ui, cluster: &Cluster) {
         ui.set_max_width(300.0);
 
-        let mut hosts = cluster.hosts.clone();
+        let mut hosts: Vec<&Host> = cluster.hosts.iter().collect();
         hosts.sort_by(|a, b| compare_string_with_number(&a.name, &b.name));
 
-        let mut selected_cluster = self
-            .temp_filters
-            .clusters
-            .as_mut()
-            .and_then(|clusters| clusters.iter_mut().find(|c| c.name == cluster.name));
-
-        if let Some(cluster) = selected_cluster.as_mut() {
-            if ui.button("Deselect All").clicked() {
-                cluster.hosts.clear();
-            }
+        if self.temp_filters.cluster_selection(cluster) != SelectionState::Unselected
+            && ui.button("Deselect All").clicked()
+        {
+            self.temp_filters.set_cluster_selected(cluster, false);
         }
 
         ScrollArea::vertical()
@@ -228,18 +237,60 @@ impl Filtering {
             .max_height(250.0)
             .show(ui, |ui| {
                 for host in hosts {
-                    let mut is_selected = selected_cluster
-                        .as_ref()
-                        .map_or(false, |c| c.hosts.iter().any(|h| h.name == host.name));
-
-                    if ui.checkbox(&mut is_selected, &host.name).changed() {
-                        if let Some(cluster) = selected_cluster.as_mut() {
-                            if is_selected {
-                                cluster.hosts.push(host.clone());
-                            } else {
-                                cluster.hosts.retain(|h| h.name != host.name);
-                            }
+                    ui.horizontal(|ui| {
+                        let selection = self.temp_filters.host_selection(&cluster.name, host);
+                        let mut is_selected = selection == SelectionState::Selected;
+
+                        if ui
+                            .add(
+                                egui::Checkbox::new(&mut is_selected, &host.name)
+                                    .indeterminate(selection == SelectionState::Partial),
+                            )
+                            .changed()
+                        {
+                            self.temp_filters
+                                .set_host_selected(cluster, host, is_selected);
                         }
+
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.menu_button(" ", |ui| {
+                                self.render_resource_menu(ui, cluster, host);
+                            });
+                        });
+                    });
+                }
+            });
+    }
+
+    /*
+     * Render the resource menu
+     * This menu is used to select single resources of a host on which the jobs will be filtered
+     */
+    fn render_resource_menu(&mut self, ui: &mut egui::Ui, cluster: &Cluster, host: &Host) {
+        ui.set_max_width(200.0);
+
+        let mut resource_ids = host.resource_ids.clone();
+        resource_ids.sort();
+
+        ScrollArea::vertical()
+            .min_scrolled_height(50.0)
+            .max_height(250.0)
+            .show(ui, |ui| {
+                for resource_id in resource_ids {
+                    let mut is_selected =
+                        self.temp_filters
+                            .is_resource_selected(&cluster.name, &host.name, resource_id);
+
+                    if ui
+                        .checkbox(&mut is_selected, resource_id.to_string())
+                        .changed()
+                    {
+                        self.temp_filters.set_resource_selected(
+                            cluster,
+                            host,
+                            resource_id,
+                            is_selected,
+                        );
                     }
                 }
             });
@@ -1,6 +1,7 @@
 use crate::models::data_structure::application_options::{
-    ApplicationOptions, LanguageOption, ThemeOption,
+    ApplicationOptions, LanguageOption, ThemeOption, TimezoneOption,
 };
+use crate::models::utils::date_converter::set_display_timezone;
 use eframe::egui::{self};
 use std::time::{Duration, Instant};
 
@@ -59,9 +60,14 @@ impl Options {
     pub fn apply_options(&self, ctx: &egui::Context, app_font_size: &mut i32) {
         self.apply_theme(ctx);
         self.apply_language();
+        self.apply_timezone();
         self.apply_font_size(ctx, app_font_size);
     }
 
+    fn apply_timezone(&self) {
+        set_display_timezone(self.application_options.selected_timezone.to_tz());
+    }
+
     fn apply_theme(&self, ctx: &egui::Context) {
         let new_visuals = match self.application_options.selected_theme {
             ThemeOption::Dark => egui::Visuals::dark(),
@@ -119,6 +125,7 @@ impl Options {
                     .show(ui, |ui| {
                         self.render_theme_selector(ui);
                         self.render_language_selector(ui);
+                        self.render_timezone_selector(ui);
                         self.render_font_size_selector(ui, app_font_size);
                     });
 
@@ -164,6 +171,52 @@ impl Options {
         ui.end_row();
     }
 
+    fn render_timezone_selector(&mut self, ui: &mut egui::Ui) {
+        ui.label(t!("app.options.timezone.title"));
+        let selected_text = match &self.application_options.selected_timezone {
+            TimezoneOption::Local => t!("app.options.timezone.local").to_string(),
+            TimezoneOption::Utc => "UTC".to_string(),
+            TimezoneOption::Named(name) => name.clone(),
+        };
+        egui::ComboBox::from_label(t!("app.options.timezone.choose"))
+            .selected_text(selected_text)
+            .height(300.0)
+            .show_ui(ui, |ui| {
+                let mut timezones = vec![
+                    (
+                        TimezoneOption::Local,
+                        t!("app.options.timezone.local").to_string(),
+                    ),
+                    (TimezoneOption::Utc, "UTC".to_string()),
+                ];
+                timezones.extend(
+                    chrono_tz::TZ_VARIANTS
+                        .iter()
+                        .filter(|timezone| **timezone != chrono_tz::Tz::UTC)
+                        .map(|timezone| {
+                            (
+                                TimezoneOption::Named(timezone.name().to_string()),
+                                timezone.name().to_string(),
+                            )
+                        }),
+                );
+
+                for (timezone, label) in timezones {
+                    if ui
+                        .selectable_value(
+                            &mut self.application_options.selected_timezone,
+                            timezone,
+                            label,
+                        )
+                        .clicked()
+                    {
+                        self.apply_timezone();
+                    }
+                }
+            });
+        ui.end_row();
+    }
+
     fn render_font_size_selector(&mut self, ui: &mut egui::Ui, app_font_size: &mut i32) {
         ui.label(t!("app.options.font_size.title"));
         egui::ComboBox::from_label(t!("app.options.font_size.choose"))
@@ -4,6 +4,9 @@ use crate::models::data_structure::resource::ResourceState;
 use crate::models::utils::utils::get_all_clusters;
 use crate::models::utils::utils::get_all_hosts;
 use crate::models::utils::utils::get_all_resources;
+use crate::views::components::annotation_editor::AnnotationEditor;
+use crate::views::components::comparison_picker::ComparisonPicker;
+use crate::views::components::time_range_picker::TimeRangePicker;
 use crate::views::menu::tools::egui::Color32;
 use crate::{
     models::data_structure::application_context::ApplicationContext,
@@ -15,12 +18,18 @@ use super::filtering::Filtering;
 
 pub struct Tools {
     filtering_pane: Filtering,
+    time_range_picker: TimeRangePicker,
+    annotation_editor: AnnotationEditor,
+    comparison_picker: ComparisonPicker,
 }
 
 impl Default for Tools {
     fn default() -> Self {
         Tools {
             filtering_pane: Filtering::default(),
+            time_range_picker: TimeRangePicker::default(),
+            annotation_editor: AnnotationEditor::default(),
+            comparison_picker: ComparisonPicker::default(),
         }
     }
 }
@@ -50,7 +59,8 @@ impl View for Tools {
             }
 
             // Gantt Button
-            let gantt_btn = egui::Button::new("📅 Gantt").frame(!is_dashboard_selected);
+            let is_gantt_selected = matches!(app.view_type, ViewType::Gantt);
+            let gantt_btn = egui::Button::new("📅 Gantt").frame(is_gantt_selected);
             if ui.add(gantt_btn).clicked() {
                 app.view_type = ViewType::Gantt;
                 ui.close_menu();
@@ -68,6 +78,7 @@ impl View for Tools {
                         command: String::new(),
                         message: None,
                         queue: String::new(),
+                        project: String::new(),
                         assigned_resources: get_all_resources(&app.all_clusters),
                         submission_time: 0,
                         start_time: 0,
@@ -79,6 +90,15 @@ impl View for Tools {
                 }
             }
 
+            // Accounting Button
+            let is_accounting_selected = matches!(app.view_type, ViewType::Accounting);
+            let accounting_btn =
+                egui::Button::new("🧾 Accounting").frame(is_accounting_selected);
+            if ui.add(accounting_btn).clicked() {
+                app.view_type = ViewType::Accounting;
+                ui.close_menu();
+            }
+
             // On the right side
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 let refresh_btn = egui::Button::new("⟳");
@@ -124,10 +144,34 @@ impl View for Tools {
                 if ui.add(filters_btn).clicked() {
                     self.filtering_pane.open();
                 }
+
+                // Annotations Window
+                let annotations_btn =
+                    egui::Button::new("📌 ".to_string() + &t!("app.menu.annotations")).frame(true);
+                if ui.add(annotations_btn).clicked() {
+                    self.annotation_editor.open();
+                }
+
+                // Time range picker, the Gantt zooms to the chosen range
+                let current_range = (app.get_start_date(), app.get_end_date());
+                if let Some((start, end)) = self.time_range_picker.ui(ui, current_range) {
+                    app.set_localdate(start, end);
+                    app.requested_time_range = Some((start, end));
+                }
+
+                // Window compared with the current one, in the Gantt and the dashboard
+                self.comparison_picker.ui(ui, &mut app.comparison_offset_s);
+
+                // Periods missing from the cache being loaded in the background
+                if app.job_cache.is_fetching(chrono::Utc::now().timestamp()) {
+                    ui.spinner()
+                        .on_hover_text(t!("app.menu.time_range.loading"));
+                }
             });
 
             // Show External Window
             self.filtering_pane.ui(ui, app);
+            self.annotation_editor.ui(ui, app);
         });
     }
 }